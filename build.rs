@@ -1,5 +1,5 @@
 use embed_manifest::{embed_manifest, new_manifest};
-use time::{macros::format_description, OffsetDateTime};
+use time::{format_description::well_known::Rfc3339, macros::format_description, OffsetDateTime};
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
@@ -21,4 +21,10 @@ fn main() {
         .expect("failed to format build timestamp");
 
     println!("cargo:rustc-env=MD_BUILD_TIMESTAMP={}", formatted);
+
+    // Same instant as RFC 3339, for `--version --timestamp-format iso`.
+    let rfc3339 = now
+        .format(&Rfc3339)
+        .expect("failed to format RFC 3339 build timestamp");
+    println!("cargo:rustc-env=MD_BUILD_TIMESTAMP_RFC3339={}", rfc3339);
 }
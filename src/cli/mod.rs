@@ -2,19 +2,88 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use time::{macros::format_description, OffsetDateTime};
 
-use super::{BuildProfile, ProjectType, RunOptions, SummaryFormat};
+use super::{
+    BuildProfile, CiFormat, ConflictPolicy, FreshnessCheck, LinkMode, ProjectType, RunOptions,
+    SelfUpdateStrategy, SummaryFormat, TimestampFormat, WarningCode,
+};
 
-const SUMMARY_ALLOWED: &[&str] = &["text", "json", "json-pretty"];
+const SUMMARY_ALLOWED: &[&str] = &["text", "json", "json-pretty", "yaml", "ndjson", "markdown"];
+const COUNT_ALLOWED: &[&str] = &["a non-negative integer"];
+const SIZE_ALLOWED: &[&str] = &["a byte count, optionally suffixed K, M or G (e.g. 50M)"];
+/// [`WarningCode::as_str`] of every [`WarningCode::ALL`] entry, in order.
+const WARNING_CODE_ALLOWED: &[&str] = &{
+    let mut codes = [""; WarningCode::ALL.len()];
+    let mut index = 0;
+    while index < codes.len() {
+        codes[index] = WarningCode::ALL[index].as_str();
+        index += 1;
+    }
+    codes
+};
+const TIMESTAMP_ALLOWED: &[&str] = &["human", "iso"];
+const INDENT_ALLOWED: &[&str] = &["an integer from 0 to 8"];
+const DEST_ALLOWED: &[&str] = &["<name>=<dir>"];
+const JOBS_ALLOWED: &[&str] = &["a positive integer"];
+const MODE_ALLOWED: &[&str] = &["an octal permission mode up to 7777 (e.g. 755)"];
+const CONFLICT_ALLOWED: &[&str] = &["skip", "overwrite", "backup", "fail"];
+const FORMAT_ALLOWED: &[&str] = &["plain", "github"];
+const SELF_UPDATE_ALLOWED: &[&str] = &["defer", "skip", "replace", "copy"];
+const SHELL_ALLOWED: &[&str] = &["bash", "zsh", "fish", "powershell"];
+const FRESH_ALLOWED: &[&str] = &["warn", "strict"];
+
+/// Flags whose value is optional and can only be given as `--flag=value`.
+const OPTIONAL_VALUE_FLAGS: &[&str] = &["--check-fresh"];
+
+/// Flags that take a value (and so also accept the `--flag=value` form).
+const VALUE_FLAGS: &[&str] = &[
+    "-t",
+    "--target",
+    "--summary",
+    "--expect-count",
+    "--profile-dir",
+    "--target-triple",
+    "--target-dir",
+    "--target-unix",
+    "--target-windows",
+    "--self-update-strategy",
+    "--env-file",
+    "--max-file-size",
+    "--min-file-size",
+    "--suppress-warning",
+    "--deploy-id",
+    "--exclude-dir",
+    "--exclude",
+    "--bin",
+    "--bins-from",
+    "--source-glob",
+    "--checksum-file",
+    "--gpg-key",
+    "--dest",
+    "--summary-indent",
+    "--on-conflict",
+    "--format",
+    "-j",
+    "--jobs",
+    "--mode",
+    "--prefix",
+    "--suffix",
+    "--manifest-out",
+    "--pre-hook",
+    "--backup-dir",
+];
 
 const PACKAGE_NAME: &str = env!("CARGO_PKG_NAME");
 const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
 const BUILD_TIMESTAMP: &str = env!("MD_BUILD_TIMESTAMP");
+const BUILD_TIMESTAMP_RFC3339: &str = env!("MD_BUILD_TIMESTAMP_RFC3339");
 
 #[derive(Clone, Copy, Debug)]
 pub struct VersionMetadata {
     pub name: &'static str,
     pub version: &'static str,
     pub build_timestamp: &'static str,
+    /// The build timestamp as RFC 3339.
+    pub build_timestamp_rfc3339: &'static str,
 }
 
 pub fn version_metadata() -> VersionMetadata {
@@ -22,21 +91,44 @@ pub fn version_metadata() -> VersionMetadata {
         name: PACKAGE_NAME,
         version: PACKAGE_VERSION,
         build_timestamp: BUILD_TIMESTAMP,
+        build_timestamp_rfc3339: BUILD_TIMESTAMP_RFC3339,
     }
 }
 
 pub fn version_banner() -> String {
+    version_banner_with(TimestampFormat::Human)
+}
+
+/// Version banner with the build timestamp rendered in `format`.
+pub fn version_banner_with(format: TimestampFormat) -> String {
     let meta = version_metadata();
+    let timestamp = match format {
+        TimestampFormat::Human => meta.build_timestamp,
+        TimestampFormat::Iso => meta.build_timestamp_rfc3339,
+    };
     format!(
         "{} {} {}",
         meta.name.bold().bright_white(),
         format!("v{}", meta.version).bright_blue().bold(),
-        format!("built {}", meta.build_timestamp).dimmed()
+        format!("built {}", timestamp).dimmed()
     )
 }
 
 pub fn write_version_banner(writer: &mut impl std::io::Write) -> std::io::Result<()> {
-    writeln!(writer, "{}", version_banner())
+    write_version_banner_with(writer, TimestampFormat::Human)
+}
+
+pub fn write_version_banner_with(
+    writer: &mut impl std::io::Write,
+    format: TimestampFormat,
+) -> std::io::Result<()> {
+    writeln!(writer, "{}", version_banner_with(format))
+}
+
+/// Write only the bare package version (e.g. `1.4.0`) with no name, timestamp,
+/// or color, for scripts and package managers.
+pub fn write_version_short(writer: &mut impl std::io::Write) -> std::io::Result<()> {
+    writeln!(writer, "{}", version_metadata().version)
 }
 
 pub fn deploy_banner() -> String {
@@ -66,6 +158,33 @@ pub fn help_text() -> String {
         "mdrcp [OPTIONS]".bold()
     ));
     lines.push(String::new());
+    lines.push("Commands:".bold().bright_white().to_string());
+    lines.push(format!(
+        "  {} {}",
+        "init [--force]".bright_cyan(),
+        "Write a commented mdrcp.toml into the current project".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "query <path>".bright_cyan(),
+        "Show where a deployed binary came from (sidecar and/or xattrs)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "merge-summaries [--pretty] <files...>".bright_cyan(),
+        "Combine JSON deployment summaries into one".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "list [--release|--debug] [--target-triple <t>] [--summary <fmt>]".bright_cyan(),
+        "Show the binaries a deploy would consider and whether each is built".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "completions <bash|zsh|fish|powershell>".bright_cyan(),
+        "Print a shell completion script".dimmed()
+    ));
+    lines.push(String::new());
     lines.push("Options:".bold().bright_white().to_string());
     lines.push(format!(
         "  {} {}",
@@ -77,21 +196,102 @@ pub fn help_text() -> String {
         "--version, -V".bright_cyan(),
         "Show version information".dimmed()
     ));
+    lines.push(format!(
+        "  {} {}",
+        "--version --short".bright_cyan(),
+        "Print only the bare version number".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--version --timestamp-format <human|iso>".bright_cyan(),
+        "Render the build timestamp as human text or RFC 3339".dimmed()
+    ));
     lines.push(format!(
         "  {} {}",
         "--target <path>, -t <path>".bright_cyan(),
-        "Copy built binaries into the directory (relative paths resolve from project root)"
+        "Copy built binaries into the directory (relative paths resolve from project root; repeatable)"
             .dimmed()
     ));
+    lines.push(format!(
+        "  {} {}",
+        "--target-unix <path>".bright_cyan(),
+        "Default target on Linux/macOS instead of ~/.local/bin (ignored on Windows)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--target-windows <path>".bright_cyan(),
+        "Default target on Windows instead of c:\\apps (ignored elsewhere)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-resolve-target".bright_cyan(),
+        "Compare the target path literally instead of resolving symlinks".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--parallel-targets".bright_cyan(),
+        "With several --target directories, deploy to them concurrently".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--dest <name>=<dir>".bright_cyan(),
+        "Deploy binary <name> to <dir> instead of the target (repeatable)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--by-package".bright_cyan(),
+        "Place each package's binaries in <target>/<package>/".dimmed()
+    ));
     lines.push(format!(
         "  {} {}",
         "--quiet, -q".bright_cyan(),
         "Suppress version banner and progress output".dimmed()
     ));
+    lines.push(format!(
+        "  {} {}",
+        "--silent".bright_cyan(),
+        "Like --quiet, and write nothing to stderr (summaries still list warnings)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--verbose, -v".bright_cyan(),
+        "Explain binary discovery on stderr; repeat (-vv) for the directory scan".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-banner".bright_cyan(),
+        "Suppress only the version banner, keeping progress output".dimmed()
+    ));
     lines.push(format!(
         "  {} {}",
         "--summary <format>".bright_cyan(),
-        "Emit deployment summary in the given format (text | json | json-pretty)".dimmed()
+        "Emit deployment summary in the given format (text | json | json-pretty | yaml | ndjson | markdown)"
+            .dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--summary-indent <n>".bright_cyan(),
+        "Indent json-pretty output by n spaces (0-8, default 2)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--summary-compact".bright_cyan(),
+        "Always write a JSON summary as a single line (overrides json-pretty)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--format <plain|github>".bright_cyan(),
+        "Also emit GitHub Actions annotations (default: github when GITHUB_ACTIONS=true)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-quiet".bright_cyan(),
+        "Print progress even when the project config sets quiet = true".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-config".bright_cyan(),
+        "Ignore the project's .mdrcp.toml / mdrcp.toml".dimmed()
     ));
     lines.push(format!(
         "  {} {}",
@@ -103,6 +303,78 @@ pub fn help_text() -> String {
         "--debug".bright_cyan(),
         "Copy from target/debug (use after `cargo build`)".dimmed()
     ));
+    lines.push(format!(
+        "  {} {}",
+        "--build".bright_cyan(),
+        "Run `cargo build` for the selected profile first; a failed build copies nothing".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--env-file <path>".bright_cyan(),
+        "Load KEY=VALUE pairs (e.g. MD_TARGET_DIR, HOME) before resolving paths".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--profile-dir <path>".bright_cyan(),
+        "Read built binaries from this directory instead of target/<profile>".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--target-triple <triple>".bright_cyan(),
+        "Read cross-compiled binaries from target/<triple>/<profile>".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--target-dir <path>".bright_cyan(),
+        "Cargo build directory (default: CARGO_TARGET_DIR, build.target-dir, or target)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--all-files".bright_cyan(),
+        "Deploy every executable in the profile directory, not just manifest binaries".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--all".bright_cyan(),
+        "Scan every workspace member, not just workspace.default-members".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--bin <name>".bright_cyan(),
+        "Deploy only this manifest binary (repeatable)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--bins-from <file|->".bright_cyan(),
+        "Add --bin names listed one per line in a file, or on stdin with -".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--exclude <glob>".bright_cyan(),
+        "Skip manifest binaries whose base name matches (e.g. '*-test'; repeatable)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--exclude-dir <name>".bright_cyan(),
+        "Skip this subdirectory during --all-files (build, deps, incremental, examples always are)"
+            .dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--source-glob <pattern>".bright_cyan(),
+        "Deploy files matching the glob (e.g. 'target/release/*-cli') instead of manifest binaries"
+            .dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--allow-clobber".bright_cyan(),
+        "Warn (deploying the first source) when two sources share a destination name".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--allow-duplicates".bright_cyan(),
+        "Warn (deploying the first member's) when two members declare one binary name".dimmed()
+    ));
     lines.push(format!(
         "  {} {}",
         "--tauri".bright_cyan(),
@@ -115,493 +387,2402 @@ pub fn help_text() -> String {
     ));
     lines.push(format!(
         "  {} {}",
-        "(none)".bright_cyan(),
-        "Run deployment routine".dimmed()
+        "--report-unbuilt".bright_cyan(),
+        "List declared binaries with no built file for the selected profile".dimmed()
     ));
-    lines.push(String::new());
     lines.push(format!(
-        "{} {}",
-        "Tip:".bold().cyan(),
-        "Use after `cargo build --release` (or `cargo build` with --debug) to copy built binaries."
+        "  {} {}",
+        "--expect-count <n>".bright_cyan(),
+        "Warn when the number of binaries to deploy differs from n".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--max-file-size <size>".bright_cyan(),
+        "Skip binaries larger than size (bytes, or 50M / 1G style)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--min-file-size <size>".bright_cyan(),
+        "Fail binaries smaller than size (default 1 byte: catches empty builds)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--check-fresh[=strict]".bright_cyan(),
+        "Warn (or abort) when src/**/*.rs or Cargo.toml is newer than a binary".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--strict".bright_cyan(),
+        "Treat deployment warnings (e.g. --expect-count mismatches) as errors".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--suppress-warning <code>".bright_cyan(),
+        "Drop warnings with this code, e.g. not_on_path (repeatable)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-path-check".bright_cyan(),
+        "Skip the check that the target is on PATH (same as --suppress-warning not_on_path)"
             .dimmed()
     ));
     lines.push(format!(
-        "{} {}",
-        "Relative paths:".bold().magenta(),
-        "Resolved against the project directory passed to the tool.".dimmed()
+        "  {} {}",
+        "--progress".bright_cyan(),
+        "Show per-binary progress with percentage and ETA on stderr".dimmed()
     ));
     lines.push(format!(
-        "{} {}",
-        "Tauri:".bold().magenta(),
-        "Auto-detected when src-tauri/Cargo.toml and tauri.conf.json exist.".dimmed()
+        "  {} {}",
+        "--tag-xattr".bright_cyan(),
+        "Tag deployed files with user.mdrcp.source/version xattrs (Unix)".dimmed()
     ));
-    lines.join("\n")
-}
-
-pub fn write_help(writer: &mut impl std::io::Write) -> std::io::Result<()> {
-    writeln!(writer, "{}", help_text())
-}
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum Command {
-    Deploy(RunOptions),
-    ShowHelp,
-    ShowVersion,
-    FinishUpdate { source: PathBuf, dest: PathBuf },
-}
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum ParseError {
-    UnknownArgs(Vec<String>),
-    MissingValue {
-        flag: String,
-    },
-    InvalidValue {
-        flag: String,
-        value: String,
-        expected: &'static [&'static str],
-    },
-}
-
-pub fn parse_args(args: &[String]) -> Result<Command, ParseError> {
-    if args.is_empty() {
-        return Ok(Command::Deploy(RunOptions::default()));
-    }
-
-    if args.len() == 1 {
-        match args[0].as_str() {
-            "-h" | "--help" => return Ok(Command::ShowHelp),
-            "-V" | "--version" => return Ok(Command::ShowVersion),
-            _ => {}
-        }
-    }
-
-    // Internal command for self-update: --finish-update <source> <dest>
-    if args.len() == 3 && args[0] == "--finish-update" {
-        return Ok(Command::FinishUpdate {
-            source: PathBuf::from(&args[1]),
-            dest: PathBuf::from(&args[2]),
-        });
-    }
-
-    let mut options = RunOptions::default();
-    let mut index = 0;
-    while index < args.len() {
-        let arg = &args[index];
-        match arg.as_str() {
-            "-t" | "--target" => {
-                index += 1;
-                if index >= args.len() {
-                    return Err(ParseError::MissingValue { flag: arg.clone() });
-                }
-                let value = args[index].clone();
-                options.target_override = Some(PathBuf::from(value));
-            }
-            "-q" | "--quiet" => {
-                options.quiet = true;
-            }
-            "--summary" => {
-                index += 1;
-                if index >= args.len() {
-                    return Err(ParseError::MissingValue { flag: arg.clone() });
-                }
-                let value = args[index].clone();
-                options.summary =
-                    parse_summary_format(&value).ok_or_else(|| ParseError::InvalidValue {
-                        flag: arg.clone(),
-                        value,
-                        expected: SUMMARY_ALLOWED,
-                    })?;
-            }
-            _ if arg.starts_with("--target=") => {
-                let value = arg.split_once('=').map(|(_, v)| v).unwrap_or("");
-                if value.is_empty() {
-                    return Err(ParseError::MissingValue {
-                        flag: "--target".to_string(),
-                    });
-                }
-                options.target_override = Some(PathBuf::from(value));
-            }
-            _ if arg.starts_with("--summary=") => {
-                let value = arg.split_once('=').map(|(_, v)| v).unwrap_or("");
-                if value.is_empty() {
-                    return Err(ParseError::MissingValue {
-                        flag: "--summary".to_string(),
-                    });
-                }
-                options.summary =
-                    parse_summary_format(value).ok_or_else(|| ParseError::InvalidValue {
-                        flag: "--summary".to_string(),
-                        value: value.to_string(),
-                        expected: SUMMARY_ALLOWED,
-                    })?;
-            }
-            "--release" => {
-                options.profile = BuildProfile::Release;
-            }
-            "--debug" => {
-                options.profile = BuildProfile::Debug;
-            }
-            "--tauri" => {
-                options.project_type = Some(ProjectType::Tauri);
-            }
-            "--no-tauri" => {
-                options.project_type = Some(ProjectType::Standard);
-            }
-            _ => {
-                return Err(ParseError::UnknownArgs(args.to_vec()));
-            }
-        }
-        index += 1;
-    }
-
-    Ok(Command::Deploy(options))
-}
-
-fn parse_summary_format(value: &str) -> Option<SummaryFormat> {
-    match value {
-        "text" => Some(SummaryFormat::Text),
-        "json" => Some(SummaryFormat::Json),
-        "json-pretty" => Some(SummaryFormat::JsonPretty),
-        _ => None,
-    }
-}
-
-pub fn write_parse_error(
-    writer: &mut impl std::io::Write,
-    error: &ParseError,
-) -> std::io::Result<()> {
+    lines.push(format!(
+        "  {} {}",
+        "--preserve-timestamps".bright_cyan(),
+        "Give each copy its source's modified and access times".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--sidecar".bright_cyan(),
+        "Write a <binary>.mdrcp.json provenance file beside each deployed binary".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--since-last-deploy".bright_cyan(),
+        "Copy only binaries rebuilt since the last deploy into the target".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--prune".bright_cyan(),
+        "Delete binaries an earlier deploy installed that this one no longer includes".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--copy-if-newer".bright_cyan(),
+        "Skip binaries whose destination is at least as new as the source (mtime)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--verify-format".bright_cyan(),
+        "Refuse sources that are not native executables (ELF / Mach-O / PE)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "-j, --jobs <n>".bright_cyan(),
+        "Copy up to n binaries at once (default: one per CPU)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--skip-unchanged".bright_cyan(),
+        "Leave destinations whose size and mtime match the source".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--mode <octal>".bright_cyan(),
+        "Permission bits for each copy on Unix (default: 755, kept stricter if the source is)"
+            .dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--include-libs".bright_cyan(),
+        "Also deploy cdylib libraries declared with [lib] crate-type".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--examples".bright_cyan(),
+        "Also deploy [[example]] binaries from target/<profile>/examples".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--strip".bright_cyan(),
+        "Strip debug symbols from each deployed copy (Unix only)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--verify".bright_cyan(),
+        "Compare SHA-256 of each copy with its source; fail and delete mismatches".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--atomic".bright_cyan(),
+        "Stage all binaries first; replace them only if every one succeeded".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--symlink".bright_cyan(),
+        "Symlink destinations to the built files instead of copying them".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--sizes".bright_cyan(),
+        "After copying, print a table of deployed sizes and destinations".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--timings".bright_cyan(),
+        "Time each copy: duration_ms in the summary, slowest three in text".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--on-conflict <p>".bright_cyan(),
+        "Existing destination: overwrite (default) | skip | backup | fail".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--backup".bright_cyan(),
+        "Move existing destinations to <name>.bak first (same as --on-conflict backup)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--backup-dir <path>".bright_cyan(),
+        "Back up into <path>/<timestamp>/ instead of <name>.bak (implies --backup)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--force".bright_cyan(),
+        "Copy over existing destinations, even read-only ones (implies --on-conflict overwrite)"
+            .dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--interactive".bright_cyan(),
+        "Ask before replacing each existing destination (only when stdin is a terminal)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "-y, --yes".bright_cyan(),
+        "Answer yes to every --interactive prompt".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--skip-self".bright_cyan(),
+        "Never overwrite the running mdrcp binary (same as --self-update-strategy skip)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--self-update-strategy <s>".bright_cyan(),
+        "Handle the running mdrcp binary: defer (default) | skip | replace | copy".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--no-self-update".bright_cyan(),
+        "Copy over the running mdrcp binary like any other file (strategy copy)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--plan".bright_cyan(),
+        "List each planned source -> destination copy, then stop".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--checksum-file <path>".bright_cyan(),
+        "Write sha256sum-compatible checksums of the deployed binaries".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--manifest-out <path>".bright_cyan(),
+        "Write a JSON manifest of deployed files with paths, sizes, and hashes".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--gpg-sign".bright_cyan(),
+        "Write a detached <name>.sig beside each deployed binary using gpg".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--gpg-key <id>".bright_cyan(),
+        "Key to sign with (gpg --local-user); defaults to gpg's default key".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--prefix <str>".bright_cyan(),
+        "Prepend to each deployed executable's name (my- turns foo into my-foo)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--suffix <str>".bright_cyan(),
+        "Append to each deployed executable's name, before .exe".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--pre-hook <cmd>".bright_cyan(),
+        "Run cmd through the shell before copying; a non-zero exit aborts".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--deploy-id <id>".bright_cyan(),
+        "Correlation ID echoed into the JSON summary and sidecars".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "-n, --dry-run".bright_cyan(),
+        "Show planned copies and what changed since the last deploy; write nothing".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--relativize-sources".bright_cyan(),
+        "Show source paths relative to the project (e.g. target/release/foo)".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "--print-sources".bright_cyan(),
+        "List the build output each copied binary came from".dimmed()
+    ));
+    lines.push(format!(
+        "  {} {}",
+        "(none)".bright_cyan(),
+        "Run deployment routine".dimmed()
+    ));
+    lines.push(String::new());
+    lines.push(format!(
+        "{} {}",
+        "Tip:".bold().cyan(),
+        "Use after `cargo build --release` (or `cargo build` with --debug) to copy built binaries."
+            .dimmed()
+    ));
+    lines.push(format!(
+        "{} {}",
+        "Relative paths:".bold().magenta(),
+        "Resolved against the project directory passed to the tool.".dimmed()
+    ));
+    lines.push(format!(
+        "{} {}",
+        "Target precedence:".bold().magenta(),
+        "--target (or config) > $MDRCP_TARGET > package metadata target > --target-unix / --target-windows > OS default ($MD_TARGET_DIR, ~/.local/bin, c:\\apps)."
+            .dimmed()
+    ));
+    lines.push(format!(
+        "{} {}",
+        "Tauri:".bold().magenta(),
+        "Auto-detected when src-tauri/Cargo.toml and tauri.conf.json exist.".dimmed()
+    ));
+    lines.join("\n")
+}
+
+pub fn write_help(writer: &mut impl std::io::Write) -> std::io::Result<()> {
+    writeln!(writer, "{}", help_text())
+}
+
+/// Shells `mdrcp completions` can generate a script for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+/// What a completed flag's value looks like.
+enum CompletionValue {
+    None,
+    Directory,
+    Choices(&'static [&'static str]),
+}
+
+/// One flag offered by `mdrcp completions`. Value choices reuse the parser's
+/// allowed-value lists, and a test checks each entry against `parse_args`.
+struct CompletionFlag {
+    long: &'static str,
+    short: Option<&'static str>,
+    value: CompletionValue,
+    help: &'static str,
+}
+
+const COMPLETION_COMMANDS: &[&str] = &["init", "query", "merge-summaries", "list", "completions"];
+
+const COMPLETION_FLAGS: &[CompletionFlag] = &[
+    CompletionFlag {
+        long: "--target",
+        short: Some("-t"),
+        value: CompletionValue::Directory,
+        help: "Override the deployment directory",
+    },
+    CompletionFlag {
+        long: "--summary",
+        short: None,
+        value: CompletionValue::Choices(SUMMARY_ALLOWED),
+        help: "Summary output format",
+    },
+    CompletionFlag {
+        long: "--quiet",
+        short: Some("-q"),
+        value: CompletionValue::None,
+        help: "Suppress non-error output",
+    },
+    CompletionFlag {
+        long: "--release",
+        short: None,
+        value: CompletionValue::None,
+        help: "Copy from target/release",
+    },
+    CompletionFlag {
+        long: "--debug",
+        short: None,
+        value: CompletionValue::None,
+        help: "Copy from target/debug",
+    },
+    CompletionFlag {
+        long: "--help",
+        short: Some("-h"),
+        value: CompletionValue::None,
+        help: "Show help",
+    },
+    CompletionFlag {
+        long: "--version",
+        short: Some("-V"),
+        value: CompletionValue::None,
+        help: "Show version information",
+    },
+];
+
+/// Every spelling of the completion flags, for word lists.
+fn completion_words() -> Vec<&'static str> {
+    COMPLETION_FLAGS
+        .iter()
+        .flat_map(|flag| flag.short.into_iter().chain([flag.long]))
+        .collect()
+}
+
+/// Completion script for `shell`, generated from [`COMPLETION_FLAGS`].
+pub fn completions_script(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+        Shell::PowerShell => powershell_completions(),
+    }
+}
+
+/// Write the completion script for `shell` (`mdrcp completions <shell>`).
+pub fn print_completions(writer: &mut impl std::io::Write, shell: Shell) -> std::io::Result<()> {
+    write!(writer, "{}", completions_script(shell))
+}
+
+fn bash_completions() -> String {
+    let mut cases = String::new();
+    for flag in COMPLETION_FLAGS {
+        let reply = match flag.value {
+            CompletionValue::None => continue,
+            CompletionValue::Directory => "compgen -d -- \"$cur\"".to_string(),
+            CompletionValue::Choices(choices) => {
+                format!("compgen -W \"{}\" -- \"$cur\"", choices.join(" "))
+            }
+        };
+        let names: Vec<&str> = flag.short.into_iter().chain([flag.long]).collect();
+        cases.push_str(&format!(
+            "        {})\n            COMPREPLY=($({}))\n            return\n            ;;\n",
+            names.join("|"),
+            reply
+        ));
+    }
+    format!(
+        r#"_mdrcp() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    case "$prev" in
+{cases}    esac
+    if [[ $COMP_CWORD -eq 1 && $cur != -* ]]; then
+        COMPREPLY=($(compgen -W "{commands}" -- "$cur"))
+        return
+    fi
+    COMPREPLY=($(compgen -W "{flags}" -- "$cur"))
+}}
+complete -F _mdrcp mdrcp
+"#,
+        commands = COMPLETION_COMMANDS.join(" "),
+        flags = completion_words().join(" "),
+    )
+}
+
+fn zsh_completions() -> String {
+    let mut specs = Vec::new();
+    for flag in COMPLETION_FLAGS {
+        let names = match flag.short {
+            Some(short) => format!("{{{},{}}}", short, flag.long),
+            None => flag.long.to_string(),
+        };
+        let value = match flag.value {
+            CompletionValue::None => String::new(),
+            CompletionValue::Directory => ":directory:_files -/".to_string(),
+            CompletionValue::Choices(choices) => format!(":value:({})", choices.join(" ")),
+        };
+        specs.push(format!("    '*'{}'[{}]{}'", names, flag.help, value));
+    }
+    specs.push(format!(
+        "    '1::command:({})'",
+        COMPLETION_COMMANDS.join(" ")
+    ));
+    format!("#compdef mdrcp\n\n_arguments \\\n{}\n", specs.join(" \\\n"))
+}
+
+fn fish_completions() -> String {
+    let mut lines = vec![format!(
+        "complete -c mdrcp -n __fish_use_subcommand -f -a '{}'",
+        COMPLETION_COMMANDS.join(" ")
+    )];
+    for flag in COMPLETION_FLAGS {
+        let mut line = "complete -c mdrcp".to_string();
+        if let Some(short) = flag.short {
+            line.push_str(&format!(" -s {}", short.trim_start_matches('-')));
+        }
+        line.push_str(&format!(" -l {}", flag.long.trim_start_matches("--")));
+        match flag.value {
+            CompletionValue::None => {}
+            CompletionValue::Directory => line.push_str(" -r -a '(__fish_complete_directories)'"),
+            CompletionValue::Choices(choices) => {
+                line.push_str(&format!(" -x -a '{}'", choices.join(" ")))
+            }
+        }
+        line.push_str(&format!(" -d '{}'", flag.help));
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+fn powershell_completions() -> String {
+    let quote = |words: &[&str]| {
+        words
+            .iter()
+            .map(|word| format!("'{}'", word))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut cases = String::new();
+    for flag in COMPLETION_FLAGS {
+        let names: Vec<&str> = flag.short.into_iter().chain([flag.long]).collect();
+        let body = match flag.value {
+            CompletionValue::None => continue,
+            // Returning nothing falls back to PowerShell's path completion.
+            CompletionValue::Directory => "return".to_string(),
+            CompletionValue::Choices(choices) => format!("@({})", quote(choices)),
+        };
+        cases.push_str(&format!(
+            "        {{ $_ -in {} }} {{ {} }}\n",
+            quote(&names),
+            body
+        ));
+    }
+    let mut words = COMPLETION_COMMANDS.to_vec();
+    words.extend(completion_words());
+    format!(
+        r#"Register-ArgumentCompleter -Native -CommandName mdrcp -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $elements = @($commandAst.CommandElements | ForEach-Object {{ $_.ToString() }})
+    $prev = if ($wordToComplete) {{ $elements[-2] }} else {{ $elements[-1] }}
+    $candidates = switch ($prev) {{
+{cases}        default {{ @({words}) }}
+    }}
+    $candidates | Where-Object {{ $_ -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+    }}
+}}
+"#,
+        words = quote(&words),
+    )
+}
+// Parsed once per process, so the size gap between variants does not matter.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    Deploy(RunOptions),
+    ShowHelp,
+    ShowVersion { timestamp_format: TimestampFormat },
+    ShowVersionShort,
+    Init { force: bool },
+    Query { path: PathBuf },
+    MergeSummaries { files: Vec<PathBuf>, pretty: bool },
+    List(RunOptions),
+    Completions(Shell),
+    FinishUpdate { source: PathBuf, dest: PathBuf },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    UnknownArgs(Vec<String>),
+    MissingValue {
+        flag: String,
+    },
+    InvalidValue {
+        flag: String,
+        value: String,
+        expected: &'static [&'static str],
+    },
+    /// Two flags that cannot be used together, e.g. `--quiet --interactive`.
+    ConflictingFlags {
+        first: String,
+        second: String,
+        hint: &'static str,
+    },
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, ParseError> {
+    if args.is_empty() {
+        return Ok(Command::Deploy(RunOptions::default()));
+    }
+
+    if args.len() == 1 && matches!(args[0].as_str(), "-h" | "--help") {
+        return Ok(Command::ShowHelp);
+    }
+
+    // Version banner: --version [--short] [--timestamp-format human|iso]
+    if matches!(args[0].as_str(), "-V" | "--version" | "version") {
+        return parse_version_args(args);
+    }
+
+    // Scaffold a config file: init [--force] (also accepted as --init)
+    if args[0] == "init" || args[0] == "--init" {
+        return match &args[1..] {
+            [] => Ok(Command::Init { force: false }),
+            [flag] if flag == "--force" => Ok(Command::Init { force: true }),
+            _ => Err(ParseError::UnknownArgs(args.to_vec())),
+        };
+    }
+
+    // Report provenance of a deployed binary: query <path>
+    if args[0] == "query" {
+        return match &args[1..] {
+            [path] => Ok(Command::Query {
+                path: PathBuf::from(path),
+            }),
+            [] => Err(ParseError::MissingValue {
+                flag: "query".to_string(),
+            }),
+            _ => Err(ParseError::UnknownArgs(args.to_vec())),
+        };
+    }
+
+    // Combine JSON summaries: merge-summaries [--pretty] <files...>
+    if args[0] == "merge-summaries" {
+        let mut pretty = false;
+        let mut files = Vec::new();
+        for arg in &args[1..] {
+            match arg.as_str() {
+                "--pretty" => pretty = true,
+                flag if flag.starts_with('-') => {
+                    return Err(ParseError::UnknownArgs(args.to_vec()));
+                }
+                path => files.push(PathBuf::from(path)),
+            }
+        }
+        if files.is_empty() {
+            return Err(ParseError::MissingValue {
+                flag: "merge-summaries".to_string(),
+            });
+        }
+        return Ok(Command::MergeSummaries { files, pretty });
+    }
+
+    // Show discovered binaries without deploying: list [deploy flags]
+    if args[0] == "list" {
+        return match parse_args(&args[1..])? {
+            Command::Deploy(options) => Ok(Command::List(options)),
+            _ => Err(ParseError::UnknownArgs(args.to_vec())),
+        };
+    }
+
+    // Print a shell completion script: completions <bash|zsh|fish|powershell>
+    if args[0] == "completions" {
+        return match &args[1..] {
+            [shell] => match shell.as_str() {
+                "bash" => Ok(Command::Completions(Shell::Bash)),
+                "zsh" => Ok(Command::Completions(Shell::Zsh)),
+                "fish" => Ok(Command::Completions(Shell::Fish)),
+                "powershell" => Ok(Command::Completions(Shell::PowerShell)),
+                _ => Err(ParseError::InvalidValue {
+                    flag: "completions".to_string(),
+                    value: shell.clone(),
+                    expected: SHELL_ALLOWED,
+                }),
+            },
+            [] => Err(ParseError::MissingValue {
+                flag: "completions".to_string(),
+            }),
+            _ => Err(ParseError::UnknownArgs(args.to_vec())),
+        };
+    }
+
+    // Internal command for self-update: --finish-update <source> <dest>
+    if args.len() == 3 && args[0] == "--finish-update" {
+        return Ok(Command::FinishUpdate {
+            source: PathBuf::from(&args[1]),
+            dest: PathBuf::from(&args[2]),
+        });
+    }
+
+    let mut options = RunOptions::default();
+    let mut index = 0;
+    while index < args.len() {
+        let arg = &args[index];
+        // `--flag=value` is accepted for every flag that takes a value.
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (arg.as_str(), None),
+        };
+        if inline.is_some() && !VALUE_FLAGS.contains(&flag) && !OPTIONAL_VALUE_FLAGS.contains(&flag)
+        {
+            return Err(ParseError::UnknownArgs(args.to_vec()));
+        }
+        match flag {
+            "-t" | "--target" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.given.target = true;
+                if options.target_override.is_none() {
+                    options.target_override = Some(PathBuf::from(value));
+                } else {
+                    options.extra_targets.push(PathBuf::from(value));
+                }
+            }
+            "--target-unix" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.target_unix = Some(PathBuf::from(value));
+            }
+            "--target-windows" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.target_windows = Some(PathBuf::from(value));
+            }
+            "--no-resolve-target" => {
+                options.no_resolve_target = true;
+            }
+            "--parallel-targets" => {
+                options.parallel_targets = true;
+            }
+            "--by-package" => {
+                options.by_package = true;
+            }
+            "--dest" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                match value.split_once('=') {
+                    Some((name, dir)) if !name.is_empty() && !dir.is_empty() => {
+                        options
+                            .dest_overrides
+                            .insert(name.to_string(), PathBuf::from(dir));
+                    }
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: DEST_ALLOWED,
+                        })
+                    }
+                }
+            }
+            "-q" | "--quiet" => {
+                options.quiet = true;
+                options.given.quiet = true;
+            }
+            "--no-quiet" => {
+                options.quiet = false;
+                options.silent = false;
+                options.given.quiet = true;
+            }
+            "--silent" => {
+                options.quiet = true;
+                options.silent = true;
+                options.given.quiet = true;
+            }
+            "--interactive" => {
+                options.interactive = true;
+            }
+            "-y" | "--yes" => {
+                options.yes = true;
+            }
+            "-v" | "--verbose" => {
+                options.verbosity = options.verbosity.saturating_add(1);
+            }
+            "-vv" => {
+                options.verbosity = options.verbosity.saturating_add(2);
+            }
+            "--summary" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.summary =
+                    parse_summary_format(&value).ok_or_else(|| ParseError::InvalidValue {
+                        flag: flag.to_string(),
+                        value,
+                        expected: SUMMARY_ALLOWED,
+                    })?;
+                options.given.summary = true;
+            }
+            "--release" => {
+                options.profile = BuildProfile::Release;
+                options.given.profile = true;
+            }
+            "--debug" => {
+                options.profile = BuildProfile::Debug;
+                options.given.profile = true;
+            }
+            "--build" => {
+                options.build = true;
+            }
+            "--tauri" => {
+                options.project_type = Some(ProjectType::Tauri);
+            }
+            "--no-tauri" => {
+                options.project_type = Some(ProjectType::Standard);
+            }
+            "--report-unbuilt" => {
+                options.report_unbuilt = true;
+            }
+            "--summary-compact" => {
+                options.summary_compact = true;
+            }
+            "--summary-indent" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                match value.parse::<usize>() {
+                    Ok(width) if width <= 8 => options.summary_indent = Some(width),
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: INDENT_ALLOWED,
+                        })
+                    }
+                }
+            }
+            "--expect-count" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.expect_count = Some(parse_count(flag, value)?);
+            }
+            "--max-file-size" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.max_file_size = Some(parse_size(flag, value)?);
+            }
+            "--min-file-size" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.min_file_size = Some(parse_size(flag, value)?);
+            }
+            "--strict" => {
+                options.strict = true;
+            }
+            "--check-fresh" => {
+                options.check_fresh = match inline {
+                    None | Some("warn") => FreshnessCheck::Warn,
+                    Some("strict") => FreshnessCheck::Strict,
+                    Some(value) => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value: value.to_string(),
+                            expected: FRESH_ALLOWED,
+                        })
+                    }
+                };
+            }
+            "--progress" => {
+                options.progress = true;
+            }
+            "--no-banner" => {
+                options.no_banner = true;
+            }
+            "--tag-xattr" => {
+                options.tag_xattr = true;
+            }
+            "--preserve-timestamps" => {
+                options.preserve_timestamps = true;
+            }
+            "--sidecar" => {
+                options.sidecar = true;
+            }
+            "--since-last-deploy" => {
+                options.since_last_deploy = true;
+            }
+            "--prune" => {
+                options.prune = true;
+            }
+            "--verify-format" => {
+                options.verify_format = true;
+            }
+            "--copy-if-newer" => {
+                options.copy_if_newer = true;
+            }
+            "--env-file" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.env_file = Some(PathBuf::from(value));
+            }
+            "--deploy-id" => {
+                options.deploy_id = Some(take_value(args, &mut index, flag, inline)?);
+            }
+            "--manifest-out" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.manifest_out = Some(PathBuf::from(value));
+            }
+            "--prefix" => {
+                options.prefix = Some(take_value(args, &mut index, flag, inline)?);
+            }
+            "--suffix" => {
+                options.suffix = Some(take_value(args, &mut index, flag, inline)?);
+            }
+            "--pre-hook" => {
+                options.pre_hook = Some(take_value(args, &mut index, flag, inline)?);
+            }
+            "--all-files" => {
+                options.all_files = true;
+            }
+            "--all" => {
+                options.all_members = true;
+            }
+            "--bin" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.only.push(value);
+            }
+            "--bins-from" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.bins_from = Some(PathBuf::from(value));
+            }
+            "--exclude" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.exclude.push(value);
+            }
+            "--exclude-dir" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.exclude_dirs.push(value);
+            }
+            "--source-glob" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.source_globs.push(value);
+            }
+            "--allow-clobber" => {
+                options.allow_clobber = true;
+            }
+            "--allow-duplicates" => {
+                options.allow_duplicates = true;
+            }
+            "--checksum-file" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.checksum_file = Some(PathBuf::from(value));
+            }
+            "--gpg-sign" => {
+                options.gpg_sign = true;
+            }
+            "--gpg-key" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.gpg_key = Some(value);
+            }
+            "-n" | "--dry-run" => {
+                options.dry_run = true;
+            }
+            "--plan" => {
+                options.plan = true;
+            }
+            "--relativize-sources" => {
+                options.relativize_sources = true;
+            }
+            "--print-sources" => {
+                options.print_sources = true;
+            }
+            "--format" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.ci_format = Some(match value.as_str() {
+                    "plain" => CiFormat::Plain,
+                    "github" => CiFormat::Github,
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: FORMAT_ALLOWED,
+                        })
+                    }
+                });
+            }
+            "--on-conflict" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.on_conflict =
+                    parse_conflict_policy(&value).ok_or_else(|| ParseError::InvalidValue {
+                        flag: flag.to_string(),
+                        value,
+                        expected: CONFLICT_ALLOWED,
+                    })?;
+            }
+            "-j" | "--jobs" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.jobs = match value.parse::<usize>() {
+                    Ok(jobs) if jobs > 0 => Some(jobs),
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: JOBS_ALLOWED,
+                        })
+                    }
+                };
+            }
+            "--atomic" => {
+                options.atomic = true;
+            }
+            "--verify" => {
+                options.verify = true;
+            }
+            "--skip-unchanged" => {
+                options.skip_unchanged = true;
+            }
+            "--strip" => {
+                options.strip = true;
+            }
+            "--include-libs" => {
+                options.include_libs = true;
+            }
+            "--examples" => {
+                options.examples = true;
+            }
+            "--no-config" => {
+                options.no_config = true;
+            }
+            "--mode" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                let digits = value.strip_prefix("0o").unwrap_or(&value);
+                options.mode = match u32::from_str_radix(digits, 8) {
+                    Ok(mode) if mode <= 0o7777 && !digits.starts_with('+') => Some(mode),
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: MODE_ALLOWED,
+                        })
+                    }
+                };
+            }
+            "--symlink" => {
+                options.link_mode = LinkMode::Symlink;
+            }
+            "--sizes" => {
+                options.sizes = true;
+            }
+            "--timings" => {
+                options.timings = true;
+            }
+            "--backup" => {
+                options.on_conflict = ConflictPolicy::Backup;
+            }
+            "--backup-dir" => {
+                options.backup_dir =
+                    Some(PathBuf::from(take_value(args, &mut index, flag, inline)?));
+                options.on_conflict = ConflictPolicy::Backup;
+            }
+            "--force" => {
+                options.on_conflict = ConflictPolicy::Overwrite;
+                options.force = true;
+            }
+            "--skip-self" => {
+                options.self_update = SelfUpdateStrategy::Skip;
+            }
+            "--no-self-update" => {
+                options.self_update = SelfUpdateStrategy::Copy;
+            }
+            "--self-update-strategy" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.self_update =
+                    parse_self_update_strategy(&value).ok_or_else(|| ParseError::InvalidValue {
+                        flag: flag.to_string(),
+                        value,
+                        expected: SELF_UPDATE_ALLOWED,
+                    })?;
+            }
+            "--suppress-warning" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                let code = WarningCode::parse(&value).ok_or_else(|| ParseError::InvalidValue {
+                    flag: flag.to_string(),
+                    value,
+                    expected: WARNING_CODE_ALLOWED,
+                })?;
+                if !options.suppress_warnings.contains(&code) {
+                    options.suppress_warnings.push(code);
+                }
+            }
+            "--no-path-check" => {
+                if !options.suppress_warnings.contains(&WarningCode::NotOnPath) {
+                    options.suppress_warnings.push(WarningCode::NotOnPath);
+                }
+            }
+            "--profile-dir" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.profile_dir = Some(PathBuf::from(value));
+            }
+            "--target-triple" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.target_triple = Some(value);
+            }
+            "--target-dir" => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                options.cargo_target_dir = Some(PathBuf::from(value));
+            }
+            _ => {
+                return Err(ParseError::UnknownArgs(args.to_vec()));
+            }
+        }
+        index += 1;
+    }
+
+    if options.interactive && options.quiet {
+        return Err(ParseError::ConflictingFlags {
+            first: "--interactive".to_string(),
+            second: if options.silent { "--silent" } else { "--quiet" }.to_string(),
+            hint: "--interactive prompts on the terminal; drop the quiet flag, or pass --yes to confirm every overwrite",
+        });
+    }
+    if options.interactive && (options.parallel_targets || !options.extra_targets.is_empty()) {
+        return Err(ParseError::ConflictingFlags {
+            first: "--interactive".to_string(),
+            second: if options.parallel_targets {
+                "--parallel-targets"
+            } else {
+                "--target"
+            }
+            .to_string(),
+            hint: "each target's output is buffered, so prompts could not be answered; deploy one target at a time, or pass --yes instead of --interactive",
+        });
+    }
+
+    Ok(Command::Deploy(options))
+}
+
+fn parse_version_args(args: &[String]) -> Result<Command, ParseError> {
+    let mut short = false;
+    let mut timestamp_format = TimestampFormat::Human;
+    let mut index = 1;
+    while index < args.len() {
+        let arg = &args[index];
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+            _ => (arg.as_str(), None),
+        };
+        match (flag, inline) {
+            ("--short", None) => short = true,
+            ("--timestamp-format", _) => {
+                let value = take_value(args, &mut index, flag, inline)?;
+                timestamp_format = match value.as_str() {
+                    "human" => TimestampFormat::Human,
+                    "iso" => TimestampFormat::Iso,
+                    _ => {
+                        return Err(ParseError::InvalidValue {
+                            flag: flag.to_string(),
+                            value,
+                            expected: TIMESTAMP_ALLOWED,
+                        })
+                    }
+                };
+            }
+            _ => return Err(ParseError::UnknownArgs(args.to_vec())),
+        }
+        index += 1;
+    }
+    if short {
+        Ok(Command::ShowVersionShort)
+    } else {
+        Ok(Command::ShowVersion { timestamp_format })
+    }
+}
+
+/// Fetch the value for `flag`, either from its inline `--flag=value` form or
+/// from the following argument (advancing `index` past it).
+fn take_value(
+    args: &[String],
+    index: &mut usize,
+    flag: &str,
+    inline: Option<&str>,
+) -> Result<String, ParseError> {
+    let value = match inline {
+        Some(value) => value.to_string(),
+        None => {
+            *index += 1;
+            args.get(*index).cloned().unwrap_or_default()
+        }
+    };
+    if value.is_empty() {
+        return Err(ParseError::MissingValue {
+            flag: flag.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn parse_count(flag: &str, value: String) -> Result<usize, ParseError> {
+    value.parse().map_err(|_| ParseError::InvalidValue {
+        flag: flag.to_string(),
+        value,
+        expected: COUNT_ALLOWED,
+    })
+}
+
+/// Parse a byte size such as `1048576`, `512K`, `50M` or `1G` (binary units).
+fn parse_size(flag: &str, value: String) -> Result<u64, ParseError> {
+    let trimmed = value.trim();
+    let (digits, multiplier) = match trimmed.char_indices().last() {
+        Some((at, 'k' | 'K')) => (&trimmed[..at], 1u64 << 10),
+        Some((at, 'm' | 'M')) => (&trimmed[..at], 1u64 << 20),
+        Some((at, 'g' | 'G')) => (&trimmed[..at], 1u64 << 30),
+        _ => (trimmed, 1),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| ParseError::InvalidValue {
+            flag: flag.to_string(),
+            value,
+            expected: SIZE_ALLOWED,
+        })
+}
+
+fn parse_conflict_policy(value: &str) -> Option<ConflictPolicy> {
+    match value {
+        "skip" => Some(ConflictPolicy::Skip),
+        "overwrite" => Some(ConflictPolicy::Overwrite),
+        "backup" => Some(ConflictPolicy::Backup),
+        "fail" => Some(ConflictPolicy::Fail),
+        _ => None,
+    }
+}
+
+fn parse_self_update_strategy(value: &str) -> Option<SelfUpdateStrategy> {
+    match value {
+        "defer" => Some(SelfUpdateStrategy::Defer),
+        "skip" => Some(SelfUpdateStrategy::Skip),
+        "replace" => Some(SelfUpdateStrategy::Replace),
+        "copy" => Some(SelfUpdateStrategy::Copy),
+        _ => None,
+    }
+}
+
+fn parse_summary_format(value: &str) -> Option<SummaryFormat> {
+    match value {
+        "text" => Some(SummaryFormat::Text),
+        "json" => Some(SummaryFormat::Json),
+        "json-pretty" => Some(SummaryFormat::JsonPretty),
+        "yaml" => Some(SummaryFormat::Yaml),
+        "ndjson" => Some(SummaryFormat::Ndjson),
+        "markdown" | "md" => Some(SummaryFormat::Markdown),
+        _ => None,
+    }
+}
+
+pub fn write_parse_error(
+    writer: &mut impl std::io::Write,
+    error: &ParseError,
+) -> std::io::Result<()> {
     match error {
         ParseError::UnknownArgs(args) => {
             if args.is_empty() {
                 return Ok(());
             }
-            let joined = args.join(" ");
-            writeln!(
-                writer,
-                "{} {}",
-                "Unknown arguments:".bold().bright_red(),
-                joined.bold()
-            )?;
-            writeln!(
-                writer,
-                "{} {}",
-                "Hint:".bold().cyan(),
-                "Run `mdrcp --help` for usage details.".dimmed()
-            )?;
+            let joined = args.join(" ");
+            writeln!(
+                writer,
+                "{} {}",
+                "Unknown arguments:".bold().bright_red(),
+                joined.bold()
+            )?;
+            writeln!(
+                writer,
+                "{} {}",
+                "Hint:".bold().cyan(),
+                "Run `mdrcp --help` for usage details.".dimmed()
+            )?;
+        }
+        ParseError::MissingValue { flag } => {
+            writeln!(
+                writer,
+                "{} {}",
+                "Missing value:".bold().bright_red(),
+                flag.bold()
+            )?;
+            if flag == "-t" || flag == "--target" {
+                writeln!(
+                    writer,
+                    "{} {}",
+                    "Hint:".bold().cyan(),
+                    "Pass a directory after the flag, e.g. --target path/to/bin".dimmed()
+                )?;
+                writeln!(
+                    writer,
+                    "{} {}",
+                    "Relative paths:".bold().magenta(),
+                    "Resolved against the project directory passed to the tool.".dimmed()
+                )?;
+            } else {
+                writeln!(
+                    writer,
+                    "{} {}",
+                    "Hint:".bold().cyan(),
+                    format!("Pass a value after the flag, e.g. {} <value>", flag).dimmed()
+                )?;
+            }
+        }
+        ParseError::InvalidValue {
+            flag,
+            value,
+            expected,
+        } => {
+            writeln!(
+                writer,
+                "{} {} {}",
+                "Invalid value:".bold().bright_red(),
+                flag.bold(),
+                value.bold()
+            )?;
+            writeln!(
+                writer,
+                "{} {}",
+                "Accepted:".bold().cyan(),
+                expected.join(", ")
+            )?;
+        }
+        ParseError::ConflictingFlags {
+            first,
+            second,
+            hint,
+        } => {
+            writeln!(
+                writer,
+                "{} {} {}",
+                "Conflicting flags:".bold().bright_red(),
+                first.bold(),
+                second.bold()
+            )?;
+            writeln!(writer, "{} {}", "Hint:".bold().cyan(), hint.dimmed())?;
+        }
+    }
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_metadata_matches_env() {
+        let meta = version_metadata();
+        assert_eq!(meta.name, env!("CARGO_PKG_NAME"));
+        assert_eq!(meta.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(meta.build_timestamp, env!("MD_BUILD_TIMESTAMP"));
+    }
+
+    #[test]
+    fn test_version_banner_contains_fields() {
+        let banner = version_banner();
+        assert!(banner.contains(env!("CARGO_PKG_VERSION")));
+        assert!(banner.contains(env!("MD_BUILD_TIMESTAMP")));
+    }
+
+    #[test]
+    fn test_write_version_short_is_bare_semver() {
+        let mut sink = Vec::new();
+        write_version_short(&mut sink).unwrap();
+        assert_eq!(
+            String::from_utf8(sink).unwrap(),
+            format!("{}\n", env!("CARGO_PKG_VERSION"))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_version_short() {
+        for first in ["--version", "-V", "version"] {
+            let cmd = parse_args(&[first.to_string(), "--short".to_string()]).unwrap();
+            assert_eq!(cmd, Command::ShowVersionShort);
+        }
+        assert_eq!(
+            parse_args(&["version".to_string()]).unwrap(),
+            Command::ShowVersion {
+                timestamp_format: TimestampFormat::Human
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_version_timestamp_format() {
+        let cmd = parse_args(&[
+            "--version".to_string(),
+            "--timestamp-format".to_string(),
+            "iso".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(
+            cmd,
+            Command::ShowVersion {
+                timestamp_format: TimestampFormat::Iso
+            }
+        );
+        let err =
+            parse_args(&["-V".to_string(), "--timestamp-format=unix".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == TIMESTAMP_ALLOWED));
+        let err = parse_args(&["--version".to_string(), "--quiet".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownArgs(_)));
+    }
+
+    #[test]
+    fn test_iso_version_banner_is_rfc3339() {
+        use time::format_description::well_known::Rfc3339;
+        let meta = version_metadata();
+        OffsetDateTime::parse(meta.build_timestamp_rfc3339, &Rfc3339).unwrap();
+        assert!(version_banner_with(TimestampFormat::Iso).contains(meta.build_timestamp_rfc3339));
+    }
+
+    #[test]
+    fn test_help_text_includes_options() {
+        let help = help_text();
+        assert!(help.contains("mdrcp [OPTIONS]"));
+        assert!(help.contains("--help"));
+        assert!(help.contains("--version"));
+        assert!(help.contains("--target"));
+        assert!(help.contains("Relative paths"));
+        assert!(help.contains("--quiet"));
+        assert!(help.contains("--summary"));
+        assert!(help.contains("--debug"));
+        assert!(help.contains("--release"));
+    }
+
+    #[test]
+    fn test_parse_args_default_deploy() {
+        let cmd = parse_args(&[]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert!(opts.target_override.is_none());
+                assert!(!opts.quiet);
+                assert_eq!(opts.summary, SummaryFormat::Text);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_target_flag() {
+        let cmd = parse_args(&["--target".to_string(), "out/bin".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
+                assert!(!opts.quiet);
+                assert_eq!(opts.summary, SummaryFormat::Text);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_repeated_target_collects_extras() {
+        let cmd = parse_args(&[
+            "-t".to_string(),
+            "a".to_string(),
+            "--target=b".to_string(),
+            "--parallel-targets".to_string(),
+            "-t".to_string(),
+            "c".to_string(),
+        ])
+        .unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.target_override == Some(PathBuf::from("a"))
+                && opts.extra_targets == vec![PathBuf::from("b"), PathBuf::from("c")]
+                && opts.parallel_targets));
+    }
+
+    #[test]
+    fn test_parse_args_dest_overrides() {
+        let cmd = parse_args(&[
+            "--dest".to_string(),
+            "tool=opt/tools".to_string(),
+            "--dest=helper=/srv/bin".to_string(),
+        ])
+        .unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.dest_overrides.len(), 2);
+                assert_eq!(opts.dest_overrides["tool"], PathBuf::from("opt/tools"));
+                assert_eq!(opts.dest_overrides["helper"], PathBuf::from("/srv/bin"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        let err = parse_args(&["--dest".to_string(), "tool".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { flag, .. } if flag == "--dest"));
+    }
+
+    #[test]
+    fn test_parse_args_no_resolve_target() {
+        let cmd = parse_args(&["--no-resolve-target".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.no_resolve_target));
+    }
+
+    #[test]
+    fn test_parse_args_by_package() {
+        let cmd = parse_args(&["--by-package".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.by_package));
+    }
+
+    #[test]
+    fn test_parse_args_target_equals_syntax() {
+        let cmd = parse_args(&["--target=out/bin".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
+                assert!(!opts.quiet);
+                assert_eq!(opts.summary, SummaryFormat::Text);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_quiet_flag() {
+        let cmd = parse_args(&["--quiet".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert!(opts.quiet);
+                assert!(opts.target_override.is_none());
+                assert_eq!(opts.summary, SummaryFormat::Text);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_silent_implies_quiet() {
+        let cmd = parse_args(&["--silent".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.silent && opts.quiet));
+    }
+
+    #[test]
+    fn test_parse_args_quiet_short_flag() {
+        let cmd = parse_args(&[
+            "-q".to_string(),
+            "--target".to_string(),
+            "out/bin".to_string(),
+        ])
+        .unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert!(opts.quiet);
+                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
+                assert_eq!(opts.summary, SummaryFormat::Text);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_markdown() {
+        for value in ["markdown", "md"] {
+            let cmd = parse_args(&["--summary".to_string(), value.to_string()]).unwrap();
+            assert!(
+                matches!(cmd, Command::Deploy(opts) if opts.summary == SummaryFormat::Markdown)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_ndjson() {
+        let cmd = parse_args(&["--summary=ndjson".to_string(), "--quiet".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.summary, SummaryFormat::Ndjson);
+                assert!(opts.quiet);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_yaml() {
+        let cmd = parse_args(&["--summary".to_string(), "yaml".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.summary, SummaryFormat::Yaml);
+                assert!(!opts.quiet);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_json() {
+        let cmd = parse_args(&["--summary".to_string(), "json".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.summary, SummaryFormat::Json);
+                assert!(!opts.quiet);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_indent_range() {
+        let cmd = parse_args(&["--summary-indent".to_string(), "4".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.summary_indent == Some(4)));
+        for bad in ["9", "-1", "wide"] {
+            let err = parse_args(&["--summary-indent".to_string(), bad.to_string()]).unwrap_err();
+            assert!(
+                matches!(err, ParseError::InvalidValue { flag, .. } if flag == "--summary-indent")
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_compact() {
+        let cmd = parse_args(&[
+            "--summary".to_string(),
+            "json-pretty".to_string(),
+            "--summary-compact".to_string(),
+        ])
+        .unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.summary_compact && opts.summary == SummaryFormat::JsonPretty));
+    }
+
+    #[test]
+    fn test_parse_args_summary_equals_syntax() {
+        let cmd = parse_args(&["--summary=json".to_string(), "-q".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.summary, SummaryFormat::Json);
+                assert!(opts.quiet);
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_format() {
+        let cmd = parse_args(&["--format=github".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.ci_format == Some(CiFormat::Github)));
+        let cmd = parse_args(&[]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.ci_format.is_none()));
+        let err = parse_args(&["--format".to_string(), "gitlab".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == FORMAT_ALLOWED));
+    }
+
+    #[test]
+    fn test_parse_args_summary_invalid_value() {
+        let err = parse_args(&["--summary".to_string(), "xml".to_string()]).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::InvalidValue {
+                flag: "--summary".to_string(),
+                value: "xml".to_string(),
+                expected: SUMMARY_ALLOWED,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_debug_flag() {
+        let cmd = parse_args(&["--debug".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.profile, BuildProfile::Debug);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_release_flag() {
+        let cmd = parse_args(&["--debug".to_string(), "--release".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.profile, BuildProfile::Release);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_missing_value_errors() {
+        let err = parse_args(&["--target".to_string()]).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::MissingValue {
+                flag: "--target".to_string()
+            }
+        );
+
+        let err_summary = parse_args(&["--summary".to_string()]).unwrap_err();
+        assert_eq!(
+            err_summary,
+            ParseError::MissingValue {
+                flag: "--summary".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_unknown() {
+        let err = parse_args(&["--unknown".to_string()]).unwrap_err();
+        assert_eq!(err, ParseError::UnknownArgs(vec!["--unknown".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_args_tauri_flag() {
+        let cmd = parse_args(&["--tauri".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.project_type, Some(ProjectType::Tauri));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_no_tauri_flag() {
+        let cmd = parse_args(&["--no-tauri".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.project_type, Some(ProjectType::Standard));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_summary_text_explicit() {
+        let cmd = parse_args(&["--summary".to_string(), "text".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.summary, SummaryFormat::Text);
+            }
+            _ => panic!("Unexpected command"),
         }
-        ParseError::MissingValue { flag } => {
-            writeln!(
-                writer,
-                "{} {}",
-                "Missing value:".bold().bright_red(),
-                flag.bold()
-            )?;
-            writeln!(
-                writer,
-                "{} {}",
-                "Hint:".bold().cyan(),
-                "Pass a directory after the flag, e.g. --target path/to/bin".dimmed()
-            )?;
-            writeln!(
-                writer,
-                "{} {}",
-                "Relative paths:".bold().magenta(),
-                "Resolved against the project directory passed to the tool.".dimmed()
-            )?;
+    }
+
+    #[test]
+    fn test_parse_args_empty_values() {
+        // Test --target=
+        let err = parse_args(&["--target=".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { flag } if flag == "--target"));
+
+        // Test --summary=
+        let err_summary = parse_args(&["--summary=".to_string()]).unwrap_err();
+        assert!(matches!(err_summary, ParseError::MissingValue { flag } if flag == "--summary"));
+    }
+
+    #[test]
+    fn test_parse_args_finish_update() {
+        let args = vec![
+            "--finish-update".to_string(),
+            "source/path".to_string(),
+            "dest/path".to_string(),
+        ];
+        let cmd = parse_args(&args).unwrap();
+        match cmd {
+            Command::FinishUpdate { source, dest } => {
+                assert_eq!(source, PathBuf::from("source/path"));
+                assert_eq!(dest, PathBuf::from("dest/path"));
+            }
+            _ => panic!("Expected FinishUpdate command"),
         }
-        ParseError::InvalidValue {
-            flag,
-            value,
-            expected,
-        } => {
-            writeln!(
-                writer,
-                "{} {} {}",
-                "Invalid value:".bold().bright_red(),
-                flag.bold(),
-                value.bold()
-            )?;
-            writeln!(
-                writer,
-                "{} {}",
-                "Accepted:".bold().cyan(),
-                expected.join(", ")
-            )?;
+    }
+
+    #[test]
+    fn test_parse_args_summary_equals_invalid() {
+        let err = parse_args(&["--summary=invalid".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { flag, .. } if flag == "--summary"));
+    }
+
+    #[test]
+    fn test_parse_args_tauri_with_debug() {
+        let cmd = parse_args(&["--tauri".to_string(), "--debug".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.project_type, Some(ProjectType::Tauri));
+                assert_eq!(opts.profile, BuildProfile::Debug);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_report_unbuilt_flag() {
+        let cmd = parse_args(&["--report-unbuilt".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert!(opts.report_unbuilt);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_expect_count_and_strict() {
+        let cmd = parse_args(&[
+            "--expect-count".to_string(),
+            "3".to_string(),
+            "--strict".to_string(),
+        ])
+        .unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.expect_count, Some(3));
+                assert!(opts.strict);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let cmd = parse_args(&["--expect-count=2".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.expect_count == Some(2)));
+
+        let err = parse_args(&["--expect-count".to_string(), "many".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { flag, .. } if flag == "--expect-count"));
+    }
+
+    #[test]
+    fn test_parse_args_inline_value_on_switch_is_unknown() {
+        let err = parse_args(&["--quiet=yes".to_string()]).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::UnknownArgs(vec!["--quiet=yes".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_parse_args_init() {
+        assert_eq!(
+            parse_args(&["init".to_string()]).unwrap(),
+            Command::Init { force: false }
+        );
+        assert_eq!(
+            parse_args(&["--init".to_string(), "--force".to_string()]).unwrap(),
+            Command::Init { force: true }
+        );
+        let err = parse_args(&["init".to_string(), "--quiet".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownArgs(_)));
+    }
+
+    #[test]
+    fn test_parse_args_progress_flag() {
+        let cmd = parse_args(&["--progress".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.progress));
+    }
+
+    #[test]
+    fn test_parse_args_no_banner_independent_of_quiet() {
+        let cmd = parse_args(&["--no-banner".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert!(opts.no_banner);
+                assert!(!opts.quiet);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_tag_xattr_flag() {
+        let cmd = parse_args(&["--tag-xattr".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.tag_xattr));
+    }
+
+    #[test]
+    fn test_parse_args_preserve_timestamps() {
+        let cmd = parse_args(&["--preserve-timestamps".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.preserve_timestamps));
+    }
+
+    #[test]
+    fn test_parse_args_merge_summaries() {
+        assert_eq!(
+            parse_args(&[
+                "merge-summaries".to_string(),
+                "a.json".to_string(),
+                "--pretty".to_string(),
+                "b.json".to_string(),
+            ])
+            .unwrap(),
+            Command::MergeSummaries {
+                files: vec![PathBuf::from("a.json"), PathBuf::from("b.json")],
+                pretty: true,
+            }
+        );
+        let err = parse_args(&["merge-summaries".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { flag } if flag == "merge-summaries"));
+    }
+
+    #[test]
+    fn test_parse_args_query_and_sidecar() {
+        assert_eq!(
+            parse_args(&["query".to_string(), "bin/tool".to_string()]).unwrap(),
+            Command::Query {
+                path: PathBuf::from("bin/tool")
+            }
+        );
+        let err = parse_args(&["query".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { flag } if flag == "query"));
+
+        let cmd = parse_args(&["--sidecar".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.sidecar));
+    }
+
+    #[test]
+    fn test_parse_args_since_last_deploy_flag() {
+        let cmd = parse_args(&["--since-last-deploy".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.since_last_deploy));
+    }
+
+    #[test]
+    fn test_parse_args_platform_targets() {
+        let cmd = parse_args(&[
+            "--target-unix=/opt/bin".to_string(),
+            "--target-windows".to_string(),
+            r"D:\tools".to_string(),
+        ])
+        .unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.target_unix, Some(PathBuf::from("/opt/bin")));
+                assert_eq!(opts.target_windows, Some(PathBuf::from(r"D:\tools")));
+                assert_eq!(opts.target_override, None);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_args_prune() {
+        let cmd = parse_args(&["--prune".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.prune));
+    }
+
+    #[test]
+    fn test_parse_args_verify_format_flag() {
+        let cmd = parse_args(&["--verify-format".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verify_format));
+    }
+
+    #[test]
+    fn test_parse_args_copy_if_newer_flag() {
+        let cmd = parse_args(&["--copy-if-newer".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.copy_if_newer));
+    }
+
+    #[test]
+    fn test_parse_args_profile_dir() {
+        let cmd = parse_args(&["--profile-dir".to_string(), "out/bins".to_string()]).unwrap();
+        match cmd {
+            Command::Deploy(opts) => {
+                assert_eq!(opts.profile_dir, Some(PathBuf::from("out/bins")));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        let err = parse_args(&["--profile-dir=".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { flag } if flag == "--profile-dir"));
+    }
+
+    #[test]
+    fn test_parse_args_target_triple() {
+        let args = [
+            "--target-triple",
+            "x86_64-unknown-linux-musl",
+            "--target",
+            "dist",
+        ];
+        let cmd = parse_args(&args.map(String::from)).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.target_triple.as_deref() == Some("x86_64-unknown-linux-musl")
+                && opts.target_override == Some(PathBuf::from("dist"))));
+    }
+
+    #[test]
+    fn test_parse_args_target_dir() {
+        let args = ["--target-dir", "/srv/cargo-target", "-t", "dist"];
+        let cmd = parse_args(&args.map(String::from)).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.cargo_target_dir == Some(PathBuf::from("/srv/cargo-target"))
+                && opts.target_override == Some(PathBuf::from("dist"))));
+    }
+
+    #[test]
+    fn test_parse_args_skip_self_flag() {
+        let cmd = parse_args(&["--skip-self".to_string()]).unwrap();
+        assert!(
+            matches!(cmd, Command::Deploy(opts) if opts.self_update == SelfUpdateStrategy::Skip)
+        );
+    }
+
+    #[test]
+    fn test_parse_args_no_self_update_flag() {
+        let cmd = parse_args(&["--no-self-update".to_string()]).unwrap();
+        assert!(
+            matches!(cmd, Command::Deploy(opts) if opts.self_update == SelfUpdateStrategy::Copy)
+        );
+    }
+
+    #[test]
+    fn test_parse_args_self_update_strategy() {
+        for (raw, expected) in [
+            ("defer", SelfUpdateStrategy::Defer),
+            ("skip", SelfUpdateStrategy::Skip),
+            ("replace", SelfUpdateStrategy::Replace),
+            ("copy", SelfUpdateStrategy::Copy),
+        ] {
+            let cmd = parse_args(&[format!("--self-update-strategy={}", raw)]).unwrap();
+            assert!(matches!(cmd, Command::Deploy(opts) if opts.self_update == expected));
+        }
+        let err =
+            parse_args(&["--self-update-strategy".to_string(), "yolo".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == SELF_UPDATE_ALLOWED));
+    }
+
+    #[test]
+    fn test_parse_args_jobs() {
+        let cmd = parse_args(&["--jobs".to_string(), "4".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.jobs == Some(4)));
+        let cmd = parse_args(&["-j".to_string(), "1".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.jobs == Some(1)));
+        for bad in ["0", "many"] {
+            let err = parse_args(&[format!("--jobs={}", bad)]).unwrap_err();
+            assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+                if expected == JOBS_ALLOWED));
+        }
+    }
+
+    #[test]
+    fn test_parse_args_verbosity_counts() {
+        let cmd = parse_args(&[]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verbosity == 0));
+        let cmd = parse_args(&["-v".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verbosity == 1));
+        let cmd = parse_args(&["-v", "--verbose"].map(String::from)).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verbosity == 2));
+        let cmd = parse_args(&["-vv".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verbosity == 2));
+    }
+
+    #[test]
+    fn test_parse_args_list() {
+        let args = ["list", "--debug", "--summary", "json"];
+        let cmd = parse_args(&args.map(String::from)).unwrap();
+        assert!(matches!(cmd, Command::List(opts)
+            if opts.profile == BuildProfile::Debug && opts.summary == SummaryFormat::Json));
+        assert!(matches!(
+            parse_args(&["list".to_string()]).unwrap(),
+            Command::List(_)
+        ));
+        let err = parse_args(&["list".to_string(), "--help".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownArgs(_)));
+    }
+
+    #[test]
+    fn test_parse_args_completions() {
+        let cmd = parse_args(&["completions".to_string(), "fish".to_string()]).unwrap();
+        assert_eq!(cmd, Command::Completions(Shell::Fish));
+        let err = parse_args(&["completions".to_string(), "tcsh".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == SHELL_ALLOWED));
+        let err = parse_args(&["completions".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { .. }));
+    }
+
+    #[test]
+    fn test_completion_flags_match_parser() {
+        for flag in COMPLETION_FLAGS {
+            for name in flag.short.into_iter().chain([flag.long]) {
+                let takes_value = !matches!(flag.value, CompletionValue::None);
+                assert_eq!(VALUE_FLAGS.contains(&name), takes_value, "{name}");
+                let values: &[&str] = match flag.value {
+                    CompletionValue::None => &[],
+                    CompletionValue::Directory => &["dist"],
+                    CompletionValue::Choices(choices) => choices,
+                };
+                if values.is_empty() && !matches!(flag.long, "--help" | "--version") {
+                    assert!(parse_args(&[name.to_string()]).is_ok(), "{name}");
+                }
+                for value in values {
+                    let args = [name.to_string(), value.to_string()];
+                    assert!(parse_args(&args).is_ok(), "{name} {value}");
+                }
+            }
+        }
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = completions_script(shell);
+            for word in [
+                "target",
+                "summary",
+                "json-pretty",
+                "quiet",
+                "release",
+                "debug",
+            ] {
+                assert!(script.contains(word), "{shell:?} lacks {word}");
+            }
         }
     }
-    Ok(())
-}
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_version_metadata_matches_env() {
-        let meta = version_metadata();
-        assert_eq!(meta.name, env!("CARGO_PKG_NAME"));
-        assert_eq!(meta.version, env!("CARGO_PKG_VERSION"));
-        assert_eq!(meta.build_timestamp, env!("MD_BUILD_TIMESTAMP"));
+    fn test_parse_args_mode() {
+        let cmd = parse_args(&["--mode".to_string(), "0750".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.mode == Some(0o750)));
+        let cmd = parse_args(&["--mode=0o700".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.mode == Some(0o700)));
+        for bad in ["789", "rwx", "17777", "+755"] {
+            let err = parse_args(&[format!("--mode={}", bad)]).unwrap_err();
+            assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+                if expected == MODE_ALLOWED));
+        }
+    }
+
+    #[test]
+    fn test_parse_args_atomic() {
+        let cmd = parse_args(&["--atomic".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.atomic));
+    }
+
+    #[test]
+    fn test_parse_args_verify() {
+        let cmd = parse_args(&[]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if !opts.verify));
+        let cmd = parse_args(&["--verify".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.verify));
+    }
+
+    #[test]
+    fn test_parse_args_skip_unchanged() {
+        let cmd = parse_args(&["--skip-unchanged".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.skip_unchanged));
+    }
+
+    #[test]
+    fn test_parse_args_no_path_check() {
+        let args = ["--no-path-check", "--suppress-warning", "not_on_path"];
+        let cmd = parse_args(&args.map(String::from)).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.suppress_warnings == vec![WarningCode::NotOnPath]));
+    }
+
+    #[test]
+    fn test_parse_args_no_config() {
+        let cmd = parse_args(&["--no-config".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.no_config));
     }
 
     #[test]
-    fn test_version_banner_contains_fields() {
-        let banner = version_banner();
-        assert!(banner.contains(env!("CARGO_PKG_VERSION")));
-        assert!(banner.contains(env!("MD_BUILD_TIMESTAMP")));
+    fn test_parse_args_records_given_config_keys() {
+        let cmd = parse_args(&["--release".to_string(), "--summary=text".to_string()]).unwrap();
+        let Command::Deploy(opts) = cmd else {
+            panic!("expected deploy");
+        };
+        assert_eq!(opts.profile, BuildProfile::Release);
+        assert!(opts.given.profile && opts.given.summary);
+        assert!(!opts.given.target && !opts.given.quiet);
+
+        let cmd = parse_args(&["--quiet".to_string(), "--no-quiet".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if !opts.quiet && opts.given.quiet));
     }
 
     #[test]
-    fn test_help_text_includes_options() {
-        let help = help_text();
-        assert!(help.contains("mdrcp [OPTIONS]"));
-        assert!(help.contains("--help"));
-        assert!(help.contains("--version"));
-        assert!(help.contains("--target"));
-        assert!(help.contains("Relative paths"));
-        assert!(help.contains("--quiet"));
-        assert!(help.contains("--summary"));
-        assert!(help.contains("--debug"));
-        assert!(help.contains("--release"));
+    fn test_parse_args_strip() {
+        let cmd = parse_args(&["--strip".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.strip));
     }
 
     #[test]
-    fn test_parse_args_default_deploy() {
+    fn test_parse_args_include_libs() {
+        let cmd = parse_args(&["--include-libs".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.include_libs));
+    }
+
+    #[test]
+    fn test_parse_args_all_members() {
+        let cmd = parse_args(&["--all".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.all_members && !opts.all_files));
+    }
+
+    #[test]
+    fn test_parse_args_examples() {
+        let cmd = parse_args(&["--examples".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.examples));
+    }
+
+    #[test]
+    fn test_parse_args_symlink() {
         let cmd = parse_args(&[]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert!(opts.target_override.is_none());
-                assert!(!opts.quiet);
-                assert_eq!(opts.summary, SummaryFormat::Text);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.link_mode == LinkMode::Copy));
+        let cmd = parse_args(&["--symlink".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.link_mode == LinkMode::Symlink));
     }
 
     #[test]
-    fn test_parse_args_target_flag() {
-        let cmd = parse_args(&["--target".to_string(), "out/bin".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
-                assert!(!opts.quiet);
-                assert_eq!(opts.summary, SummaryFormat::Text);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_sizes() {
+        let cmd = parse_args(&["--sizes".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.sizes));
     }
 
     #[test]
-    fn test_parse_args_target_equals_syntax() {
-        let cmd = parse_args(&["--target=out/bin".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
-                assert!(!opts.quiet);
-                assert_eq!(opts.summary, SummaryFormat::Text);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
+    fn test_parse_args_check_fresh() {
+        let cmd = parse_args(&["--check-fresh".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.check_fresh == FreshnessCheck::Warn));
+        let cmd = parse_args(&["--check-fresh=strict".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.check_fresh == FreshnessCheck::Strict));
+        let err = parse_args(&["--check-fresh=never".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == FRESH_ALLOWED));
+        // The value is only taken inline; a following argument is not consumed.
+        let err = parse_args(&["--check-fresh".to_string(), "strict".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownArgs(_)));
+    }
+
+    #[test]
+    fn test_parse_args_build() {
+        let cmd = parse_args(&["--build".to_string(), "--debug".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.build && opts.profile == BuildProfile::Debug));
+    }
+
+    #[test]
+    fn test_parse_args_interactive() {
+        let cmd = parse_args(&["--interactive".to_string(), "-y".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.interactive && opts.yes));
+        for quiet in ["--quiet", "--silent"] {
+            let err = parse_args(&[quiet.to_string(), "--interactive".to_string()]).unwrap_err();
+            assert!(
+                matches!(&err, ParseError::ConflictingFlags { first, second, .. }
+                if first == "--interactive" && second == quiet)
+            );
+            let mut out = Vec::new();
+            write_parse_error(&mut out, &err).unwrap();
+            let text = String::from_utf8(out).unwrap();
+            assert!(text.contains("Conflicting flags:"), "{text}");
+            assert!(text.contains("--yes"), "{text}");
         }
+        let err = parse_args(&[
+            "--interactive".to_string(),
+            "-t".to_string(),
+            "a".to_string(),
+            "-t".to_string(),
+            "b".to_string(),
+        ])
+        .unwrap_err();
+        assert!(
+            matches!(&err, ParseError::ConflictingFlags { second, .. } if second == "--target")
+        );
+        let err = parse_args(&[
+            "--parallel-targets".to_string(),
+            "--interactive".to_string(),
+        ])
+        .unwrap_err();
+        assert!(matches!(&err, ParseError::ConflictingFlags { second, .. }
+            if second == "--parallel-targets"));
     }
 
     #[test]
-    fn test_parse_args_quiet_flag() {
-        let cmd = parse_args(&["--quiet".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert!(opts.quiet);
-                assert!(opts.target_override.is_none());
-                assert_eq!(opts.summary, SummaryFormat::Text);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
+    fn test_parse_args_timings() {
+        let cmd = parse_args(&["--timings".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.timings && !opts.sizes));
+    }
+
+    #[test]
+    fn test_parse_args_on_conflict() {
+        for (raw, expected) in [
+            ("skip", ConflictPolicy::Skip),
+            ("overwrite", ConflictPolicy::Overwrite),
+            ("backup", ConflictPolicy::Backup),
+            ("fail", ConflictPolicy::Fail),
+        ] {
+            let cmd = parse_args(&["--on-conflict".to_string(), raw.to_string()]).unwrap();
+            assert!(matches!(cmd, Command::Deploy(opts) if opts.on_conflict == expected));
         }
+        let cmd = parse_args(&["--backup".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.on_conflict == ConflictPolicy::Backup));
+        let cmd = parse_args(&["--backup-dir=old".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.on_conflict == ConflictPolicy::Backup
+                && opts.backup_dir == Some(PathBuf::from("old"))));
+        let cmd = parse_args(&["--on-conflict=fail".to_string(), "--force".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.on_conflict == ConflictPolicy::Overwrite && opts.force));
+        let err = parse_args(&["--on-conflict=merge".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == CONFLICT_ALLOWED));
     }
 
     #[test]
-    fn test_parse_args_quiet_short_flag() {
+    fn test_parse_args_plan_and_relativize() {
+        let cmd = parse_args(&["--plan".to_string(), "--relativize-sources".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.plan && opts.relativize_sources));
+        let cmd = parse_args(&["--print-sources".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.print_sources));
+    }
+
+    #[test]
+    fn test_parse_args_deploy_id() {
+        let cmd = parse_args(&["--deploy-id".to_string(), "ci-1234".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.deploy_id.as_deref() == Some("ci-1234")));
+    }
+
+    #[test]
+    fn test_parse_args_prefix_suffix() {
+        let args = [
+            "--prefix=my-".to_string(),
+            "--suffix".to_string(),
+            "-2".to_string(),
+        ];
+        let cmd = parse_args(&args).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.prefix.as_deref() == Some("my-") && opts.suffix.as_deref() == Some("-2")));
+    }
+
+    #[test]
+    fn test_parse_args_pre_hook() {
+        let args = [
+            "--pre-hook".to_string(),
+            "systemctl --user stop demo".to_string(),
+        ];
+        let cmd = parse_args(&args).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.pre_hook.as_deref() == Some("systemctl --user stop demo")));
+        let err = parse_args(&["--pre-hook".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::MissingValue { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn test_parse_args_bin_repeatable() {
         let cmd = parse_args(&[
-            "-q".to_string(),
-            "--target".to_string(),
-            "out/bin".to_string(),
+            "--bin".to_string(),
+            "api".to_string(),
+            "--bin=cli".to_string(),
         ])
         .unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert!(opts.quiet);
-                assert_eq!(opts.target_override, Some(PathBuf::from("out/bin")));
-                assert_eq!(opts.summary, SummaryFormat::Text);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.only == ["api", "cli"]));
     }
 
     #[test]
-    fn test_parse_args_summary_json() {
-        let cmd = parse_args(&["--summary".to_string(), "json".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.summary, SummaryFormat::Json);
-                assert!(!opts.quiet);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_bins_from() {
+        let cmd = parse_args(&["--bins-from".to_string(), "-".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.bins_from == Some(PathBuf::from("-"))));
     }
 
     #[test]
-    fn test_parse_args_summary_equals_syntax() {
-        let cmd = parse_args(&["--summary=json".to_string(), "-q".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.summary, SummaryFormat::Json);
-                assert!(opts.quiet);
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_exclude_repeatable() {
+        let cmd = parse_args(&[
+            "--exclude".to_string(),
+            "*-test".to_string(),
+            "--exclude=internal-*".to_string(),
+        ])
+        .unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.exclude == ["*-test", "internal-*"]));
     }
 
     #[test]
-    fn test_parse_args_summary_invalid_value() {
-        let err = parse_args(&["--summary".to_string(), "xml".to_string()]).unwrap_err();
-        assert_eq!(
-            err,
-            ParseError::InvalidValue {
-                flag: "--summary".to_string(),
-                value: "xml".to_string(),
-                expected: SUMMARY_ALLOWED,
-            }
-        );
+    fn test_parse_args_all_files_with_excludes() {
+        let cmd = parse_args(&[
+            "--all-files".to_string(),
+            "--exclude-dir".to_string(),
+            "bundle".to_string(),
+            "--exclude-dir=scratch".to_string(),
+        ])
+        .unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.all_files && opts.exclude_dirs == ["bundle", "scratch"]));
     }
 
     #[test]
-    fn test_parse_args_debug_flag() {
-        let cmd = parse_args(&["--debug".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.profile, BuildProfile::Debug);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_source_glob_repeatable() {
+        let cmd = parse_args(&[
+            "--source-glob".to_string(),
+            "target/release/*-cli".to_string(),
+            "--source-glob=out/*".to_string(),
+        ])
+        .unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.source_globs == ["target/release/*-cli", "out/*"]));
     }
 
     #[test]
-    fn test_parse_args_release_flag() {
-        let cmd = parse_args(&["--debug".to_string(), "--release".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.profile, BuildProfile::Release);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_allow_clobber() {
+        let cmd = parse_args(&["--allow-clobber".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.allow_clobber));
     }
 
     #[test]
-    fn test_parse_args_missing_value_errors() {
-        let err = parse_args(&["--target".to_string()]).unwrap_err();
-        assert_eq!(
-            err,
-            ParseError::MissingValue {
-                flag: "--target".to_string()
-            }
-        );
-
-        let err_summary = parse_args(&["--summary".to_string()]).unwrap_err();
-        assert_eq!(
-            err_summary,
-            ParseError::MissingValue {
-                flag: "--summary".to_string()
-            }
-        );
+    fn test_parse_args_allow_duplicates() {
+        let cmd = parse_args(&["--allow-duplicates".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.allow_duplicates));
     }
 
     #[test]
-    fn test_parse_args_unknown() {
-        let err = parse_args(&["--unknown".to_string()]).unwrap_err();
-        assert_eq!(err, ParseError::UnknownArgs(vec!["--unknown".to_string()]));
+    fn test_parse_args_checksum_file() {
+        let cmd = parse_args(&["--checksum-file".to_string(), "SHA256SUMS".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.checksum_file == Some(PathBuf::from("SHA256SUMS"))));
     }
 
     #[test]
-    fn test_parse_args_tauri_flag() {
-        let cmd = parse_args(&["--tauri".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.project_type, Some(ProjectType::Tauri));
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_manifest_out() {
+        let cmd = parse_args(&["--manifest-out=deployed.json".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.manifest_out == Some(PathBuf::from("deployed.json"))));
     }
 
     #[test]
-    fn test_parse_args_no_tauri_flag() {
-        let cmd = parse_args(&["--no-tauri".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.project_type, Some(ProjectType::Standard));
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_parse_args_gpg_sign_with_key() {
+        let cmd =
+            parse_args(&["--gpg-sign".to_string(), "--gpg-key=ABCD1234".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.gpg_sign && opts.gpg_key.as_deref() == Some("ABCD1234")));
     }
 
     #[test]
-    fn test_parse_args_summary_text_explicit() {
-        let cmd = parse_args(&["--summary".to_string(), "text".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.summary, SummaryFormat::Text);
-            }
-            _ => panic!("Unexpected command"),
-        }
+    fn test_parse_args_dry_run() {
+        let cmd = parse_args(&["--dry-run".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.dry_run && !opts.plan));
+        let cmd = parse_args(&["-n".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.dry_run));
     }
 
     #[test]
-    fn test_parse_args_empty_values() {
-        // Test --target=
-        let err = parse_args(&["--target=".to_string()]).unwrap_err();
-        assert!(matches!(err, ParseError::MissingValue { flag } if flag == "--target"));
-
-        // Test --summary=
-        let err_summary = parse_args(&["--summary=".to_string()]).unwrap_err();
-        assert!(matches!(err_summary, ParseError::MissingValue { flag } if flag == "--summary"));
+    fn test_parse_args_env_file() {
+        let cmd = parse_args(&["--env-file=deploy.env".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.env_file == Some(PathBuf::from("deploy.env"))));
     }
 
     #[test]
-    fn test_parse_args_finish_update() {
-        let args = vec![
-            "--finish-update".to_string(),
-            "source/path".to_string(),
-            "dest/path".to_string(),
-        ];
-        let cmd = parse_args(&args).unwrap();
-        match cmd {
-            Command::FinishUpdate { source, dest } => {
-                assert_eq!(source, PathBuf::from("source/path"));
-                assert_eq!(dest, PathBuf::from("dest/path"));
-            }
-            _ => panic!("Expected FinishUpdate command"),
+    fn test_parse_args_file_size_limits() {
+        let cmd = parse_args(&["--max-file-size".to_string(), "50M".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts)
+            if opts.max_file_size == Some(50 * 1024 * 1024)));
+        let cmd = parse_args(&["--max-file-size=4096".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.max_file_size == Some(4096)));
+
+        let cmd = parse_args(&["--min-file-size".to_string(), "8k".to_string()]).unwrap();
+        assert!(matches!(cmd, Command::Deploy(opts) if opts.min_file_size == Some(8192)));
+
+        for bad in ["huge", "M", "1.5G", "-1K"] {
+            let err = parse_args(&[format!("--max-file-size={}", bad)]).unwrap_err();
+            assert!(
+                matches!(err, ParseError::InvalidValue { ref flag, .. } if flag == "--max-file-size"),
+                "{} should be rejected",
+                bad
+            );
         }
     }
 
     #[test]
-    fn test_parse_args_summary_equals_invalid() {
-        let err = parse_args(&["--summary=invalid".to_string()]).unwrap_err();
-        assert!(matches!(err, ParseError::InvalidValue { flag, .. } if flag == "--summary"));
+    fn test_parse_args_suppress_warning_repeatable() {
+        let cmd = parse_args(&[
+            "--suppress-warning".to_string(),
+            "not_on_path".to_string(),
+            "--suppress-warning=redundant_target".to_string(),
+        ])
+        .unwrap();
+        assert!(
+            matches!(cmd, Command::Deploy(opts) if opts.suppress_warnings
+            == vec![WarningCode::NotOnPath, WarningCode::RedundantTarget])
+        );
+
+        let err = parse_args(&["--suppress-warning=bogus".to_string()]).unwrap_err();
+        assert!(matches!(err, ParseError::InvalidValue { expected, .. }
+            if expected == WARNING_CODE_ALLOWED));
     }
 
     #[test]
-    fn test_parse_args_tauri_with_debug() {
-        let cmd = parse_args(&["--tauri".to_string(), "--debug".to_string()]).unwrap();
-        match cmd {
-            Command::Deploy(opts) => {
-                assert_eq!(opts.project_type, Some(ProjectType::Tauri));
-                assert_eq!(opts.profile, BuildProfile::Debug);
-            }
-            other => panic!("unexpected command: {:?}", other),
-        }
+    fn test_warning_code_allowed_matches_enum() {
+        let codes: Vec<&str> = WarningCode::ALL.iter().map(|c| c.as_str()).collect();
+        assert_eq!(codes, WARNING_CODE_ALLOWED);
     }
 
     #[test]
@@ -0,0 +1,273 @@
+use crate::{BuildProfile, RunOptions, SummaryFormat};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File name written by `mdrcp init` into the project directory.
+pub const CONFIG_FILE_NAME: &str = "mdrcp.toml";
+
+/// Config files a deploy reads from the project directory; the first one
+/// present wins.
+pub const PROJECT_CONFIG_FILES: &[&str] = &[".mdrcp.toml", CONFIG_FILE_NAME];
+
+/// Commented starter config documenting every key a deploy reads.
+const CONFIG_TEMPLATE: &str = r#"# mdrcp configuration
+#
+# Each setting mirrors the command-line flag of the same name; the values shown
+# are the defaults. Flags given on the command line win, and --no-config
+# ignores this file. Other keys are rejected.
+
+# Deployment directory, like --target. Relative paths resolve from the project
+# root. Defaults to MDRCP_TARGET, then the OS default (~/.local/bin or c:\apps).
+# target = "dist/bin"
+
+# Summary format, like --summary: "text", "json", "json-pretty", "yaml",
+# "ndjson", or "markdown".
+summary = "text"
+
+# Build profile to deploy from: "release" (target/release) or "debug"
+# (target/debug), like --release / --debug.
+profile = "release"
+
+# Only print errors, like --quiet (--no-quiet overrides it).
+quiet = false
+"#;
+
+/// Write the starter config into `project_dir`, refusing to replace an existing
+/// config file unless `force` is set. With `force`, the file a deploy would read
+/// (an existing `.mdrcp.toml` first) is the one overwritten. Returns the path
+/// that was written.
+pub fn init_config(project_dir: &Path, force: bool) -> Result<PathBuf> {
+    let path =
+        project_config_file(project_dir).unwrap_or_else(|| project_dir.join(CONFIG_FILE_NAME));
+    if path.exists() && !force {
+        anyhow::bail!(
+            "{} already exists; pass --force to overwrite it",
+            path.display()
+        );
+    }
+    fs::write(&path, CONFIG_TEMPLATE)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// The first of [`PROJECT_CONFIG_FILES`] present in `project_dir`.
+fn project_config_file(project_dir: &Path) -> Option<PathBuf> {
+    PROJECT_CONFIG_FILES
+        .iter()
+        .map(|name| project_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// The keys a deploy reads; anything else is a parse error.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    target: Option<PathBuf>,
+    summary: Option<String>,
+    profile: Option<String>,
+    quiet: Option<bool>,
+}
+
+/// Read the project's config file (see [`PROJECT_CONFIG_FILES`]) into
+/// options. Keys that are absent, or no file at all, leave the defaults.
+pub fn load_config(project_dir: &Path) -> Result<RunOptions> {
+    let mut options = RunOptions::default();
+    let Some(path) = project_config_file(project_dir) else {
+        return Ok(options);
+    };
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let config: FileConfig =
+        toml::from_str(&text).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    options.target_override = config.target;
+    if let Some(summary) = config.summary {
+        options.summary = match summary.as_str() {
+            "text" => SummaryFormat::Text,
+            "json" => SummaryFormat::Json,
+            "json-pretty" => SummaryFormat::JsonPretty,
+            "yaml" => SummaryFormat::Yaml,
+            "ndjson" => SummaryFormat::Ndjson,
+            "markdown" | "md" => SummaryFormat::Markdown,
+            other => anyhow::bail!(
+                "Invalid summary '{}' in {}: expected text, json, json-pretty, yaml, ndjson, or markdown",
+                other,
+                path.display()
+            ),
+        };
+    }
+    if let Some(profile) = config.profile {
+        options.profile = match profile.as_str() {
+            "release" => BuildProfile::Release,
+            "debug" => BuildProfile::Debug,
+            other => anyhow::bail!(
+                "Invalid profile '{}' in {}: expected release or debug",
+                other,
+                path.display()
+            ),
+        };
+    }
+    options.quiet = config.quiet.unwrap_or(false);
+    Ok(options)
+}
+
+/// `cli` layered over the project config. A key the command line gave (see
+/// [`GivenFlags`](crate::GivenFlags)) always wins, even at its default value;
+/// otherwise a value the caller changed from the default wins, and the config
+/// fills the rest. `--no-config` skips the file entirely.
+pub fn apply_config(project_dir: &Path, cli: &RunOptions) -> Result<RunOptions> {
+    if cli.no_config {
+        return Ok(cli.clone());
+    }
+    let config = load_config(project_dir)?;
+    let defaults = RunOptions::default();
+    let mut merged = cli.clone();
+    if !cli.given.target && merged.target_override.is_none() {
+        merged.target_override = config.target_override;
+    }
+    if !cli.given.summary && merged.summary == defaults.summary {
+        merged.summary = config.summary;
+    }
+    if !cli.given.profile && merged.profile == defaults.profile {
+        merged.profile = config.profile;
+    }
+    if !cli.given.quiet {
+        merged.quiet |= config.quiet;
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_config_creates_then_refuses_overwrite() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = init_config(temp.path(), false).unwrap();
+        assert_eq!(path, temp.path().join(CONFIG_FILE_NAME));
+        assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG_TEMPLATE);
+
+        let err = init_config(temp.path(), false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+    }
+
+    #[test]
+    fn test_init_config_force_overwrites() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(CONFIG_FILE_NAME);
+        fs::write(&path, "stale").unwrap();
+        init_config(temp.path(), true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG_TEMPLATE);
+    }
+
+    #[test]
+    fn test_load_config_reads_keys_and_prefers_dotfile() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(load_config(temp.path()).unwrap(), RunOptions::default());
+
+        fs::write(temp.path().join(CONFIG_FILE_NAME), "quiet = true").unwrap();
+        assert!(load_config(temp.path()).unwrap().quiet);
+
+        fs::write(
+            temp.path().join(".mdrcp.toml"),
+            "target = \"dist\"\nsummary = \"json\"\nprofile = \"debug\"",
+        )
+        .unwrap();
+        let options = load_config(temp.path()).unwrap();
+        assert_eq!(options.target_override, Some(PathBuf::from("dist")));
+        assert_eq!(options.summary, SummaryFormat::Json);
+        assert_eq!(options.profile, BuildProfile::Debug);
+        assert!(!options.quiet);
+    }
+
+    #[test]
+    fn test_load_config_rejects_bad_values() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(temp.path().join(".mdrcp.toml"), "summary = \"xml\"").unwrap();
+        let err = load_config(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("Invalid summary 'xml'"), "{err}");
+
+        fs::write(temp.path().join(".mdrcp.toml"), "all_files = true").unwrap();
+        let err = load_config(temp.path()).unwrap_err();
+        assert!(
+            format!("{err:#}").contains("unknown field `all_files`"),
+            "{err:#}"
+        );
+    }
+
+    #[test]
+    fn test_init_config_targets_existing_dotfile() {
+        let temp = tempfile::tempdir().unwrap();
+        let dotfile = temp.path().join(".mdrcp.toml");
+        fs::write(&dotfile, "quiet = true").unwrap();
+        let err = init_config(temp.path(), false).unwrap_err();
+        assert!(err.to_string().contains(".mdrcp.toml"), "{err}");
+
+        assert_eq!(init_config(temp.path(), true).unwrap(), dotfile);
+        assert!(!temp.path().join(CONFIG_FILE_NAME).exists());
+        assert_eq!(load_config(temp.path()).unwrap(), RunOptions::default());
+    }
+
+    #[test]
+    fn test_apply_config_cli_wins() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(
+            temp.path().join(".mdrcp.toml"),
+            "target = \"dist\"\nsummary = \"json\"",
+        )
+        .unwrap();
+        let cli = RunOptions {
+            target_override: Some(PathBuf::from("elsewhere")),
+            ..Default::default()
+        };
+        let merged = apply_config(temp.path(), &cli).unwrap();
+        assert_eq!(merged.target_override, Some(PathBuf::from("elsewhere")));
+        assert_eq!(merged.summary, SummaryFormat::Json);
+
+        let cli = RunOptions {
+            no_config: true,
+            ..Default::default()
+        };
+        assert_eq!(apply_config(temp.path(), &cli).unwrap(), cli);
+    }
+
+    #[test]
+    fn test_apply_config_given_default_flags_win() {
+        let temp = tempfile::tempdir().unwrap();
+        fs::write(
+            temp.path().join(".mdrcp.toml"),
+            "summary = \"json\"\nprofile = \"debug\"\nquiet = true",
+        )
+        .unwrap();
+        let cli = RunOptions {
+            given: crate::GivenFlags {
+                summary: true,
+                profile: true,
+                quiet: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let merged = apply_config(temp.path(), &cli).unwrap();
+        assert_eq!(merged.summary, SummaryFormat::Text);
+        assert_eq!(merged.profile, BuildProfile::Release);
+        assert!(!merged.quiet);
+
+        let merged = apply_config(temp.path(), &RunOptions::default()).unwrap();
+        assert_eq!(merged.summary, SummaryFormat::Json);
+        assert_eq!(merged.profile, BuildProfile::Debug);
+        assert!(merged.quiet);
+    }
+
+    #[test]
+    fn test_config_template_is_valid_toml() {
+        let parsed: toml::Value = toml::from_str(CONFIG_TEMPLATE).unwrap();
+        assert_eq!(parsed["profile"].as_str(), Some("release"));
+        assert_eq!(parsed["summary"].as_str(), Some("text"));
+        let parsed: FileConfig = toml::from_str(CONFIG_TEMPLATE).unwrap();
+        assert_eq!(parsed.quiet, Some(false));
+    }
+}
@@ -0,0 +1,64 @@
+//! Ctrl-C handling for deploys.
+//!
+//! The signal handler only raises a flag. The copy loop checks it between
+//! binaries, so a copy already in flight finishes and the target never holds a
+//! half-written file; binaries not yet started are reported and the run exits
+//! with [`EXIT_CODE`].
+
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, OnceLock};
+
+/// Exit status for an interrupted deploy (128 + SIGINT, as shells report it).
+pub const EXIT_CODE: i32 = 130;
+
+static FLAG: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// The process-wide flag raised by Ctrl-C once [`install`] has run.
+pub fn flag() -> Arc<AtomicBool> {
+    FLAG.get_or_init(|| Arc::new(AtomicBool::new(false)))
+        .clone()
+}
+
+/// Route SIGINT to [`flag`] instead of terminating the process. A second
+/// Ctrl-C exits immediately. Only Unix is handled; elsewhere Ctrl-C keeps its
+/// default behaviour.
+pub fn install() {
+    let _ = flag();
+    #[cfg(unix)]
+    // SAFETY: the handler only touches an already-initialised atomic and
+    // calls `_exit`, both async-signal-safe.
+    unsafe {
+        libc::signal(libc::SIGINT, on_sigint as *const () as libc::sighandler_t);
+    }
+}
+
+#[cfg(unix)]
+extern "C" fn on_sigint(_signal: libc::c_int) {
+    if let Some(flag) = FLAG.get() {
+        if flag.swap(true, std::sync::atomic::Ordering::SeqCst) {
+            // SAFETY: `_exit` is async-signal-safe.
+            unsafe { libc::_exit(EXIT_CODE) };
+        }
+    }
+}
+
+/// Error returned when a deploy stopped early because the flag was raised.
+#[derive(Debug)]
+pub struct Interrupted {
+    /// Binaries that were never started.
+    pub remaining: usize,
+}
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Interrupted; {} binar{} not deployed",
+            self.remaining,
+            if self.remaining == 1 { "y" } else { "ies" }
+        )
+    }
+}
+
+impl std::error::Error for Interrupted {}
@@ -1,11 +1,14 @@
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsString;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use toml::Value;
 
 // NOTE: must not contain "update"/"setup"/"install"/"patch" — those keywords
@@ -15,10 +18,23 @@ use toml::Value;
 const UPDATER_TEMP_NAME: &str = "mdrcp_swap.exe";
 
 pub mod cli;
+pub mod config;
+pub mod interrupt;
+mod markdown;
+pub mod provenance;
+pub mod runner;
+pub mod target_manifest;
+mod yaml;
 
 pub use cli::{
-    parse_args, write_deploy_banner, write_help, write_parse_error, write_version_banner, Command,
-    ParseError,
+    parse_args, print_completions, version_banner_with, write_deploy_banner, write_help,
+    write_parse_error, write_version_banner, write_version_banner_with, write_version_short,
+    Command, ParseError, Shell,
+};
+pub use config::{init_config, load_config};
+pub use runner::{CommandRunner, SystemRunner};
+use target_manifest::{
+    read_target_manifest, write_target_manifest, ManifestDiff, TargetManifest, TARGET_MANIFEST_FILE,
 };
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum SummaryFormat {
@@ -26,6 +42,22 @@ pub enum SummaryFormat {
     Text,
     Json,
     JsonPretty,
+    Yaml,
+    /// One JSON object per line, streamed as each binary is copied, then a
+    /// final `"event": "summary"` line.
+    Ndjson,
+    /// A Markdown table of binaries plus a warnings list, for PR comments.
+    Markdown,
+}
+
+/// How the version banner renders the build timestamp.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimestampFormat {
+    /// `2025-01-31 12:00:00 UTC`
+    #[default]
+    Human,
+    /// RFC 3339, e.g. `2025-01-31T12:00:00.123456Z`
+    Iso,
 }
 
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -35,6 +67,68 @@ pub enum BuildProfile {
     Debug,
 }
 
+/// How to handle a destination that is the running mdrcp executable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfUpdateStrategy {
+    /// Copy everything else first, then spawn a helper to swap the binary.
+    #[default]
+    Defer,
+    /// Leave the running binary in place and warn.
+    Skip,
+    /// Stage the new binary beside the old one and rename it into place.
+    Replace,
+    /// No special handling: copy over it like any other destination.
+    Copy,
+}
+
+/// How a binary is placed at its destination.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LinkMode {
+    /// Copy the built file.
+    #[default]
+    Copy,
+    /// Symlink the destination to the built file, so it always tracks the
+    /// latest build.
+    Symlink,
+}
+
+/// `--check-fresh`: compare each built binary with its crate's sources.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FreshnessCheck {
+    /// No comparison.
+    #[default]
+    Off,
+    /// Warn (`stale_binary`) about binaries older than their sources.
+    Warn,
+    /// Abort before copying anything if a binary is older than its sources.
+    Strict,
+}
+
+/// `--format`: how results are surfaced to a CI system, on top of `--summary`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CiFormat {
+    /// No extra output.
+    #[default]
+    Plain,
+    /// GitHub Actions workflow commands: `::error::` per failed binary and a
+    /// `::notice::` on success.
+    Github,
+}
+
+/// What to do when a destination file already exists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Copy over it.
+    #[default]
+    Overwrite,
+    /// Leave it and list the binary as skipped.
+    Skip,
+    /// Move it aside to `<name>.bak` (or into `--backup-dir`), then copy.
+    Backup,
+    /// Record a per-binary failure and leave it untouched.
+    Fail,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ProjectType {
     #[default]
@@ -76,13 +170,196 @@ impl BuildProfile {
     }
 }
 
+/// Config-file keys set on the command line. [`config::apply_config`] leaves
+/// these alone even when they hold the default value (`--release`,
+/// `--summary text`, `--no-quiet`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GivenFlags {
+    pub target: bool,
+    pub summary: bool,
+    pub profile: bool,
+    pub quiet: bool,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct RunOptions {
     pub target_override: Option<PathBuf>,
+    /// Run `cargo build` for the selected profile before deploying.
+    pub build: bool,
     pub quiet: bool,
+    /// Write nothing to stderr (warnings, failures, the final error); the
+    /// summary keeps the warnings and the exit status still reports failure.
+    pub silent: bool,
     pub summary: SummaryFormat,
     pub profile: BuildProfile,
     pub project_type: Option<ProjectType>, // None = auto-detect
+    pub report_unbuilt: bool,
+    /// Expected number of binaries to deploy; a mismatch warns (errors under `strict`).
+    pub expect_count: Option<usize>,
+    pub strict: bool,
+    pub progress: bool,
+    /// Skip the version banner without silencing progress output.
+    pub no_banner: bool,
+    /// Record source project and mdrcp version as xattrs on deployed files.
+    pub tag_xattr: bool,
+    /// Write a `<binary>.mdrcp.json` provenance sidecar beside each deployed file.
+    pub sidecar: bool,
+    /// Give each copy its source's modified (and access) time.
+    pub preserve_timestamps: bool,
+    /// Copy only sources modified since the deploy recorded in the target manifest.
+    pub since_last_deploy: bool,
+    /// Delete binaries the target manifest recorded that this deploy no longer
+    /// includes.
+    pub prune: bool,
+    /// Replaces the OS default target on Unix-like platforms; ignored on Windows.
+    pub target_unix: Option<PathBuf>,
+    /// Replaces the OS default target on Windows; ignored elsewhere.
+    pub target_windows: Option<PathBuf>,
+    /// Refuse sources whose magic bytes are not a host-native executable.
+    pub verify_format: bool,
+    /// Skip binaries whose destination mtime is at least the source mtime.
+    pub copy_if_newer: bool,
+    /// Directory holding built binaries, replacing `target/<profile>` entirely.
+    pub profile_dir: Option<PathBuf>,
+    /// Cross-compilation triple: read binaries from
+    /// `target/<triple>/<profile>`.
+    pub target_triple: Option<String>,
+    /// Cargo's build output directory (`--target-dir`), overriding
+    /// `CARGO_TARGET_DIR` and `build.target-dir`.
+    pub cargo_target_dir: Option<PathBuf>,
+    /// `-v` count: 1 logs manifests and candidate names during discovery, 2
+    /// adds the profile directory scan and each existence check.
+    pub verbosity: u8,
+    pub self_update: SelfUpdateStrategy,
+    /// List each planned `source -> destination` copy and stop before touching disk.
+    pub plan: bool,
+    /// Show source paths relative to the project directory.
+    pub relativize_sources: bool,
+    /// List the build output each copied binary came from, in the text
+    /// summary and as `source` in the summary's `copied` entries.
+    pub print_sources: bool,
+    /// `KEY=VALUE` file loaded into the context environment before resolution.
+    pub env_file: Option<PathBuf>,
+    /// Skip (or fail under `strict`) sources larger than this many bytes.
+    pub max_file_size: Option<u64>,
+    /// Fail sources smaller than this many bytes; `None` uses `DEFAULT_MIN_FILE_SIZE`.
+    pub min_file_size: Option<u64>,
+    /// Warning codes dropped from output, the JSON summary, and `strict` checks.
+    pub suppress_warnings: Vec<WarningCode>,
+    /// Report what would change relative to the target manifest and write nothing.
+    pub dry_run: bool,
+    /// Caller-supplied correlation ID echoed into the JSON summary and sidecars.
+    pub deploy_id: Option<String>,
+    /// Deploy every executable in the profile directory, not just manifest binaries.
+    pub all_files: bool,
+    /// Extra profile subdirectory names `all_files` does not scan.
+    pub exclude_dirs: Vec<String>,
+    /// Select sources by glob (relative to the project) instead of manifests.
+    pub source_globs: Vec<String>,
+    /// Base-name globs (e.g. `*-test`) of manifest binaries never to deploy.
+    pub exclude: Vec<String>,
+    /// When non-empty, deploy only these manifest binaries (by base name).
+    pub only: Vec<String>,
+    /// File of further `only` names, one per line; `-` reads them from stdin.
+    pub bins_from: Option<PathBuf>,
+    /// Warn instead of failing when two sources share a destination name.
+    pub allow_clobber: bool,
+    /// Warn instead of failing when two members declare the same binary name.
+    pub allow_duplicates: bool,
+    /// Write `<sha256>  <name>` lines for every copied binary to this file.
+    pub checksum_file: Option<PathBuf>,
+    /// Write a detached `<name>.sig` beside each deployed binary via `gpg`.
+    pub gpg_sign: bool,
+    /// Key passed to `gpg --local-user` when signing.
+    pub gpg_key: Option<String>,
+    /// Further `--target` directories, each deployed after `target_override`.
+    /// Honoured by [`run_with_options`]; [`deploy`] reports a single target.
+    pub extra_targets: Vec<PathBuf>,
+    /// Deploy to each target on its own thread instead of one after another.
+    pub parallel_targets: bool,
+    /// Per-binary destination directories (`--dest <name>=<dir>`), keyed by
+    /// base name; relative paths resolve from the project directory.
+    pub dest_overrides: HashMap<String, PathBuf>,
+    /// Place each package's binaries in `<target>/<package>/`.
+    pub by_package: bool,
+    /// Spaces per level for `json-pretty` (serde_json's default is 2).
+    pub summary_indent: Option<usize>,
+    /// Force any JSON summary onto a single line, overriding `json-pretty`.
+    pub summary_compact: bool,
+    /// `--format`; `None` when not given, which the `mdrcp` binary turns into
+    /// [`CiFormat::Github`] under `GITHUB_ACTIONS=true` and is otherwise plain.
+    pub ci_format: Option<CiFormat>,
+    /// Compare target paths literally instead of resolving symlinks first.
+    pub no_resolve_target: bool,
+    /// Handling for destinations that already exist.
+    pub on_conflict: ConflictPolicy,
+    /// Ask before replacing an existing destination (`--interactive`).
+    pub interactive: bool,
+    /// Answer yes to every `--interactive` prompt.
+    pub yes: bool,
+    /// Compare built binaries with their crate's sources (`--check-fresh`).
+    pub check_fresh: FreshnessCheck,
+    /// Collect backups under `<dir>/<timestamp>/` instead of `<name>.bak`
+    /// beside each destination (`--backup-dir`).
+    pub backup_dir: Option<PathBuf>,
+    /// Make a read-only destination writable and retry once when the copy
+    /// is refused (`--force`).
+    pub force: bool,
+    /// Print a name / size / destination table after the copy phase (text mode).
+    pub sizes: bool,
+    /// Time each copy: `duration_ms` in the summary's `copied` entries, and
+    /// the slowest three in text mode.
+    pub timings: bool,
+    /// Copy binaries, or symlink them to the build output.
+    pub link_mode: LinkMode,
+    /// Stage every binary beside its destination and rename them all into
+    /// place only if none failed; otherwise replace nothing.
+    pub atomic: bool,
+    /// Copies to run at once; `None` uses one per CPU.
+    pub jobs: Option<usize>,
+    /// Hash source and destination after each copy and fail binaries whose
+    /// SHA-256 differs.
+    pub verify: bool,
+    /// Leave destinations whose size and mtime (and hash, with `verify`)
+    /// already match the source.
+    pub skip_unchanged: bool,
+    /// Run `strip` on each deployed copy (Unix only).
+    pub strip: bool,
+    /// Ignore the project's `.mdrcp.toml` / `mdrcp.toml`.
+    pub no_config: bool,
+    /// Which config-file keys the command line set explicitly.
+    pub given: GivenFlags,
+    /// Also deploy `cdylib` libraries declared in the manifests.
+    pub include_libs: bool,
+    /// Also deploy `[[example]]` binaries from `target/<profile>/examples`.
+    pub examples: bool,
+    /// Scan every workspace member, ignoring `workspace.default-members`.
+    pub all_members: bool,
+    /// JSON file listing every deployed file, written after the copy phase.
+    pub manifest_out: Option<PathBuf>,
+    /// Prepended to each deployed executable's name (`--prefix`).
+    pub prefix: Option<String>,
+    /// Appended to each deployed executable's name, before `.exe` (`--suffix`).
+    pub suffix: Option<String>,
+    /// Shell command run before anything is copied; a non-zero exit aborts
+    /// the deploy (`--pre-hook`).
+    pub pre_hook: Option<String>,
+    /// Permission bits for each copy on Unix; `None` derives them from the
+    /// source (see `deployed_mode`).
+    pub mode: Option<u32>,
+}
+
+impl RunOptions {
+    /// Every `--target` in the order given: `target_override`, then
+    /// `extra_targets`. Empty when the default destination is used.
+    pub fn targets(&self) -> impl Iterator<Item = &PathBuf> {
+        self.target_override.iter().chain(&self.extra_targets)
+    }
+
+    /// Whether warnings with `code` were silenced via `--suppress-warning`.
+    pub fn suppresses(&self, code: WarningCode) -> bool {
+        self.suppress_warnings.contains(&code)
+    }
 }
 
 #[cfg(windows)]
@@ -95,6 +372,37 @@ pub fn exe_filename(base: &str) -> String {
     base.to_string()
 }
 
+/// [`exe_filename`] for `base` wrapped in `prefix` and `suffix`, so the
+/// platform extension stays last: `my-` + `foo` gives `my-foo` / `my-foo.exe`.
+pub fn affixed_exe_filename(base: &str, prefix: &str, suffix: &str) -> String {
+    exe_filename(&format!("{}{}{}", prefix, base, suffix))
+}
+
+/// Leading bytes of a native executable on the host platform.
+#[cfg(windows)]
+const HOST_EXE_MAGIC: &[&[u8]] = &[b"MZ"];
+
+#[cfg(target_os = "macos")]
+const HOST_EXE_MAGIC: &[&[u8]] = &[
+    &[0xfe, 0xed, 0xfa, 0xce],
+    &[0xfe, 0xed, 0xfa, 0xcf],
+    &[0xce, 0xfa, 0xed, 0xfe],
+    &[0xcf, 0xfa, 0xed, 0xfe],
+    &[0xca, 0xfe, 0xba, 0xbe],
+];
+
+#[cfg(not(any(windows, target_os = "macos")))]
+const HOST_EXE_MAGIC: &[&[u8]] = &[b"\x7fELF"];
+
+/// Whether `path` starts with the magic bytes of a host-native executable
+/// (ELF on Linux, Mach-O on macOS, PE/MZ on Windows).
+fn has_host_exe_magic(path: &Path) -> std::io::Result<bool> {
+    use std::io::Read;
+    let mut header = Vec::with_capacity(4);
+    fs::File::open(path)?.take(4).read_to_end(&mut header)?;
+    Ok(HOST_EXE_MAGIC.iter().any(|magic| header.starts_with(magic)))
+}
+
 #[cfg(windows)]
 const HINT_DEFAULT: &str = r"c:\apps";
 
@@ -102,20 +410,45 @@ const HINT_DEFAULT: &str = r"c:\apps";
 const HINT_DEFAULT: &str = "~/.local/bin";
 
 const TARGET_OVERRIDE_ENV: &str = "MD_TARGET_DIR";
+/// Target used when no `--target` is given, ahead of the OS default.
+const TARGET_ENV: &str = "MDRCP_TARGET";
+const IGNORE_FILE: &str = ".mdrcpignore";
+/// Cargo's own subdirectories of a profile directory, never scanned by
+/// `--all-files`.
+const CARGO_PROFILE_SUBDIRS: &[&str] =
+    &["build", "deps", "incremental", "examples", ".fingerprint"];
+/// Sources below this size are treated as broken builds unless
+/// `--min-file-size` says otherwise; the default only catches empty files.
+pub const DEFAULT_MIN_FILE_SIZE: u64 = 1;
 
-fn format_file_mtime(path: &Path) -> Option<String> {
-    let modified = fs::metadata(path).ok()?.modified().ok()?;
-    let datetime = time::OffsetDateTime::from(modified);
+fn format_utc(datetime: time::OffsetDateTime) -> Option<String> {
     let fmt =
         time::macros::format_description!("[year]-[month]-[day] [hour]:[minute]:[second] UTC");
     datetime.format(fmt).ok()
 }
 
-fn format_deployment_summary(count: usize, target_dir: &Path, override_used: bool) -> String {
+fn format_file_mtime(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    format_utc(time::OffsetDateTime::from(modified))
+}
+
+/// "Deployed N executable(s) to <dir>", or "Deployed N of M ..." when more
+/// than one binary was in the deploy list.
+fn format_deployment_summary(
+    count: usize,
+    attempted: usize,
+    target_dir: &Path,
+    override_used: bool,
+) -> String {
+    let count = if attempted > 1 {
+        format!("{} of {}", count, attempted)
+    } else {
+        count.to_string()
+    };
     let base = format!(
         "{} {} {} {}",
         "Deployed".bold().green(),
-        count.to_string().bold().green(),
+        count.bold().green(),
         "executable(s) to".dimmed(),
         target_dir.display().to_string().bold().bright_white()
     );
@@ -126,18 +459,125 @@ fn format_deployment_summary(count: usize, target_dir: &Path, override_used: boo
     }
 }
 
-#[derive(Default)]
+/// Stable identifier for each kind of non-fatal warning, serialized in
+/// snake_case so summary consumers can filter on it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCode {
+    RedundantTarget,
+    ExpectCountMismatch,
+    OversizedBinary,
+    SelfUpdateSkipped,
+    XattrFailed,
+    SidecarFailed,
+    ManifestWriteFailed,
+    NotOnPath,
+    DestinationCollision,
+    TargetContainsProject,
+    StripUnsupported,
+    StaleBinary,
+    DuplicateBinary,
+    TimestampFailed,
+    PruneFailed,
+}
+
+impl WarningCode {
+    pub const ALL: &'static [WarningCode] = &[
+        WarningCode::RedundantTarget,
+        WarningCode::ExpectCountMismatch,
+        WarningCode::OversizedBinary,
+        WarningCode::SelfUpdateSkipped,
+        WarningCode::XattrFailed,
+        WarningCode::SidecarFailed,
+        WarningCode::ManifestWriteFailed,
+        WarningCode::NotOnPath,
+        WarningCode::DestinationCollision,
+        WarningCode::TargetContainsProject,
+        WarningCode::StripUnsupported,
+        WarningCode::StaleBinary,
+        WarningCode::DuplicateBinary,
+        WarningCode::TimestampFailed,
+        WarningCode::PruneFailed,
+    ];
+
+    /// The code as it appears in the JSON summary.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            WarningCode::RedundantTarget => "redundant_target",
+            WarningCode::ExpectCountMismatch => "expect_count_mismatch",
+            WarningCode::OversizedBinary => "oversized_binary",
+            WarningCode::SelfUpdateSkipped => "self_update_skipped",
+            WarningCode::XattrFailed => "xattr_failed",
+            WarningCode::SidecarFailed => "sidecar_failed",
+            WarningCode::ManifestWriteFailed => "manifest_write_failed",
+            WarningCode::NotOnPath => "not_on_path",
+            WarningCode::DestinationCollision => "destination_collision",
+            WarningCode::TargetContainsProject => "target_contains_project",
+            WarningCode::StripUnsupported => "strip_unsupported",
+            WarningCode::StaleBinary => "stale_binary",
+            WarningCode::DuplicateBinary => "duplicate_binary",
+            WarningCode::TimestampFailed => "timestamp_failed",
+            WarningCode::PruneFailed => "prune_failed",
+        }
+    }
+
+    /// Look up a code by its summary string, e.g. `"not_on_path"`.
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str() == value)
+    }
+}
+
+/// A non-fatal issue: a stable `code` plus the human-readable message that is
+/// printed in text mode.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    pub code: WarningCode,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 struct OverrideNote {
     lines: Vec<String>,
-    warnings: Vec<String>,
+    warnings: Vec<Warning>,
+}
+
+/// `path` with symlinks resolved when `resolve` is set and the path exists;
+/// otherwise the path as given. Used wherever the target is compared.
+fn comparable_path(path: &Path, resolve: bool) -> PathBuf {
+    if resolve {
+        if let Ok(canonical) = path.canonicalize() {
+            return canonical;
+        }
+    }
+    path.to_path_buf()
 }
 
-fn build_override_note(raw: &Path, resolved: &Path, default_target: Option<&Path>) -> OverrideNote {
+/// `via` names where the override came from: `--target` or the
+/// `MDRCP_TARGET` environment variable.
+fn build_override_note(
+    raw: &Path,
+    resolved: &Path,
+    default_target: Option<&Path>,
+    resolve_symlinks: bool,
+    via: &str,
+) -> OverrideNote {
     let mut note = OverrideNote::default();
     note.lines.push(format!(
         "{} {}",
         "Note:".bold().cyan(),
-        "Destination provided via --target.".dimmed()
+        format!("Destination provided via {}.", via).dimmed()
     ));
     note.lines.push(format!(
         "{} {}",
@@ -155,43 +595,618 @@ fn build_override_note(raw: &Path, resolved: &Path, default_target: Option<&Path
         "Resolved against the project directory.".dimmed()
     ));
     if let Some(default) = default_target {
-        if default == resolved {
+        if comparable_path(default, resolve_symlinks) == comparable_path(resolved, resolve_symlinks)
+        {
             let message = "Resolved target matches default destination; override may be redundant.";
             note.lines.push(format!(
                 "{} {}",
                 "Warning:".bold().yellow(),
                 message.dimmed()
             ));
-            note.warnings.push(message.to_string());
+            note.warnings
+                .push(Warning::new(WarningCode::RedundantTarget, message));
         }
     }
     note
 }
 
-#[derive(Serialize)]
-struct DeploymentSummary {
-    status: &'static str,
-    copied_count: usize,
-    target_dir: String,
-    override_used: bool,
-    copied_binaries: Vec<String>,
-    failed_binaries: Vec<FailedCopy>,
-    warnings: Vec<String>,
+/// Version of the [`DeploymentSummary`] layout. Bump it whenever a field is
+/// added, removed, or changes meaning, so consumers can pin to a schema.
+pub const SUMMARY_SCHEMA_VERSION: u32 = 2;
+
+/// The JSON summary written by `--summary json`, and read back by
+/// `mdrcp merge-summaries`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentSummary {
+    /// [`SUMMARY_SCHEMA_VERSION`] of the writer; `0` for summaries written
+    /// before the field existed.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// `mdrcp` version that wrote the summary; empty for older summaries.
+    #[serde(default)]
+    pub tool_version: String,
+    /// `ok`, `partial`, or `failed`.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_id: Option<String>,
+    pub copied_count: usize,
+    /// `target_dirs` joined with `, `.
+    pub target_dir: String,
+    /// Every deployment directory, in the order given; empty for summaries
+    /// written before schema version 2.
+    #[serde(default)]
+    pub target_dirs: Vec<String>,
+    pub override_used: bool,
+    pub host: HostInfo,
+    pub copied_binaries: Vec<String>,
+    pub skipped_binaries: Vec<String>,
+    pub failed_binaries: Vec<FailedCopy>,
+    pub warnings: Vec<Warning>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unbuilt: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_update: Option<SelfUpdateReport>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// Dry-run diff against the target manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<ManifestDiff>,
+    /// Where each copied binary landed; only present with `--dest`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub destinations: BTreeMap<String, String>,
+    /// Binaries never started because the run was interrupted (Ctrl-C).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub interrupted: Vec<String>,
+    /// `.bak` paths of destinations moved aside by `--on-conflict backup`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub backed_up: Vec<String>,
+    /// The timestamped `--backup-dir` folder `backed_up` files went to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_dir: Option<String>,
+    /// Per-binary details for `copied_binaries`; only present with `--verify`,
+    /// `--strip`, `--timings`, or `--print-sources`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copied: Vec<CopiedBinary>,
+    /// Dynamic libraries copied by `--include-libs`; not counted in
+    /// `copied_count` or listed in `copied_binaries`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub copied_libraries: Vec<String>,
+    /// Why nothing was copied: the `--pre-hook` failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aborted: Option<String>,
+    /// Previously deployed binaries `--prune` deleted from the target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pruned: Vec<String>,
+}
+
+/// One entry of [`DeploymentSummary::copied`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopiedBinary {
+    pub name: String,
+    /// SHA-256 of the deployed file, checked against the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// Debug symbols were removed from the deployed copy (`--strip`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub stripped: bool,
+    /// Milliseconds spent copying the file (`--timings`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Build output the file was copied from (`--print-sources`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// The JSON file written by `--manifest-out`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactManifest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_id: Option<String>,
+    pub target_dir: String,
+    /// Sorted by file name.
+    pub artifacts: Vec<DeployedArtifact>,
+}
+
+/// One entry of [`ArtifactManifest::artifacts`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployedArtifact {
+    pub name: String,
+    /// Absolute destination path.
+    pub path: String,
+    /// Size in bytes of the deployed file.
+    pub size: u64,
+    /// SHA-256 checked by `--verify`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+/// Severity order of summary statuses, for picking the worst when merging.
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "ok" => 0,
+        "partial" => 1,
+        _ => 2,
+    }
+}
+
+impl DeploymentSummary {
+    /// Combine several summaries into one: counts are summed, lists are
+    /// concatenated in input order, and the worst status wins. Fields that
+    /// differ between inputs (`deploy_id`, `host`) keep the first value;
+    /// distinct target directories are listed in `target_dirs` and joined
+    /// with `, ` in `target_dir`. Returns `None` for an empty slice.
+    pub fn merge(summaries: &[DeploymentSummary]) -> Option<DeploymentSummary> {
+        let (first, rest) = summaries.split_first()?;
+        let mut merged = first.clone();
+        let mut targets = Vec::new();
+        for summary in summaries {
+            let dirs = if summary.target_dirs.is_empty() {
+                std::slice::from_ref(&summary.target_dir)
+            } else {
+                summary.target_dirs.as_slice()
+            };
+            for dir in dirs {
+                if !targets.contains(dir) {
+                    targets.push(dir.clone());
+                }
+            }
+        }
+        for summary in rest {
+            if status_rank(&summary.status) > status_rank(&merged.status) {
+                merged.status = summary.status.clone();
+            }
+            if merged.deploy_id.is_none() {
+                merged.deploy_id = summary.deploy_id.clone();
+            }
+            merged.copied_count += summary.copied_count;
+            merged.override_used |= summary.override_used;
+            merged
+                .copied_binaries
+                .extend(summary.copied_binaries.iter().cloned());
+            merged
+                .skipped_binaries
+                .extend(summary.skipped_binaries.iter().cloned());
+            merged
+                .failed_binaries
+                .extend(summary.failed_binaries.iter().cloned());
+            merged.warnings.extend(summary.warnings.iter().cloned());
+            if let Some(unbuilt) = &summary.unbuilt {
+                merged
+                    .unbuilt
+                    .get_or_insert_with(Vec::new)
+                    .extend(unbuilt.iter().cloned());
+            }
+            if merged.self_update.is_none() {
+                merged.self_update = summary.self_update.clone();
+            }
+            merged.dry_run |= summary.dry_run;
+            if let Some(changes) = &summary.changes {
+                let into = merged.changes.get_or_insert_with(ManifestDiff::default);
+                into.added.extend(changes.added.iter().cloned());
+                into.updated.extend(changes.updated.iter().cloned());
+                into.unchanged.extend(changes.unchanged.iter().cloned());
+                into.removed.extend(changes.removed.iter().cloned());
+            }
+            merged.destinations.extend(
+                summary
+                    .destinations
+                    .iter()
+                    .map(|(name, dest)| (name.clone(), dest.clone())),
+            );
+            merged
+                .interrupted
+                .extend(summary.interrupted.iter().cloned());
+            merged.backed_up.extend(summary.backed_up.iter().cloned());
+            merged.pruned.extend(summary.pruned.iter().cloned());
+            if merged.backup_dir.is_none() {
+                merged.backup_dir = summary.backup_dir.clone();
+            }
+            merged.copied.extend(summary.copied.iter().cloned());
+            merged
+                .copied_libraries
+                .extend(summary.copied_libraries.iter().cloned());
+            if merged.aborted.is_none() {
+                merged.aborted = summary.aborted.clone();
+            }
+        }
+        merged.target_dir = targets.join(", ");
+        merged.target_dirs = targets;
+        Some(merged)
+    }
+}
+
+/// Read each JSON summary in `paths`, merge them, and write the result to
+/// `writer` in `format` (`json` or `json-pretty`).
+pub fn write_merged_summaries(
+    writer: &mut dyn Write,
+    paths: &[PathBuf],
+    format: SummaryFormat,
+) -> Result<()> {
+    let mut summaries = Vec::with_capacity(paths.len());
+    for path in paths {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read summary {}", path.display()))?;
+        let summary: DeploymentSummary = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid deployment summary in {}", path.display()))?;
+        summaries.push(summary);
+    }
+    let merged = DeploymentSummary::merge(&summaries).context("No summaries to merge")?;
+    write_summary(writer, format, None, &merged)
+}
+
+fn write_summary(
+    writer: &mut dyn Write,
+    format: SummaryFormat,
+    indent: Option<usize>,
+    summary: &impl Serialize,
+) -> Result<()> {
+    let summary_json = match (format, indent) {
+        (SummaryFormat::Json | SummaryFormat::Ndjson, _) => {
+            serde_json::to_string(summary).context("Failed to serialize deployment summary")?
+        }
+        (SummaryFormat::JsonPretty, None) => serde_json::to_string_pretty(summary)
+            .context("Failed to serialize deployment summary")?,
+        (SummaryFormat::JsonPretty, Some(width)) => {
+            let indent = " ".repeat(width);
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut buf = Vec::new();
+            let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+            summary
+                .serialize(&mut serializer)
+                .context("Failed to serialize deployment summary")?;
+            String::from_utf8(buf).context("Failed to serialize deployment summary")?
+        }
+        (SummaryFormat::Yaml, _) => {
+            let value =
+                serde_json::to_value(summary).context("Failed to serialize deployment summary")?;
+            yaml::to_yaml(&value)
+        }
+        // Rendered from the concrete type by the caller.
+        (SummaryFormat::Text | SummaryFormat::Markdown, _) => unreachable!(),
+    };
+    writeln!(writer, "{}", summary_json)?;
+    Ok(())
+}
+
+/// One binary reported by `mdrcp list`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListedBinary {
+    /// Base name (without `.exe`).
+    pub name: String,
+    /// Whether the built file exists for the selected profile.
+    pub exists: bool,
+    /// Where the built file is, or would be.
+    pub path: String,
+}
+
+/// The `--summary` payload of `mdrcp list`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinaryList {
+    pub binaries: Vec<ListedBinary>,
+}
+
+/// The binaries a deploy with `options` would consider, built or not, sorted
+/// by name. Nothing is copied and no target directory is resolved.
+pub fn list_binaries(project_dir: &Path, options: &RunOptions) -> Result<Vec<ListedBinary>> {
+    let options = config::apply_config(project_dir, options)?;
+    scan_listed(project_dir, &options)
+}
+
+fn scan_listed(project_dir: &Path, options: &RunOptions) -> Result<Vec<ListedBinary>> {
+    // `mdrcp list` has no stderr writer, so discovery tracing is off; the
+    // context only carries `--env-file` for `CARGO_TARGET_DIR`.
+    let mut stdout = std::io::sink();
+    let mut stderr = std::io::sink();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    load_env_file(&mut ctx, project_dir, options)?;
+    let cargo_target = cargo_target_dir(project_dir, options, |key| ctx.var_os(key));
+    let scan = scan_project(
+        project_dir,
+        options,
+        cargo_target.as_deref(),
+        &mut Trace::off(),
+    )?;
+    let mut binaries: Vec<ListedBinary> = scan
+        .discovery
+        .built
+        .into_iter()
+        .map(|binary| ListedBinary {
+            name: binary.base_name,
+            exists: true,
+            path: binary.source.display().to_string(),
+        })
+        .chain(scan.discovery.unbuilt.into_iter().map(|name| {
+            ListedBinary {
+                path: scan
+                    .source_dir
+                    .join(exe_filename(&name))
+                    .display()
+                    .to_string(),
+                name,
+                exists: false,
+            }
+        }))
+        .collect();
+    binaries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(binaries)
+}
+
+/// `mdrcp list`: print [`list_binaries`] as text, or as a `{"binaries": [...]}`
+/// object under `--summary json|json-pretty|yaml`.
+pub fn write_binary_list(
+    writer: &mut dyn Write,
+    project_dir: &Path,
+    options: &RunOptions,
+) -> Result<()> {
+    let options = config::apply_config(project_dir, options)?;
+    let binaries = scan_listed(project_dir, &options)?;
+    if options.summary != SummaryFormat::Text {
+        let list = BinaryList { binaries };
+        if options.summary == SummaryFormat::Markdown {
+            writeln!(writer, "{}", markdown::binary_list(&list))?;
+            return Ok(());
+        }
+        return write_summary(writer, options.summary, options.summary_indent, &list);
+    }
+    if binaries.is_empty() {
+        writeln!(writer, "{}", "No binaries declared.".dimmed())?;
+        return Ok(());
+    }
+    let width = binaries.iter().map(|b| b.name.len()).max().unwrap_or(0);
+    for binary in &binaries {
+        let status = if binary.exists {
+            "built".green().to_string()
+        } else {
+            "not built".dimmed().to_string()
+        };
+        writeln!(
+            writer,
+            "  {:<width$}  {}  {}",
+            binary.name.bold(),
+            status,
+            binary.path.dimmed(),
+            width = width
+        )?;
+    }
+    Ok(())
+}
+
+/// Platform the deploy ran on, from `std::env::consts`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+}
+
+impl HostInfo {
+    fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+/// Outcome of the running-executable handling, reported in the JSON summary.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfUpdateReport {
+    pub attempted: bool,
+    /// One of `spawned`, `replaced`, `skipped`, or `failed`.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SelfUpdateReport {
+    fn new(attempted: bool, status: &str, error: Option<String>) -> Self {
+        Self {
+            attempted,
+            status: status.to_string(),
+            error,
+        }
+    }
+}
+
+/// A binary that could not be deployed, with the reason.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FailedCopy {
+    pub binary: String,
+    pub error: String,
+}
+
+/// Everything a deploy did, without any rendering. Returned by [`deploy`] and
+/// rendered as text or JSON by [`run_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct DeploymentReport {
+    /// Resolved deployment directory.
+    pub target_dir: PathBuf,
+    /// Whether the directory came from `--target`.
+    pub override_used: bool,
+    /// Sorted file names copied into `target_dir`.
+    pub copied: Vec<String>,
+    /// Sorted file names considered but intentionally not copied.
+    pub skipped: Vec<String>,
+    /// Binaries that failed, sorted by name.
+    pub failed: Vec<FailedCopy>,
+    /// Non-fatal issues, sorted by message.
+    pub warnings: Vec<Warning>,
+    /// Declared binaries with no built file for the selected profile.
+    pub unbuilt: Vec<String>,
+    /// Set when a destination was the running mdrcp executable.
+    pub self_update: Option<SelfUpdateReport>,
+    /// Dry-run diff against the target manifest; `None` for real deploys.
+    pub changes: Option<ManifestDiff>,
+    /// Full destination path of each copied file, keyed by file name.
+    pub destinations: BTreeMap<String, PathBuf>,
+    /// Sorted file names never started because the run was interrupted.
+    pub interrupted: Vec<String>,
+    /// Sorted `.bak` paths of destinations moved aside before copying.
+    pub backed_up: Vec<String>,
+    /// The timestamped `--backup-dir` folder, when anything was backed up.
+    pub backup_dir: Option<PathBuf>,
+    /// SHA-256 of each copied file checked by `--verify`, keyed by file name.
+    pub sha256: BTreeMap<String, String>,
+    /// Time spent copying each file under `--timings`, keyed by file name.
+    pub timings: BTreeMap<String, std::time::Duration>,
+    /// Source of each copied file, keyed by file name; relative to the
+    /// project under `relativize_sources`.
+    pub sources: BTreeMap<String, PathBuf>,
+    /// The `cdylib` files among `copied` (`--include-libs`), sorted.
+    pub copied_libraries: Vec<String>,
+    /// Set when the `--pre-hook` failed and nothing was copied.
+    pub aborted: Option<String>,
+    /// Sorted file names `--prune` deleted from `target_dir`.
+    pub pruned: Vec<String>,
+    /// Wall-clock time from start to finish.
+    pub elapsed: std::time::Duration,
+    /// Reports for [`RunOptions::extra_targets`], in order; only [`deploy`]
+    /// fills this.
+    pub extra_targets: Vec<DeploymentReport>,
+    override_note: Option<OverrideNote>,
+    plan_only: bool,
+}
+
+impl DeploymentReport {
+    /// `ok`, `partial`, or `failed`, as in the JSON summary.
+    pub fn status(&self) -> &'static str {
+        if self.aborted.is_some() {
+            "failed"
+        } else if self.failed.is_empty() && self.interrupted.is_empty() {
+            "ok"
+        } else if !self.copied.is_empty() {
+            "partial"
+        } else {
+            "failed"
+        }
+    }
+
+    /// This report followed by [`extra_targets`](Self::extra_targets).
+    fn all_targets(&self) -> impl Iterator<Item = &DeploymentReport> {
+        std::iter::once(self).chain(&self.extra_targets)
+    }
+
+    fn failure_message(&self) -> Option<String> {
+        if !self.extra_targets.is_empty() {
+            let failed = self.all_targets().filter(|r| r.status() != "ok").count();
+            (failed > 0).then(|| {
+                format!(
+                    "Deploy failed for {} of {} targets",
+                    failed,
+                    self.extra_targets.len() + 1
+                )
+            })
+        } else if self.aborted.is_some() {
+            self.aborted.clone()
+        } else if self.failed.is_empty() && !self.interrupted.is_empty() {
+            Some(
+                interrupt::Interrupted {
+                    remaining: self.interrupted.len(),
+                }
+                .to_string(),
+            )
+        } else if self.failed.is_empty() {
+            None
+        } else if !self.copied.is_empty() {
+            Some(format!(
+                "Failed to copy {} of {} executables (copied {} successfully)",
+                self.failed.len(),
+                self.copied.len() + self.failed.len(),
+                self.copied.len()
+            ))
+        } else {
+            Some(format!(
+                "Failed to copy {} executable(s)",
+                self.failed.len()
+            ))
+        }
+    }
+}
+
+/// Why [`deploy`] or [`run_with_options`] did not fully succeed. The
+/// latter returns it inside `anyhow::Error`; see [`DeployError::exit_code`].
+#[derive(Debug)]
+pub enum DeployError {
+    /// No `Cargo.toml` where the project should be.
+    NotRustProject(String),
+    /// The manifests were read but nothing is built for the selected profile.
+    NoBuiltBinaries(String),
+    /// Nothing was attempted: unreadable manifests, an unusable target
+    /// directory, or a `strict` violation.
+    Setup(anyhow::Error),
+    /// Some binaries failed, the `--pre-hook` aborted the deploy, or the run
+    /// was interrupted; the report also lists what was deployed.
+    Failed(Box<DeploymentReport>),
+}
+
+impl DeployError {
+    /// Process exit status for this failure: 2 not a Rust project, 3 no
+    /// built executables, 4 some copies failed, 5 every copy failed (or the
+    /// `--pre-hook` aborted), [`interrupt::EXIT_CODE`] interrupted, 1
+    /// anything else.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DeployError::NotRustProject(_) => 2,
+            DeployError::NoBuiltBinaries(_) => 3,
+            DeployError::Failed(report)
+                if report.all_targets().any(|r| !r.interrupted.is_empty()) =>
+            {
+                interrupt::EXIT_CODE
+            }
+            DeployError::Failed(report) if report.all_targets().any(|r| !r.copied.is_empty()) => 4,
+            DeployError::Failed(_) => 5,
+            DeployError::Setup(_) => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for DeployError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeployError::NotRustProject(message) | DeployError::NoBuiltBinaries(message) => {
+                write!(f, "{}", message)
+            }
+            DeployError::Setup(e) => write!(f, "{}", e),
+            DeployError::Failed(report) => {
+                write!(f, "{}", report.failure_message().unwrap_or_default())
+            }
+        }
+    }
 }
 
-#[derive(Clone, Serialize)]
-struct FailedCopy {
-    binary: String,
-    error: String,
+impl std::error::Error for DeployError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeployError::Setup(e) => Some(e.as_ref()),
+            DeployError::NotRustProject(_)
+            | DeployError::NoBuiltBinaries(_)
+            | DeployError::Failed(_) => None,
+        }
+    }
 }
 
 pub fn do_main_with_options(cwd: &Path, options: &RunOptions) -> i32 {
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
     let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.cancel = interrupt::flag();
     match run_with_options(cwd, options, &mut ctx) {
         Ok(()) => 0,
+        Err(e) if options.silent => {
+            if e.is::<interrupt::Interrupted>() {
+                interrupt::EXIT_CODE
+            } else {
+                e.downcast_ref::<DeployError>()
+                    .map_or(1, DeployError::exit_code)
+            }
+        }
+        Err(e) if e.is::<interrupt::Interrupted>() => {
+            let _ = writeln!(ctx.stderr, "{} {}", "Error:".bold().bright_red(), e);
+            interrupt::EXIT_CODE
+        }
         Err(e) => {
+            let code = e
+                .downcast_ref::<DeployError>()
+                .map_or(1, DeployError::exit_code);
             let _ = writeln!(ctx.stderr, "{} {}", "Error:".bold().bright_red(), e);
             let _ = writeln!(ctx.stderr);
             let _ = writeln!(
@@ -200,7 +1215,7 @@ pub fn do_main_with_options(cwd: &Path, options: &RunOptions) -> i32 {
                 "Usage:".bold().yellow(),
                 "mdrcp [OPTIONS]".bold()
             );
-            let hint = match default_target_dir() {
+            let hint = match platform_default_target(&ctx, cwd, options) {
                 Ok(p) => p.display().to_string(),
                 Err(_) => HINT_DEFAULT.to_string(),
             };
@@ -227,7 +1242,7 @@ pub fn do_main_with_options(cwd: &Path, options: &RunOptions) -> i32 {
                 "Docs:".bold().cyan(),
                 "See README.md troubleshooting section".dimmed()
             );
-            1
+            code
         }
     }
 }
@@ -293,6 +1308,168 @@ fn package_version(manifest: &Value, workspace_root: &Value) -> Option<String> {
 struct BuiltBinary {
     base_name: String,
     version: Option<String>,
+    /// Owning package from the manifest; `None` for glob/all-files discovery.
+    package: Option<String>,
+    /// Destination name from `[package.metadata.mdrcp] rename` / `deploy_name`.
+    deploy_name: Option<String>,
+    /// Destination directory from `[package.metadata.mdrcp] target` / `dest`,
+    /// absolute.
+    dest: Option<PathBuf>,
+    /// Size of the built file in bytes, recorded during discovery.
+    size: u64,
+    /// Full path of the built file.
+    source: PathBuf,
+    /// A `cdylib` picked up by `--include-libs` rather than an executable.
+    library: bool,
+    /// Directory of the manifest that declared it; `None` for glob/all-files
+    /// discovery.
+    crate_dir: Option<PathBuf>,
+}
+
+impl BuiltBinary {
+    /// File name at the destination (`foo` / `foo.exe`): the source file name
+    /// unless the manifest metadata or `--prefix` / `--suffix` renames it.
+    fn file_name(&self) -> String {
+        if let Some(name) = &self.deploy_name {
+            return exe_filename(name);
+        }
+        self.source
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| exe_filename(&self.base_name))
+    }
+}
+
+/// Total bytes to copy across all discovered binaries (the progress pre-pass).
+fn total_source_bytes(binaries: &[BuiltBinary]) -> u64 {
+    binaries.iter().map(|b| b.size).sum()
+}
+
+/// Render a progress line: file counter, byte percentage, and an ETA derived
+/// from the throughput observed so far. The ETA is omitted until data has moved.
+fn format_progress(
+    done_files: usize,
+    total_files: usize,
+    done_bytes: u64,
+    total_bytes: u64,
+    elapsed: std::time::Duration,
+) -> String {
+    let percent = done_bytes
+        .saturating_mul(100)
+        .checked_div(total_bytes)
+        .unwrap_or(100);
+    let secs = elapsed.as_secs_f64();
+    let eta = if done_bytes > 0 && secs > 0.0 {
+        let rate = done_bytes as f64 / secs;
+        let remaining = total_bytes.saturating_sub(done_bytes) as f64 / rate;
+        format!(" ETA {}s", remaining.ceil() as u64)
+    } else {
+        String::new()
+    };
+    format!(
+        "[{}/{}] {}% ({} of {} bytes){}",
+        done_files, total_files, percent, done_bytes, total_bytes, eta
+    )
+}
+
+/// Dimmed `[i/N] ` prefix for the log lines of the `index`th (from zero) of
+/// `total` binaries; empty for a single-binary deploy.
+fn deploy_counter(index: usize, total: usize) -> String {
+    if total <= 1 {
+        return String::new();
+    }
+    format!("{} ", format!("[{}/{}]", index + 1, total).dimmed())
+}
+
+/// Human-readable size: bytes below 1 KiB, then KiB / MiB / GiB to one decimal.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// Lay out `(name, size, destination)` rows as aligned lines: names padded to
+/// the longest, sizes right-aligned.
+fn format_sizes_table(rows: &[(String, u64, PathBuf)]) -> Vec<String> {
+    let sizes: Vec<String> = rows.iter().map(|(_, size, _)| format_size(*size)).collect();
+    let name_width = rows.iter().map(|(name, ..)| name.len()).max().unwrap_or(0);
+    let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+    rows.iter()
+        .zip(&sizes)
+        .map(|((name, _, dest), size)| {
+            format!(
+                "  {:<name_width$}  {:>size_width$}  {}",
+                name,
+                size,
+                dest.display()
+            )
+        })
+        .collect()
+}
+
+/// `--timings`: the `limit` slowest copies, slowest first, as aligned
+/// `name  N ms` lines.
+fn format_slowest(timings: &BTreeMap<String, std::time::Duration>, limit: usize) -> Vec<String> {
+    let mut rows: Vec<(&String, &std::time::Duration)> = timings.iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    rows.truncate(limit);
+    let name_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(name, elapsed)| format!("  {:<name_width$}  {} ms", name, elapsed.as_millis()))
+        .collect()
+}
+
+/// Discovery diagnostics for `-v`, written to stderr when `verbosity` reaches
+/// the message's level.
+struct Trace<'a> {
+    verbosity: u8,
+    out: Option<&'a mut dyn Write>,
+}
+
+impl<'a> Trace<'a> {
+    fn new(verbosity: u8, out: &'a mut dyn Write) -> Self {
+        Self {
+            verbosity,
+            out: Some(out),
+        }
+    }
+
+    /// A trace that logs nothing.
+    fn off() -> Self {
+        Self {
+            verbosity: 0,
+            out: None,
+        }
+    }
+
+    fn log(&mut self, level: u8, message: std::fmt::Arguments) -> std::io::Result<()> {
+        match self.out.as_mut() {
+            Some(out) if self.verbosity >= level => {
+                writeln!(out, "{} {}", "debug:".dimmed(), message)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Outcome of binary discovery: the executables present for the selected
+/// profile, plus the declared names that had no built file.
+#[derive(Debug)]
+struct Discovery {
+    built: Vec<BuiltBinary>,
+    /// Sorted base names declared in a manifest but missing from `target/<profile>/`.
+    unbuilt: Vec<String>,
+    /// Base names declared by more than one member, as `(name, kept member,
+    /// ignored member)`; the member scanned first keeps the name.
+    duplicates: Vec<(String, String, String)>,
 }
 
 /// Expand a workspace member pattern into concrete directory paths.
@@ -355,65 +1532,232 @@ fn autobin_names(pkg_dir: &Path) -> Vec<String> {
     names
 }
 
-/// Find all built executables from workspace members or single package.
-/// Returns the base names of executables (without `.exe`).
-/// `rust_base_dir` is the directory containing Cargo.toml and target/.
-fn find_built_executables(
-    rust_base_dir: &Path,
-    cargo_data: &Value,
-    profile: BuildProfile,
-    extra_names: &[String],
-) -> Result<Vec<BuiltBinary>> {
-    let profile_dir = rust_base_dir.join("target").join(profile.artifact_dir());
-    // Map base name -> package version (first writer wins for duplicate names).
-    let mut candidates: HashMap<String, Option<String>> = HashMap::new();
+/// Read binary-name glob patterns from `.mdrcpignore` in `dir`: one pattern per
+/// line, blank lines and `#` comments skipped. A missing file means no patterns.
+fn read_ignore_patterns(dir: &Path) -> Result<Vec<glob::Pattern>> {
+    let path = dir.join(IGNORE_FILE);
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    let mut patterns = Vec::new();
+    for (lineno, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let pattern = glob::Pattern::new(line).with_context(|| {
+            format!(
+                "Invalid pattern '{}' on line {} of {}",
+                line,
+                lineno + 1,
+                path.display()
+            )
+        })?;
+        patterns.push(pattern);
+    }
+    Ok(patterns)
+}
+
+/// The package a declared binary belongs to, as read from its manifest,
+/// including its `[package.metadata.mdrcp]` deploy settings.
+#[derive(Clone, Debug, Default)]
+struct Candidate {
+    version: Option<String>,
+    package: Option<String>,
+    /// `rename` (or its older spelling `deploy_name`): a string (renames the
+    /// package-named binary) or a table of binary name -> deployed name.
+    deploy_name: Option<Value>,
+    /// `target` (or `dest`), resolved against the manifest's directory.
+    dest: Option<PathBuf>,
+    /// `skip = true`: the package never deploys.
+    skip: bool,
+    /// `exclude`: glob patterns for this package's binaries to leave out.
+    exclude: Vec<glob::Pattern>,
+    /// Directory holding the manifest.
+    crate_dir: PathBuf,
+}
+
+impl Candidate {
+    fn of(manifest: &Value, workspace_root: &Value, manifest_dir: &Path) -> Self {
+        let package = manifest.get("package");
+        let metadata = package
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("mdrcp"));
+        let setting =
+            |new: &str, old: &str| metadata.and_then(|m| m.get(new).or_else(|| m.get(old)));
+        // A single pattern may be given as a bare string; unparsable ones are
+        // ignored like the rest of the metadata's malformed values.
+        let exclude = match metadata.and_then(|m| m.get("exclude")) {
+            Some(Value::String(raw)) => vec![raw.as_str()],
+            Some(Value::Array(raw)) => raw.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            version: package_version(manifest, workspace_root),
+            package: package
+                .and_then(|p| p.get("name"))
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            deploy_name: setting("rename", "deploy_name").cloned(),
+            dest: setting("target", "dest")
+                .and_then(Value::as_str)
+                .map(|dest| manifest_dir.join(dest)),
+            skip: metadata
+                .and_then(|m| m.get("skip"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            exclude: exclude
+                .into_iter()
+                .filter_map(|raw| glob::Pattern::new(raw).ok())
+                .collect(),
+            crate_dir: manifest_dir.to_path_buf(),
+        }
+    }
+
+    /// The package name, or the crate directory's name when it has none; used
+    /// to tell members apart in messages.
+    fn label(&self) -> String {
+        self.package.clone().unwrap_or_else(|| {
+            self.crate_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| self.crate_dir.display().to_string())
+        })
+    }
+
+    /// Deployed base name for binary `base`, if the metadata renames it.
+    fn deploy_name_for(&self, base: &str) -> Option<String> {
+        match self.deploy_name.as_ref()? {
+            Value::String(name) if self.package.as_deref() == Some(base) => Some(name.clone()),
+            Value::Table(names) => names.get(base)?.as_str().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+/// Which declared binaries a deploy wants.
+#[derive(Clone, Copy, Debug, Default)]
+struct Selection<'a> {
+    /// `--bin` names; empty means every binary.
+    only: &'a [String],
+    /// `--all`: scan every workspace member, not just `default-members`.
+    all_members: bool,
+}
+
+/// Workspace member directories to scan: the `workspace.default-members`
+/// entries when the manifest declares them (unless `all_members` asks for
+/// every `members` entry), expanded via [`expand_workspace_member`], minus
+/// anything under a `workspace.exclude` path.
+fn workspace_member_dirs(
+    rust_base_dir: &Path,
+    cargo_data: &Value,
+    all_members: bool,
+) -> Vec<PathBuf> {
+    let list = |key: &str| {
+        cargo_data
+            .get("workspace")
+            .and_then(|ws| ws.get(key))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+    };
+    let patterns: Vec<&str> = match list("default-members").collect::<Vec<_>>() {
+        defaults if !defaults.is_empty() && !all_members => defaults,
+        _ => list("members").collect(),
+    };
+    let excluded: Vec<PathBuf> = list("exclude")
+        .map(|path| rust_base_dir.join(path))
+        .collect();
+    patterns
+        .into_iter()
+        .flat_map(|pattern| expand_workspace_member(rust_base_dir, pattern))
+        .filter(|dir| !excluded.iter().any(|path| dir.starts_with(path)))
+        .collect()
+}
+
+/// Find all built executables from workspace members or single package.
+/// Returns the base names of executables (without `.exe`) alongside the
+/// declared names that were not built for the selected profile.
+/// `rust_base_dir` is the directory containing Cargo.toml; `profile_dir` is the
+/// directory holding the built artifacts (normally `target/<profile>`).
+/// Naming binaries with `--bin` scans every workspace member.
+fn find_built_executables(
+    rust_base_dir: &Path,
+    cargo_data: &Value,
+    profile_dir: &Path,
+    extra_names: &[String],
+    exclude: &[glob::Pattern],
+    selection: Selection,
+    trace: &mut Trace,
+) -> Result<Discovery> {
+    let only = selection.only;
+    // Map base name -> owning package (first writer wins for duplicate names).
+    let mut candidates: HashMap<String, Candidate> = HashMap::new();
+    // Names a later member also declares, with that member, so the conflict
+    // can be reported rather than one of them silently winning.
+    let mut shadowed: Vec<(String, Candidate)> = Vec::new();
 
     // Root package (if any). The root manifest is its own workspace root.
-    let root_version = package_version(cargo_data, cargo_data);
+    trace.log(
+        1,
+        format_args!("reading {}", rust_base_dir.join("Cargo.toml").display()),
+    )?;
+    let root = Candidate::of(cargo_data, cargo_data, rust_base_dir);
     for name in manifest_bin_names(cargo_data)
         .into_iter()
         .chain(autobin_names(rust_base_dir))
     {
-        candidates
-            .entry(name)
-            .or_insert_with(|| root_version.clone());
+        trace.log(1, format_args!("  candidate {}", name))?;
+        candidates.entry(name).or_insert_with(|| root.clone());
     }
 
     // Add extra names (e.g., from tauri.conf.json productName); these belong to
     // the root (src-tauri) package, so they share its version.
     for name in extra_names {
+        trace.log(1, format_args!("  candidate {} (tauri.conf.json)", name))?;
         candidates
             .entry(name.clone())
-            .or_insert_with(|| root_version.clone());
+            .or_insert_with(|| root.clone());
     }
 
-    // Workspace members (if any). Member entries may contain glob patterns
-    // (e.g. "crates/*") which Cargo expands to matching directories.
-    if let Some(members) = cargo_data
-        .get("workspace")
-        .and_then(|ws| ws.get("members"))
-        .and_then(|m| m.as_array())
-    {
-        for member in members {
-            let Some(member_pattern) = member.as_str() else {
-                continue;
-            };
-            for member_dir in expand_workspace_member(rust_base_dir, member_pattern) {
-                let member_manifest_path = member_dir.join("Cargo.toml");
-                let Ok(contents) = fs::read_to_string(&member_manifest_path) else {
-                    continue;
-                };
-                let Ok(member_data) = toml::from_str::<Value>(&contents) else {
-                    continue;
-                };
-                let member_version = package_version(&member_data, cargo_data);
-                for name in manifest_bin_names(&member_data)
-                    .into_iter()
-                    .chain(autobin_names(&member_dir))
+    // Workspace members (if any), narrowed to `default-members` when declared
+    // and minus `exclude`. Member entries may contain glob patterns (e.g.
+    // "crates/*") which Cargo expands to matching directories.
+    let all_members = selection.all_members || !only.is_empty();
+    for member_dir in workspace_member_dirs(rust_base_dir, cargo_data, all_members) {
+        let member_manifest_path = member_dir.join("Cargo.toml");
+        trace.log(
+            1,
+            format_args!("reading {}", member_manifest_path.display()),
+        )?;
+        let Ok(contents) = fs::read_to_string(&member_manifest_path) else {
+            trace.log(1, format_args!("  skipped: unreadable"))?;
+            continue;
+        };
+        let Ok(member_data) = toml::from_str::<Value>(&contents) else {
+            trace.log(1, format_args!("  skipped: not valid TOML"))?;
+            continue;
+        };
+        let member = Candidate::of(&member_data, cargo_data, &member_dir);
+        for name in manifest_bin_names(&member_data)
+            .into_iter()
+            .chain(autobin_names(&member_dir))
+        {
+            trace.log(1, format_args!("  candidate {}", name))?;
+            match candidates.get(&name) {
+                Some(first)
+                    if first.package != member.package && first.crate_dir != member.crate_dir =>
                 {
-                    candidates
-                        .entry(name)
-                        .or_insert_with(|| member_version.clone());
+                    trace.log(
+                        1,
+                        format_args!("  duplicate of {}'s {}", first.label(), name),
+                    )?;
+                    shadowed.push((name, member.clone()));
+                }
+                Some(_) => {}
+                None => {
+                    candidates.insert(name, member.clone());
                 }
             }
         }
@@ -423,194 +1767,383 @@ fn find_built_executables(
         anyhow::bail!("No packages or bins found in Cargo.toml");
     }
 
+    // A typo in --bin should fail loudly, not quietly deploy nothing.
+    if let Some(missing) = only.iter().find(|name| !candidates.contains_key(*name)) {
+        anyhow::bail!(
+            "requested binary '{}' not declared in any manifest",
+            missing
+        );
+    }
+    // Drop names the project never wants deployed: `.mdrcpignore` patterns and
+    // packages that opt out via `[package.metadata.mdrcp] skip = true` or
+    // list the name in its `exclude`.
+    let ignored = read_ignore_patterns(rust_base_dir)?;
+    let mut dropped: Vec<(String, &str)> = Vec::new();
+    candidates.retain(|name, candidate| {
+        let reason = if !only.is_empty() && !only.contains(name) {
+            Some("not named by --bin")
+        } else if candidate.skip {
+            Some("package metadata sets skip")
+        } else if candidate.exclude.iter().any(|p| p.matches(name)) {
+            Some("package metadata exclude")
+        } else if ignored.iter().any(|p| p.matches(name)) {
+            Some(IGNORE_FILE)
+        } else if exclude.iter().any(|p| p.matches(name)) {
+            Some("--exclude")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            dropped.push((name.clone(), reason));
+        }
+        reason.is_none()
+    });
+    dropped.sort();
+    for (name, reason) in &dropped {
+        trace.log(1, format_args!("dropped {} ({})", name, reason))?;
+    }
+    if !exclude.is_empty() && candidates.is_empty() {
+        anyhow::bail!("All binaries excluded by --exclude patterns");
+    }
+    // A shadowed declaration only matters if both sides would have deployed.
+    let mut duplicates: Vec<(String, String, String)> = shadowed
+        .into_iter()
+        .filter(|(name, second)| !second.skip && !second.exclude.iter().any(|p| p.matches(name)))
+        .filter_map(|(name, second)| {
+            let first = candidates.get(&name)?.label();
+            Some((name, first, second.label()))
+        })
+        .collect();
+    duplicates.sort();
+
+    if trace.verbosity >= 2 {
+        trace.log(2, format_args!("scanning {}", profile_dir.display()))?;
+        let mut entries: Vec<String> = fs::read_dir(profile_dir)
+            .map(|dir| {
+                dir.flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort();
+        if entries.is_empty() {
+            trace.log(2, format_args!("  (empty or missing)"))?;
+        }
+        for entry in entries {
+            trace.log(2, format_args!("  {}", entry))?;
+        }
+    }
+
     // Filter to only candidates with existing executables for the selected profile
     let mut built_executables = Vec::new();
-    for (base, version) in candidates {
+    let mut unbuilt = Vec::new();
+    let mut candidates: Vec<(String, Candidate)> = candidates.into_iter().collect();
+    candidates.sort_by(|a, b| a.0.cmp(&b.0));
+    for (base, candidate) in candidates {
         let exe_name = exe_filename(&base);
-        if profile_dir.join(&exe_name).exists() {
+        let exe_path = profile_dir.join(&exe_name);
+        let exists = exe_path.exists();
+        trace.log(
+            2,
+            format_args!(
+                "checking {}: {}",
+                exe_path.display(),
+                if exists { "found" } else { "missing" }
+            ),
+        )?;
+        if exists {
+            let size = fs::metadata(&exe_path).map(|m| m.len()).unwrap_or(0);
             built_executables.push(BuiltBinary {
+                deploy_name: candidate.deploy_name_for(&base),
                 base_name: base,
-                version,
+                version: candidate.version,
+                package: candidate.package,
+                dest: candidate.dest,
+                size,
+                source: exe_path,
+                library: false,
+                crate_dir: Some(candidate.crate_dir),
             });
+        } else {
+            unbuilt.push(base);
         }
     }
-    Ok(built_executables)
-}
-
-/// Determine the default deployment target directory per-OS.
-fn target_dir_override_from_env() -> Result<Option<PathBuf>> {
-    if let Some(raw) = std::env::var_os(TARGET_OVERRIDE_ENV) {
-        if raw.is_empty() {
-            anyhow::bail!(
-                "{} is set but empty; provide an absolute path",
-                TARGET_OVERRIDE_ENV
-            );
-        }
-        return Ok(Some(PathBuf::from(raw)));
-    }
-    Ok(None)
+    // Deterministic order so copy logs and summaries are stable between runs.
+    built_executables.sort_by(|a, b| a.base_name.cmp(&b.base_name));
+    unbuilt.sort();
+    Ok(Discovery {
+        built: built_executables,
+        unbuilt,
+        duplicates,
+    })
 }
 
-#[cfg(windows)]
-fn default_target_dir() -> Result<PathBuf> {
-    if let Some(custom) = target_dir_override_from_env()? {
-        return Ok(custom);
+/// `cdylib` name declared by a manifest: `[lib].name`, or the package name
+/// with `-` mapped to `_` as Cargo does. `None` unless `crate-type` lists
+/// `cdylib`.
+fn manifest_cdylib_name(manifest: &Value) -> Option<String> {
+    let lib = manifest.get("lib")?;
+    let is_cdylib = lib
+        .get("crate-type")
+        .and_then(Value::as_array)
+        .is_some_and(|types| types.iter().any(|t| t.as_str() == Some("cdylib")));
+    if !is_cdylib {
+        return None;
     }
-    Ok(PathBuf::from(r"c:\apps"))
+    lib.get("name")
+        .and_then(Value::as_str)
+        .or_else(|| manifest.get("package")?.get("name")?.as_str())
+        .map(|name| name.replace('-', "_"))
 }
 
-#[cfg(not(windows))]
-fn default_target_dir() -> Result<PathBuf> {
-    if let Some(custom) = target_dir_override_from_env()? {
-        return Ok(custom);
-    }
-    let home = std::env::var_os("HOME")
-        .ok_or_else(|| anyhow::anyhow!("HOME is not set; cannot determine ~/.local/bin"))?;
-    Ok(Path::new(&home).join(".local").join("bin"))
+/// Platform file name of a dynamic library: `libfoo.so`, `libfoo.dylib`, or
+/// `foo.dll`.
+fn library_filename(name: &str) -> String {
+    format!(
+        "{}{}{}",
+        std::env::consts::DLL_PREFIX,
+        name,
+        std::env::consts::DLL_SUFFIX
+    )
 }
 
-/// Check if the target path is our own running executable
-fn is_self_update_target(target_path: &Path, current_exe_override: Option<&Path>) -> bool {
-    let current_exe = if let Some(p) = current_exe_override {
-        p.to_path_buf()
-    } else {
-        match std::env::current_exe() {
-            Ok(exe) => exe,
-            Err(_) => return false,
-        }
-    };
-    let current_exe_canonical = current_exe.canonicalize().unwrap_or(current_exe);
-    let target_canonical = target_path
-        .canonicalize()
-        .unwrap_or_else(|_| target_path.to_path_buf());
-    current_exe_canonical == target_canonical
+/// `[[example]]` names declared by a manifest.
+fn manifest_example_names(manifest: &Value) -> Vec<String> {
+    manifest
+        .get("example")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|example| example.get("name")?.as_str())
+        .map(str::to_string)
+        .collect()
 }
 
-/// Result of attempting a self-update
-enum SelfUpdateResult {
-    /// Not a self-update scenario
-    NotApplicable,
-    /// Self-update spawned successfully - caller should exit
-    Spawned,
-    /// Self-update failed to spawn
-    Failed(String),
+/// Build outputs beyond `[[bin]]` targets that discovery can opt into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArtifactKind {
+    /// `cdylib` libraries (`--include-libs`).
+    Library,
+    /// `[[example]]` binaries (`--examples`).
+    Example,
 }
 
-/// Check if we're trying to overwrite our own executable, and if so,
-/// spawn a temp copy to perform the update.
-fn try_self_update(
-    source_path: &Path,
-    target_path: &Path,
-    current_exe_override: Option<&Path>,
-) -> SelfUpdateResult {
-    // Get canonical path of current executable
-    let current_exe = if let Some(p) = current_exe_override {
-        p.to_path_buf()
-    } else {
-        match std::env::current_exe() {
-            Ok(exe) => exe,
-            Err(_) => return SelfUpdateResult::NotApplicable,
+impl ArtifactKind {
+    fn names(self, manifest: &Value) -> Vec<String> {
+        match self {
+            ArtifactKind::Library => manifest_cdylib_name(manifest).into_iter().collect(),
+            ArtifactKind::Example => manifest_example_names(manifest),
         }
-    };
-
-    // Canonicalize paths for comparison
-    let current_exe_canonical = current_exe.canonicalize().unwrap_or(current_exe);
-    let target_canonical = target_path
-        .canonicalize()
-        .unwrap_or_else(|_| target_path.to_path_buf());
-
-    // Check if we're trying to overwrite ourselves
-    if current_exe_canonical != target_canonical {
-        return SelfUpdateResult::NotApplicable;
     }
 
-    // We're updating ourselves - copy current exe to temp and spawn it
-    let temp_dir = std::env::temp_dir();
-    let updater_path = temp_dir.join(UPDATER_TEMP_NAME);
+    /// Where Cargo writes artifact `name` under `profile_dir`.
+    fn source(self, profile_dir: &Path, name: &str) -> PathBuf {
+        match self {
+            ArtifactKind::Library => profile_dir.join(library_filename(name)),
+            ArtifactKind::Example => profile_dir.join("examples").join(exe_filename(name)),
+        }
+    }
+}
 
-    // Copy current executable to temp location
-    if let Err(e) = fs::copy(&current_exe_canonical, &updater_path) {
-        return SelfUpdateResult::Failed(format!("Failed to copy self to temp: {}", e));
+/// `--include-libs` / `--examples` discovery: artifacts of `kind` declared by
+/// the root manifest or any scanned workspace member (see
+/// [`workspace_member_dirs`]) that exist under `profile_dir`.
+/// Names matching `.mdrcpignore` or `--exclude` are left out, as for
+/// executables.
+fn find_built_artifacts(
+    kind: ArtifactKind,
+    rust_base_dir: &Path,
+    cargo_data: &Value,
+    profile_dir: &Path,
+    exclude: &[glob::Pattern],
+    all_members: bool,
+) -> Result<Vec<BuiltBinary>> {
+    let mut manifests = vec![(cargo_data.clone(), rust_base_dir.to_path_buf())];
+    for member_dir in workspace_member_dirs(rust_base_dir, cargo_data, all_members) {
+        let Ok(contents) = fs::read_to_string(member_dir.join("Cargo.toml")) else {
+            continue;
+        };
+        if let Ok(member_data) = toml::from_str::<Value>(&contents) {
+            manifests.push((member_data, member_dir));
+        }
     }
 
-    // Spawn the temp copy with --finish-update
-    match ProcessCommand::new(&updater_path)
-        .arg("--finish-update")
-        .arg(source_path)
-        .arg(target_path)
-        .spawn()
-    {
-        Ok(_) => SelfUpdateResult::Spawned,
-        Err(e) => SelfUpdateResult::Failed(format!("Failed to spawn updater: {}", e)),
+    let ignored = read_ignore_patterns(rust_base_dir)?;
+    let mut found: Vec<BuiltBinary> = Vec::new();
+    for (manifest, manifest_dir) in &manifests {
+        let candidate = Candidate::of(manifest, cargo_data, manifest_dir);
+        for name in kind.names(manifest) {
+            if candidate.skip
+                || ignored.iter().chain(exclude).any(|p| p.matches(&name))
+                || found.iter().any(|b| b.base_name == name)
+            {
+                continue;
+            }
+            let source = kind.source(profile_dir, &name);
+            let Ok(metadata) = fs::metadata(&source) else {
+                continue;
+            };
+            found.push(BuiltBinary {
+                base_name: name,
+                version: candidate.version.clone(),
+                package: candidate.package.clone(),
+                deploy_name: None,
+                dest: candidate.dest.clone(),
+                size: metadata.len(),
+                source,
+                library: kind == ArtifactKind::Library,
+                crate_dir: Some(candidate.crate_dir.clone()),
+            });
+        }
     }
+    found.sort_by(|a, b| a.base_name.cmp(&b.base_name));
+    Ok(found)
 }
 
-/// Detect whether this is a Tauri project by checking for src-tauri/Cargo.toml
-/// and tauri.conf.json (or .json5) in the src-tauri directory.
-fn detect_project_type(project_dir: &Path) -> ProjectType {
-    let tauri_cargo = project_dir.join("src-tauri").join("Cargo.toml");
-    let tauri_conf = project_dir.join("src-tauri").join("tauri.conf.json");
-    let tauri_conf5 = project_dir.join("src-tauri").join("tauri.conf.json5");
-
-    if tauri_cargo.exists() && (tauri_conf.exists() || tauri_conf5.exists()) {
-        ProjectType::Tauri
-    } else {
-        ProjectType::Standard
+/// Base name for a discovered file: the file name without the platform's
+/// executable suffix.
+fn base_name_of(path: &Path) -> String {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.strip_suffix(std::env::consts::EXE_SUFFIX) {
+        Some(stem) if !std::env::consts::EXE_SUFFIX.is_empty() => stem.to_string(),
+        _ => name,
     }
 }
 
-/// Read the productName from tauri.conf.json or tauri.conf.json5.
-/// Returns None if file doesn't exist or productName is not set.
-fn read_tauri_product_name(project_dir: &Path) -> Option<String> {
-    let conf_paths = [
-        project_dir.join("src-tauri").join("tauri.conf.json"),
-        project_dir.join("src-tauri").join("tauri.conf.json5"),
-    ];
-
-    for conf_path in &conf_paths {
-        if let Ok(contents) = fs::read_to_string(conf_path) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
-                // productName can be at root level or under package
-                if let Some(name) = json.get("productName").and_then(|v| v.as_str()) {
-                    return Some(name.to_string());
-                }
-                if let Some(name) = json
-                    .get("package")
-                    .and_then(|p| p.get("productName"))
-                    .and_then(|v| v.as_str())
-                {
-                    return Some(name.to_string());
-                }
+/// `--source-glob` discovery: files matching any pattern (relative patterns
+/// resolve from the project directory), in place of manifest discovery.
+fn find_glob_executables(
+    project_dir: &Path,
+    rust_base_dir: &Path,
+    patterns: &[String],
+) -> Result<Vec<BuiltBinary>> {
+    let ignored = read_ignore_patterns(rust_base_dir)?;
+    let mut found: Vec<BuiltBinary> = Vec::new();
+    for pattern in patterns {
+        let full = if Path::new(pattern).is_absolute() {
+            PathBuf::from(pattern)
+        } else {
+            project_dir.join(pattern)
+        };
+        let matches = glob::glob(&full.to_string_lossy())
+            .with_context(|| format!("Invalid --source-glob pattern '{}'", pattern))?;
+        for path in matches.flatten() {
+            if !path.is_file() {
+                continue;
             }
+            let base_name = base_name_of(&path);
+            // Overlapping patterns may match the same file twice.
+            if ignored.iter().any(|p| p.matches(&base_name))
+                || found.iter().any(|b| b.source == path)
+            {
+                continue;
+            }
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            found.push(BuiltBinary {
+                base_name,
+                version: None,
+                package: None,
+                deploy_name: None,
+                dest: None,
+                size,
+                source: path,
+                library: false,
+                crate_dir: None,
+            });
         }
     }
-    None
+    found.sort_by(|a, b| (&a.base_name, &a.source).cmp(&(&b.base_name, &b.source)));
+    Ok(found)
 }
 
-/// Execution context for IO and environment mocking
-pub struct CliContext<'a> {
-    pub stdout: &'a mut dyn Write,
-    pub stderr: &'a mut dyn Write,
-    /// Mock for std::env::current_exe()
-    pub current_exe: Option<PathBuf>,
+/// Whether `path` is a file this platform would run: the execute bit on Unix,
+/// an `.exe` extension on Windows.
+#[cfg(unix)]
+fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
 }
 
-impl<'a> CliContext<'a> {
-    pub fn new(stdout: &'a mut dyn Write, stderr: &'a mut dyn Write) -> Self {
-        Self {
-            stdout,
-            stderr,
-            current_exe: None,
-        }
-    }
+#[cfg(not(unix))]
+fn is_executable_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
 }
 
-/// Main deployment function that handles both single packages and workspaces
-pub fn run_with_options(
+/// Permission bits for a copy of a file with `source_mode`: `requested`
+/// verbatim, or 0o755 narrowed to the source's bits and then made executable
+/// wherever it is readable, so a stricter 0o700 source stays 0o700 but a copy
+/// that lost its `+x` regains it.
+#[cfg(unix)]
+fn deployed_mode(source_mode: u32, requested: Option<u32>) -> u32 {
+    requested.unwrap_or_else(|| {
+        let mode = 0o755 & source_mode;
+        mode | (mode & 0o444) >> 2
+    })
+}
+
+/// Give the copy at `target` its [`deployed_mode`]. Filesystems mounted
+/// without exec-by-default can otherwise leave it non-executable.
+#[cfg(unix)]
+fn set_deployed_mode(source: &Path, target: &Path, requested: Option<u32>) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let source_mode = fs::metadata(source)?.permissions().mode();
+    let mode = deployed_mode(source_mode, requested);
+    fs::set_permissions(target, fs::Permissions::from_mode(mode))
+}
+
+#[cfg(not(unix))]
+fn set_deployed_mode(
+    _source: &Path,
+    _target: &Path,
+    _requested: Option<u32>,
+) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Clear the read-only state of `path`: owner write permission on Unix, the
+/// read-only attribute elsewhere.
+#[cfg(unix)]
+fn make_writable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o200);
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::permissions_set_readonly_false)]
+fn make_writable(path: &Path) -> std::io::Result<()> {
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+/// A project's layout and the binaries discovered in it.
+struct ProjectScan {
+    project_type: ProjectType,
+    /// Whether `project_type` was detected rather than given.
+    auto_detected: bool,
+    /// Where built binaries are read from.
+    source_dir: PathBuf,
+    discovery: Discovery,
+}
+
+/// Locate the Rust project under `project_dir` and discover its binaries per
+/// `options`. Shared by deploys and `mdrcp list`.
+fn scan_project(
     project_dir: &Path,
     options: &RunOptions,
-    ctx: &mut CliContext,
-) -> Result<()> {
+    cargo_target: Option<&Path>,
+    trace: &mut Trace,
+) -> Result<ProjectScan> {
     // Determine project type: use explicit option or auto-detect
     let (project_type, auto_detected) = match options.project_type {
         Some(pt) => (pt, false),
@@ -626,14 +2159,15 @@ pub fn run_with_options(
 
     let cargo_path = rust_base_dir.join("Cargo.toml");
     if !cargo_path.exists() {
-        if project_type == ProjectType::Tauri {
-            anyhow::bail!(
+        let message = if project_type == ProjectType::Tauri {
+            format!(
                 "No Cargo.toml found at {}. Is this a valid Tauri project?",
                 cargo_path.display()
-            );
+            )
         } else {
-            anyhow::bail!("No Cargo.toml found. Please run this tool in a Rust project directory");
-        }
+            "No Cargo.toml found. Please run this tool in a Rust project directory".to_string()
+        };
+        return Err(DeployError::NotRustProject(message).into());
     }
 
     let cargo_contents = fs::read_to_string(&cargo_path).context("Failed to read Cargo.toml")?;
@@ -649,46 +2183,1713 @@ pub fn run_with_options(
         }
     }
 
-    let profile = options.profile;
-    let built_executables =
-        find_built_executables(&rust_base_dir, &cargo_data, profile, &extra_names)?;
+    let source_dir = profile_source_dir(project_dir, &rust_base_dir, cargo_target, options);
+    let discovery = if !options.source_globs.is_empty() {
+        Discovery {
+            built: find_glob_executables(project_dir, &rust_base_dir, &options.source_globs)?,
+            unbuilt: Vec::new(),
+            duplicates: Vec::new(),
+        }
+    } else if options.all_files {
+        Discovery {
+            built: find_all_executables(&rust_base_dir, &source_dir, &options.exclude_dirs)?,
+            unbuilt: Vec::new(),
+            duplicates: Vec::new(),
+        }
+    } else {
+        let exclude = options
+            .exclude
+            .iter()
+            .map(|raw| {
+                glob::Pattern::new(raw)
+                    .with_context(|| format!("Invalid --exclude pattern '{}'", raw))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut discovery = find_built_executables(
+            &rust_base_dir,
+            &cargo_data,
+            &source_dir,
+            &extra_names,
+            &exclude,
+            Selection {
+                only: &options.only,
+                all_members: options.all_members,
+            },
+            trace,
+        )?;
+        let kinds = [
+            (ArtifactKind::Library, options.include_libs),
+            (ArtifactKind::Example, options.examples),
+        ];
+        for (kind, enabled) in kinds {
+            if !enabled {
+                continue;
+            }
+            discovery.built.extend(find_built_artifacts(
+                kind,
+                &rust_base_dir,
+                &cargo_data,
+                &source_dir,
+                &exclude,
+                options.all_members || !options.only.is_empty(),
+            )?);
+        }
+        discovery
+    };
+    Ok(ProjectScan {
+        project_type,
+        auto_detected,
+        source_dir,
+        discovery,
+    })
+}
 
-    if built_executables.is_empty() {
-        anyhow::bail!(
-            "No built {} executables found. Have you run '{}'?",
-            profile.label(),
-            profile.cargo_hint(project_type)
-        );
+/// Cargo's build output directory when it is not `<project>/target`: the
+/// `--target-dir` option, then `CARGO_TARGET_DIR`, then `build.target-dir`
+/// from `.cargo/config.toml` in the project or one of its ancestors, then in
+/// Cargo's home. Relative paths resolve from the project root, or for config
+/// files from the directory holding `.cargo`.
+fn cargo_target_dir(
+    project_dir: &Path,
+    options: &RunOptions,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Option<PathBuf> {
+    if let Some(dir) = options.cargo_target_dir.as_ref() {
+        return Some(project_dir.join(dir));
+    }
+    if let Some(dir) = var("CARGO_TARGET_DIR").filter(|dir| !dir.is_empty()) {
+        return Some(project_dir.join(dir));
     }
+    let cargo_home = var("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| Path::new(&home).join(".cargo")));
+    let project_configs = absolute(project_dir)
+        .ancestors()
+        .map(|dir| (dir.join(".cargo").join("config.toml"), dir.to_path_buf()))
+        .collect::<Vec<_>>();
+    let home_config = cargo_home.map(|home| {
+        let base = home.parent().map(Path::to_path_buf).unwrap_or_default();
+        (home.join("config.toml"), base)
+    });
+    project_configs
+        .into_iter()
+        .chain(home_config)
+        .find_map(|(config, base)| {
+            let value: Value = toml::from_str(&fs::read_to_string(config).ok()?).ok()?;
+            let dir = value.get("build")?.get("target-dir")?.as_str()?;
+            Some(base.join(dir))
+        })
+}
 
-    let override_raw = options.target_override.clone();
-    let override_used = override_raw.is_some();
-    let summary_format = options.summary;
-    let emit_text = summary_format == SummaryFormat::Text && !options.quiet;
-    let produce_json = matches!(
-        summary_format,
-        SummaryFormat::Json | SummaryFormat::JsonPretty
+/// Directory the built binaries are read from: `--profile-dir` verbatim, or
+/// `target/<profile>` (`target/<triple>/<profile>` with `--target-triple`)
+/// under `cargo_target`, which defaults to `rust_base_dir/target`.
+fn profile_source_dir(
+    project_dir: &Path,
+    rust_base_dir: &Path,
+    cargo_target: Option<&Path>,
+    options: &RunOptions,
+) -> PathBuf {
+    match options.profile_dir.as_ref() {
+        Some(dir) if dir.is_absolute() => dir.clone(),
+        Some(dir) => project_dir.join(dir),
+        None => {
+            let mut dir = cargo_target
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| rust_base_dir.join("target"));
+            if let Some(triple) = options.target_triple.as_deref() {
+                dir.push(triple);
+            }
+            dir.join(options.profile.artifact_dir())
+        }
+    }
+}
+
+/// `--all-files` discovery: every executable under `profile_dir`, ignoring
+/// manifests. Cargo's internal subdirectories and `exclude_dirs` are not
+/// descended into.
+fn find_all_executables(
+    rust_base_dir: &Path,
+    profile_dir: &Path,
+    exclude_dirs: &[String],
+) -> Result<Vec<BuiltBinary>> {
+    let ignored = read_ignore_patterns(rust_base_dir)?;
+    let mut found: Vec<BuiltBinary> = Vec::new();
+    let mut pending = vec![profile_dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut entries: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        entries.sort();
+        let mut subdirs = Vec::new();
+        for path in entries {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if path.is_dir() {
+                let skipped =
+                    CARGO_PROFILE_SUBDIRS.contains(&name.as_str()) || exclude_dirs.contains(&name);
+                if !skipped {
+                    subdirs.push(path);
+                }
+                continue;
+            }
+            if name.starts_with('.') || !is_executable_file(&path) {
+                continue;
+            }
+            let base_name = base_name_of(&path);
+            if ignored.iter().any(|p| p.matches(&base_name)) {
+                continue;
+            }
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            found.push(BuiltBinary {
+                base_name,
+                version: None,
+                package: None,
+                deploy_name: None,
+                dest: None,
+                size,
+                source: path,
+                library: false,
+                crate_dir: None,
+            });
+        }
+        // Breadth-first: top-level files are listed before nested ones.
+        pending.splice(0..0, subdirs.into_iter().rev());
+    }
+    found.sort_by(|a, b| (&a.base_name, &a.source).cmp(&(&b.base_name, &b.source)));
+    Ok(found)
+}
+
+/// Determine the default deployment target directory per-OS.
+fn target_dir_override_from_env(ctx: &CliContext) -> Result<Option<PathBuf>> {
+    if let Some(raw) = ctx.var_os(TARGET_OVERRIDE_ENV) {
+        if raw.is_empty() {
+            anyhow::bail!(
+                "{} is set but empty; provide an absolute path",
+                TARGET_OVERRIDE_ENV
+            );
+        }
+        return Ok(Some(PathBuf::from(raw)));
+    }
+    Ok(None)
+}
+
+/// The OS default target, unless `--target-unix` / `--target-windows` names
+/// one for the platform this is running on (the other flag is ignored).
+/// Relative paths resolve from the project root.
+fn platform_default_target(
+    ctx: &CliContext,
+    project_dir: &Path,
+    options: &RunOptions,
+) -> Result<PathBuf> {
+    let platform = if cfg!(windows) {
+        options.target_windows.as_ref()
+    } else {
+        options.target_unix.as_ref()
+    };
+    match platform {
+        Some(dir) if dir.is_absolute() => Ok(dir.clone()),
+        Some(dir) => Ok(project_dir.join(dir)),
+        None => default_target_dir(ctx),
+    }
+}
+
+#[cfg(windows)]
+fn default_target_dir(ctx: &CliContext) -> Result<PathBuf> {
+    if let Some(custom) = target_dir_override_from_env(ctx)? {
+        return Ok(custom);
+    }
+    Ok(PathBuf::from(r"c:\apps"))
+}
+
+#[cfg(not(windows))]
+fn default_target_dir(ctx: &CliContext) -> Result<PathBuf> {
+    if let Some(custom) = target_dir_override_from_env(ctx)? {
+        return Ok(custom);
+    }
+    let home = ctx
+        .var_os("HOME")
+        .ok_or_else(|| anyhow::anyhow!("HOME is not set; cannot determine ~/.local/bin"))?;
+    Ok(Path::new(&home).join(".local").join("bin"))
+}
+
+fn file_mtime(path: &Path) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// `--check-fresh`: the most recently modified of `crate_dir/Cargo.toml` and
+/// every `.rs` file under `crate_dir/src`, with its modification time.
+fn newest_source(crate_dir: &Path) -> Option<(PathBuf, std::time::SystemTime)> {
+    let manifest = crate_dir.join("Cargo.toml");
+    let mut newest = file_mtime(&manifest).map(|mtime| (manifest, mtime));
+    let mut pending = vec![crate_dir.join("src")];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let Some(mtime) = file_mtime(&path) else {
+                    continue;
+                };
+                if newest.as_ref().is_none_or(|(_, newest)| mtime > *newest) {
+                    newest = Some((path, mtime));
+                }
+            }
+        }
+    }
+    newest
+}
+
+/// Why an incremental deploy can leave `target` alone, or `None` to copy.
+/// `last_deploy` (from `--since-last-deploy`) skips sources not modified since
+/// then; `copy_if_newer` skips when the destination is at least as new as the
+/// source. Both are mtime-only, so a content change with an unchanged mtime is
+/// missed. A missing destination is always copied.
+fn incremental_skip_reason(
+    source: &Path,
+    target: &Path,
+    last_deploy: Option<std::time::SystemTime>,
+    copy_if_newer: bool,
+) -> Option<&'static str> {
+    let target_mtime = file_mtime(target)?;
+    let source_mtime = file_mtime(source)?;
+    if last_deploy.is_some_and(|since| source_mtime <= since) {
+        return Some("not modified since last deploy");
+    }
+    if copy_if_newer && target_mtime >= source_mtime {
+        return Some("destination is up to date");
+    }
+    None
+}
+
+/// `--skip-unchanged`: whether `target` already holds `source`, judged by
+/// size and mtime, plus content hash when `compare_hash` is set. A missing or
+/// unreadable destination counts as changed.
+fn is_unchanged(source: &Path, target: &Path, compare_hash: bool) -> bool {
+    let (Ok(source_meta), Ok(target_meta)) = (fs::metadata(source), fs::metadata(target)) else {
+        return false;
+    };
+    if source_meta.len() != target_meta.len()
+        || source_meta.modified().ok() != target_meta.modified().ok()
+    {
+        return false;
+    }
+    !compare_hash
+        || matches!(
+            (provenance::sha256_hex(source), provenance::sha256_hex(target)),
+            (Ok(a), Ok(b)) if a == b
+        )
+}
+
+/// `path` made absolute via canonicalization, or unchanged when that fails.
+fn absolute(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `source` and `target` name the same existing file once symlinks
+/// and `..` are resolved, e.g. `--target target/release`.
+fn same_file(source: &Path, target: &Path) -> bool {
+    match (source.canonicalize(), target.canonicalize()) {
+        (Ok(source), Ok(target)) => source == target,
+        _ => false,
+    }
+}
+
+/// `path` relative to `base` (e.g. `target/release/foo`), falling back to the
+/// absolute path when it does not live under `base`.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let path = absolute(path);
+    match path.strip_prefix(absolute(base)) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => path,
+    }
+}
+
+/// `source` as `--plan` and `--print-sources` show it: relative to
+/// `project_dir` under `--relativize-sources`, otherwise absolute.
+fn display_source(source: &Path, project_dir: &Path, options: &RunOptions) -> PathBuf {
+    if options.relativize_sources {
+        relative_to(source, project_dir)
+    } else {
+        absolute(source)
+    }
+}
+
+/// Check if the target path is our own running executable
+fn is_self_update_target(target_path: &Path, current_exe_override: Option<&Path>) -> bool {
+    let current_exe = if let Some(p) = current_exe_override {
+        p.to_path_buf()
+    } else {
+        match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(_) => return false,
+        }
+    };
+    let current_exe_canonical = current_exe.canonicalize().unwrap_or(current_exe);
+    let target_canonical = target_path
+        .canonicalize()
+        .unwrap_or_else(|_| target_path.to_path_buf());
+    current_exe_canonical == target_canonical
+}
+
+/// One copy to perform: `source_path` to `place_path` (the destination, or
+/// its staging file under `--atomic`).
+struct CopyJob {
+    placed: Placed,
+    source_path: PathBuf,
+    place_path: PathBuf,
+    /// Where the old destination was moved aside to, if it was.
+    backup_path: Option<PathBuf>,
+    size: u64,
+}
+
+/// Worker count when `--jobs` is not given: one per CPU.
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Result of one copy job: the verified SHA-256 under `--verify`, and how
+/// long placing the file took.
+type CopyResult = std::io::Result<(Option<String>, std::time::Duration)>;
+
+/// Perform `jobs` on up to `workers` threads, passing each result to `handle`
+/// on the calling thread in job order. A job not started because `cancel` was
+/// raised is passed `None`. With one worker, each copy starts only after the
+/// previous result has been handled.
+fn run_copy_jobs(
+    jobs: &[CopyJob],
+    options: &RunOptions,
+    workers: usize,
+    cancel: &AtomicBool,
+    mut handle: impl FnMut(&CopyJob, Option<CopyResult>) -> Result<()>,
+) -> Result<()> {
+    let place = |job: &CopyJob| {
+        (!cancel.load(Ordering::SeqCst)).then(|| {
+            let started = std::time::Instant::now();
+            match place_binary(options.link_mode, &job.source_path, &job.place_path) {
+                Err(e)
+                    if options.force
+                        && e.kind() == std::io::ErrorKind::PermissionDenied
+                        && job.place_path.is_file() =>
+                {
+                    make_writable(&job.place_path)?;
+                    place_binary(options.link_mode, &job.source_path, &job.place_path)?;
+                }
+                result => result?,
+            }
+            let elapsed = started.elapsed();
+            if options.skip_unchanged && options.link_mode == LinkMode::Copy {
+                // fs::copy does not carry the mtime over; without it the next
+                // --skip-unchanged run could never match. Set before --mode,
+                // through a read-only handle on Unix (see copy_file_times), as
+                // the copy may already lack owner write permission.
+                if let Some(mtime) = file_mtime(&job.source_path) {
+                    let file = if cfg!(unix) {
+                        fs::File::open(&job.place_path)?
+                    } else {
+                        fs::File::options().write(true).open(&job.place_path)?
+                    };
+                    file.set_modified(mtime)?;
+                }
+            }
+            if options.link_mode == LinkMode::Copy {
+                set_deployed_mode(&job.source_path, &job.place_path, options.mode)?;
+            }
+            let sha256 = if options.verify {
+                Some(verify_copy(&job.source_path, &job.place_path)?)
+            } else {
+                None
+            };
+            Ok((sha256, elapsed))
+        })
+    };
+    if workers <= 1 || jobs.len() <= 1 {
+        for job in jobs {
+            handle(job, place(job))?;
+        }
+        return Ok(());
+    }
+
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..workers.min(jobs.len()) {
+            let (tx, next, place) = (tx.clone(), &next, &place);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(job) = jobs.get(index) else {
+                    break;
+                };
+                // The receiver is gone only if handling failed; stop early.
+                if tx.send((index, place(job))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        // Results arrive in completion order; hand them on in job order.
+        let mut pending: Vec<Option<Option<CopyResult>>> = jobs.iter().map(|_| None).collect();
+        let mut next_to_handle = 0;
+        for (index, result) in rx {
+            pending[index] = Some(result);
+            while let Some(result) = pending.get_mut(next_to_handle).and_then(Option::take) {
+                handle(&jobs[next_to_handle], result)?;
+                next_to_handle += 1;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// A binary now at its destination, awaiting the post-copy steps.
+#[derive(Clone)]
+struct Placed {
+    exe_name: String,
+    /// Dimmed " v<version>" suffix for log lines, or empty.
+    ver_suffix: String,
+    version: Option<String>,
+    source_path: PathBuf,
+    target_path: PathBuf,
+    source_mtime: Option<String>,
+    /// Set once `--verify` has checked the copy.
+    sha256: Option<String>,
+    /// Dimmed `[i/N] ` prefix for log lines (see [`deploy_counter`]).
+    counter: String,
+}
+
+/// Announce a placed binary and run the post-copy steps: strip, xattrs,
+/// sidecar, and signing. Returns the error for a binary that must count as
+/// failed (it could not be stripped or its signature could not be written).
+fn finish_placed(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    placed: &Placed,
+    source_project: &Path,
+    warnings: &mut Vec<Warning>,
+) -> Result<Option<String>> {
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    let Placed {
+        exe_name,
+        ver_suffix,
+        source_path,
+        target_path,
+        ..
+    } = placed;
+    if emit_text {
+        let mtime_str = placed.source_mtime.as_deref().unwrap_or("unknown");
+        let verb = match options.link_mode {
+            LinkMode::Copy => "Copied",
+            LinkMode::Symlink => "Linked",
+        };
+        writeln!(
+            ctx.stdout,
+            "{}{} {}{} {} {}",
+            placed.counter,
+            verb.bold().green(),
+            exe_name.bold().green(),
+            ver_suffix,
+            format!("-> {}", target_path.display()).dimmed(),
+            format!("({})", mtime_str).dimmed()
+        )?;
+    }
+    // Only the deployed copy is stripped; the build output keeps its symbols.
+    if options.strip && cfg!(unix) {
+        let args = [target_path.as_os_str().to_owned()];
+        if let Err(e) = run_tool(ctx, "strip", &args)
+            .with_context(|| format!("strip failed for {}", target_path.display()))
+        {
+            return post_copy_failure(ctx, emit_text, placed, e);
+        }
+    }
+    // After strip, which rewrites the file. A symlink already shows the
+    // source's times.
+    if options.preserve_timestamps && options.link_mode == LinkMode::Copy {
+        if let Err(e) = copy_file_times(source_path, target_path) {
+            let message = format!(
+                "Could not preserve timestamps on {}: {}",
+                target_path.display(),
+                e
+            );
+            push_warning(
+                ctx,
+                options,
+                warnings,
+                WarningCode::TimestampFailed,
+                message,
+            )?;
+        }
+    }
+    if options.tag_xattr {
+        if let Err(e) = provenance::tag_xattrs(target_path, source_project) {
+            let message = format!(
+                "Could not tag {} with provenance xattrs: {}",
+                target_path.display(),
+                e
+            );
+            push_warning(ctx, options, warnings, WarningCode::XattrFailed, message)?;
+        }
+    }
+    if options.sidecar {
+        // `--verify` already hashed the copy, unless strip rewrote it since.
+        let sha256 = match &placed.sha256 {
+            Some(sha256) if !options.strip => Some(sha256.clone()),
+            _ => provenance::sha256_hex(target_path).ok(),
+        };
+        let record = provenance::Provenance {
+            source: Some(source_project.display().to_string()),
+            mdrcp_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            binary_version: placed.version.clone(),
+            deployed_at: format_utc(time::OffsetDateTime::now_utc()),
+            sha256,
+            deploy_id: options.deploy_id.clone(),
+        };
+        if let Err(e) = provenance::write_sidecar(target_path, &record) {
+            let message = format!("Could not write provenance sidecar: {:#}", e);
+            push_warning(ctx, options, warnings, WarningCode::SidecarFailed, message)?;
+        }
+    }
+    if !options.gpg_sign {
+        return Ok(None);
+    }
+    match gpg_sign(ctx, target_path, options.gpg_key.as_deref()) {
+        Ok(sig_path) => {
+            if emit_text {
+                writeln!(
+                    ctx.stdout,
+                    "{} {}",
+                    "Signed".bold().green(),
+                    format!("-> {}", sig_path.display()).dimmed()
+                )?;
+            }
+            Ok(None)
+        }
+        Err(e) => post_copy_failure(ctx, emit_text, placed, e),
+    }
+}
+
+/// One `--summary ndjson` line: an `event` tag alongside the fields of `data`.
+#[derive(Serialize)]
+struct NdjsonEvent<'a, T: Serialize> {
+    event: &'a str,
+    #[serde(flatten)]
+    data: T,
+}
+
+/// `--summary ndjson`: stream a `copied` event for `binary` as soon as it is
+/// in place. Emitted even under `--quiet`, which only silences text.
+fn emit_copied_event(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    binary: &str,
+    target: &Path,
+) -> Result<()> {
+    if options.summary != SummaryFormat::Ndjson {
+        return Ok(());
+    }
+    #[derive(Serialize)]
+    struct Copied<'a> {
+        binary: &'a str,
+        target: String,
+    }
+    let event = NdjsonEvent {
+        event: "copied",
+        data: Copied {
+            binary,
+            target: target.display().to_string(),
+        },
+    };
+    write_summary(ctx.stdout, SummaryFormat::Ndjson, None, &event)
+}
+
+/// Report a post-copy step that failed `placed`, returning its error text.
+fn post_copy_failure(
+    ctx: &mut CliContext,
+    emit_text: bool,
+    placed: &Placed,
+    error: anyhow::Error,
+) -> Result<Option<String>> {
+    if emit_text {
+        writeln!(
+            ctx.stderr,
+            "{} {}{} {}",
+            "Failed".bold().bright_red(),
+            placed.exe_name.bold().yellow(),
+            placed.ver_suffix,
+            format!("-> {:#}", error).dimmed()
+        )?;
+    }
+    Ok(Some(format!("{:#}", error)))
+}
+
+/// `--verify`: compare the SHA-256 of `source` and its `copy`, deleting the
+/// copy on a mismatch. Returns the verified hash.
+fn verify_copy(source: &Path, copy: &Path) -> std::io::Result<String> {
+    let expected = provenance::sha256_hex(source)?;
+    let actual = provenance::sha256_hex(copy)?;
+    if actual != expected {
+        let _ = fs::remove_file(copy);
+        return Err(std::io::Error::other(format!(
+            "SHA-256 mismatch (source {}, copy {}); copy removed",
+            expected, actual
+        )));
+    }
+    Ok(actual)
+}
+
+/// `--preserve-timestamps`: give `target` the modified time of `source`, and
+/// its access time where the platform reports one.
+fn copy_file_times(source: &Path, target: &Path) -> std::io::Result<()> {
+    let metadata = fs::metadata(source)?;
+    let mut times = fs::FileTimes::new().set_modified(metadata.modified()?);
+    if let Ok(accessed) = metadata.accessed() {
+        times = times.set_accessed(accessed);
+    }
+    // Setting explicit times only needs ownership on Unix, so a read-only
+    // `--mode` still works; Windows needs a writable handle.
+    let file = if cfg!(unix) {
+        fs::File::open(target)?
+    } else {
+        fs::File::options().write(true).open(target)?
+    };
+    file.set_times(times)
+}
+
+/// Put `source` at `target`: a plain copy, or a symlink to the absolute
+/// source path that replaces whatever was at `target`.
+fn place_binary(mode: LinkMode, source: &Path, target: &Path) -> std::io::Result<()> {
+    match mode {
+        LinkMode::Copy => fs::copy(source, target).map(|_| ()),
+        LinkMode::Symlink => {
+            let source = fs::canonicalize(source)?;
+            if fs::symlink_metadata(target).is_ok() {
+                fs::remove_file(target)?;
+            }
+            symlink_file(&source, target)
+        }
+    }
+}
+
+#[cfg(unix)]
+fn symlink_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn symlink_file(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink_file(_source: &Path, _target: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "symlinks are not supported on this platform",
+    ))
+}
+
+/// Move an existing destination aside to `<name>.bak`, replacing any older
+/// backup. Returns the backup path.
+fn backup_existing(target: &Path) -> std::io::Result<PathBuf> {
+    let mut backup = target.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    if backup.exists() {
+        fs::remove_file(&backup)?;
+    }
+    fs::rename(target, &backup)?;
+    Ok(backup)
+}
+
+/// `--backup-dir`: move an existing destination into `dir`, which is created
+/// on first use, keeping its path relative to `target_dir` (or just its file
+/// name when it lies elsewhere). Returns the backup path.
+fn backup_into(target: &Path, target_dir: &Path, dir: &Path) -> std::io::Result<PathBuf> {
+    let relative = match target.strip_prefix(target_dir) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => PathBuf::from(target.file_name().unwrap_or_default()),
+    };
+    let backup = dir.join(relative);
+    if let Some(parent) = backup.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if backup.exists() {
+        fs::remove_file(&backup)?;
+    }
+    fs::rename(target, &backup)?;
+    Ok(backup)
+}
+
+/// The `<backup-dir>/<timestamp>` folder for a deploy started at `started`.
+fn backup_stamp_dir(backup_dir: &Path, started: std::time::SystemTime) -> PathBuf {
+    let fmt = time::macros::format_description!("[year]-[month]-[day]_[hour]-[minute]-[second]");
+    let stamp = time::OffsetDateTime::from(started)
+        .format(fmt)
+        .unwrap_or_else(|_| "backup".to_string());
+    backup_dir.join(stamp)
+}
+
+/// Temporary file `replace_in_place` stages a new copy of `target` in.
+fn staging_path(target: &Path) -> PathBuf {
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{}.mdrcp-new", file_name))
+}
+
+/// Where the `--atomic` commit keeps the file `target` replaces until every
+/// rename has succeeded.
+fn aside_path(target: &Path) -> PathBuf {
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!(".{}.mdrcp-old", file_name))
+}
+
+/// Replace `target` (possibly the running executable) without a helper
+/// process: stage a copy of `source` beside it, then rename it over the old
+/// file. Windows cannot replace a running image directly, so there the old
+/// file is first renamed aside to `<name>.old`.
+fn replace_in_place(source: &Path, target: &Path) -> std::io::Result<()> {
+    let file_name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let staging = staging_path(target);
+    fs::copy(source, &staging)?;
+    if cfg!(windows) {
+        let aside = target.with_file_name(format!("{}.old", file_name));
+        let _ = fs::remove_file(&aside);
+        if let Err(e) = fs::rename(target, &aside) {
+            let _ = fs::remove_file(&staging);
+            return Err(e);
+        }
+    }
+    fs::rename(&staging, target).inspect_err(|_| {
+        let _ = fs::remove_file(&staging);
+    })
+}
+
+/// Result of attempting a self-update
+enum SelfUpdateResult {
+    /// Not a self-update scenario
+    NotApplicable,
+    /// Self-update spawned successfully - caller should exit
+    Spawned,
+    /// Self-update failed to spawn
+    Failed(String),
+}
+
+/// Check if we're trying to overwrite our own executable, and if so,
+/// spawn a temp copy to perform the update.
+fn try_self_update(
+    source_path: &Path,
+    target_path: &Path,
+    current_exe_override: Option<&Path>,
+) -> SelfUpdateResult {
+    // Get canonical path of current executable
+    let current_exe = if let Some(p) = current_exe_override {
+        p.to_path_buf()
+    } else {
+        match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(_) => return SelfUpdateResult::NotApplicable,
+        }
+    };
+
+    // Canonicalize paths for comparison
+    let current_exe_canonical = current_exe.canonicalize().unwrap_or(current_exe);
+    let target_canonical = target_path
+        .canonicalize()
+        .unwrap_or_else(|_| target_path.to_path_buf());
+
+    // Check if we're trying to overwrite ourselves
+    if current_exe_canonical != target_canonical {
+        return SelfUpdateResult::NotApplicable;
+    }
+
+    // We're updating ourselves - copy current exe to temp and spawn it
+    let temp_dir = std::env::temp_dir();
+    let updater_path = temp_dir.join(UPDATER_TEMP_NAME);
+
+    // Copy current executable to temp location
+    if let Err(e) = fs::copy(&current_exe_canonical, &updater_path) {
+        return SelfUpdateResult::Failed(format!("Failed to copy self to temp: {}", e));
+    }
+
+    // Spawn the temp copy with --finish-update. It waits for EOF on its
+    // stdin, which only arrives once this process has exited (the write end
+    // is held, never closed, until then), so the rest of this deploy can run
+    // for as long as it needs before the running binary is replaced.
+    match ProcessCommand::new(&updater_path)
+        .arg("--finish-update")
+        .arg(source_path)
+        .arg(target_path)
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+    {
+        Ok(mut child) => {
+            std::mem::forget(child.stdin.take());
+            SelfUpdateResult::Spawned
+        }
+        Err(e) => SelfUpdateResult::Failed(format!("Failed to spawn updater: {}", e)),
+    }
+}
+
+/// Detect whether this is a Tauri project by checking for src-tauri/Cargo.toml
+/// and tauri.conf.json (or .json5) in the src-tauri directory.
+fn detect_project_type(project_dir: &Path) -> ProjectType {
+    let tauri_cargo = project_dir.join("src-tauri").join("Cargo.toml");
+    let tauri_conf = project_dir.join("src-tauri").join("tauri.conf.json");
+    let tauri_conf5 = project_dir.join("src-tauri").join("tauri.conf.json5");
+
+    if tauri_cargo.exists() && (tauri_conf.exists() || tauri_conf5.exists()) {
+        ProjectType::Tauri
+    } else {
+        ProjectType::Standard
+    }
+}
+
+/// Read the productName from tauri.conf.json or tauri.conf.json5.
+/// Returns None if file doesn't exist or productName is not set.
+fn read_tauri_product_name(project_dir: &Path) -> Option<String> {
+    let conf_paths = [
+        project_dir.join("src-tauri").join("tauri.conf.json"),
+        project_dir.join("src-tauri").join("tauri.conf.json5"),
+    ];
+
+    for conf_path in &conf_paths {
+        if let Ok(contents) = fs::read_to_string(conf_path) {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&contents) {
+                // productName can be at root level or under package
+                if let Some(name) = json.get("productName").and_then(|v| v.as_str()) {
+                    return Some(name.to_string());
+                }
+                if let Some(name) = json
+                    .get("package")
+                    .and_then(|p| p.get("productName"))
+                    .and_then(|v| v.as_str())
+                {
+                    return Some(name.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Write a non-fatal warning to stderr: colorized for text output, plain when
+/// a machine-readable summary owns stdout.
+fn emit_warning(ctx: &mut CliContext, colored: bool, message: &str) -> std::io::Result<()> {
+    if colored {
+        writeln!(
+            ctx.stderr,
+            "{} {}",
+            "Warning:".bold().yellow(),
+            message.dimmed()
+        )
+    } else {
+        writeln!(ctx.stderr, "Warning: {}", message)
+    }
+}
+
+/// Record a warning unless `--suppress-warning` silenced its code: print it to
+/// stderr and keep it for the JSON summary.
+fn push_warning(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    warnings: &mut Vec<Warning>,
+    code: WarningCode,
+    message: String,
+) -> std::io::Result<()> {
+    if options.suppresses(code) {
+        return Ok(());
+    }
+    emit_warning(ctx, options.summary == SummaryFormat::Text, &message)?;
+    warnings.push(Warning::new(code, message));
+    Ok(())
+}
+
+/// Whether `dir` (canonicalized when possible) is one of the entries in `path_var`.
+fn dir_on_path(dir: &Path, path_var: &std::ffi::OsStr) -> bool {
+    let wanted = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    std::env::split_paths(path_var).any(|entry| {
+        entry
+            .canonicalize()
+            .map(|entry| entry == wanted)
+            .unwrap_or(entry == wanted)
+    })
+}
+
+/// Execution context for IO and environment mocking
+pub struct CliContext<'a> {
+    pub stdout: &'a mut dyn Write,
+    pub stderr: &'a mut dyn Write,
+    /// Mock for std::env::current_exe()
+    pub current_exe: Option<PathBuf>,
+    /// Environment overrides (e.g. from `--env-file`) consulted before the
+    /// process environment.
+    pub env: HashMap<String, OsString>,
+    /// Executes external tools such as `gpg`; see [`run_tool`]. Bypassed
+    /// under `--parallel-targets`, where each target uses a [`SystemRunner`].
+    pub runner: Box<dyn CommandRunner>,
+    /// Set for `--dry-run`. Every side-effect site checks this one flag and
+    /// reports what it would have done instead.
+    pub dry_run: bool,
+    /// Checked between binaries; once raised, no further copies start. Set to
+    /// [`interrupt::flag`] to honour Ctrl-C.
+    pub cancel: Arc<AtomicBool>,
+    /// Answers to `--interactive` prompts.
+    pub stdin: Box<dyn std::io::BufRead>,
+    /// Whether `stdin` is a terminal; `--interactive` only prompts when it is.
+    pub stdin_is_terminal: bool,
+}
+
+impl<'a> CliContext<'a> {
+    pub fn new(stdout: &'a mut dyn Write, stderr: &'a mut dyn Write) -> Self {
+        Self {
+            stdout,
+            stderr,
+            current_exe: None,
+            env: HashMap::new(),
+            runner: Box::new(SystemRunner),
+            dry_run: false,
+            cancel: Arc::new(AtomicBool::new(false)),
+            stdin: Box::new(std::io::BufReader::new(std::io::stdin())),
+            stdin_is_terminal: std::io::IsTerminal::is_terminal(&std::io::stdin()),
+        }
+    }
+
+    /// `--interactive`: ask on stderr whether to overwrite `path`, reading
+    /// the answer from `stdin`. Anything but `y` / `yes` declines.
+    fn confirm_overwrite(&mut self, path: &Path) -> std::io::Result<bool> {
+        write!(self.stderr, "Overwrite {}? [y/N] ", path.display())?;
+        self.stderr.flush()?;
+        let mut answer = String::new();
+        self.stdin.read_line(&mut answer)?;
+        Ok(matches!(
+            answer.trim().to_ascii_lowercase().as_str(),
+            "y" | "yes"
+        ))
+    }
+
+    /// Flush both streams so per-file lines reach buffered outputs (CI logs,
+    /// pipes) as they are written rather than at exit.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.stdout.flush()?;
+        self.stderr.flush()
+    }
+
+    /// Look up `key` in the overrides, then in the process environment.
+    pub fn var_os(&self, key: &str) -> Option<OsString> {
+        self.env.get(key).cloned().or_else(|| std::env::var_os(key))
+    }
+}
+
+/// Post-copy side effects `options` enables, phrased for dry-run output.
+fn post_copy_actions(project_dir: &Path, options: &RunOptions) -> Vec<String> {
+    let mut actions = Vec::new();
+    if options.tag_xattr {
+        actions.push("tag deployed files with provenance xattrs".to_string());
+    }
+    if options.sidecar {
+        actions.push("write a .mdrcp.json sidecar per binary".to_string());
+    }
+    if options.preserve_timestamps {
+        actions.push("give each copy its source's timestamps".to_string());
+    }
+    if options.prune {
+        actions.push(format!(
+            "delete the binaries listed under Would remove and refresh {}",
+            TARGET_MANIFEST_FILE
+        ));
+    }
+    if options.on_conflict == ConflictPolicy::Backup {
+        match options.backup_dir.as_ref() {
+            Some(dir) => actions.push(format!(
+                "move existing destinations into {}/<timestamp>",
+                project_dir.join(dir).display()
+            )),
+            None => actions.push("move existing destinations aside to <name>.bak".to_string()),
+        }
+    }
+    if options.gpg_sign {
+        actions.push("sign each deployed binary with gpg --detach-sign".to_string());
+    }
+    if let Some(checksum_file) = options.checksum_file.as_ref() {
+        actions.push(format!(
+            "write checksums to {}",
+            project_dir.join(checksum_file).display()
+        ));
+    }
+    if let Some(manifest_out) = options.manifest_out.as_ref() {
+        actions.push(format!(
+            "write the artifact manifest to {}",
+            project_dir.join(manifest_out).display()
+        ));
+    }
+    if !options.prune {
+        actions.push(format!("refresh {}", TARGET_MANIFEST_FILE));
+    }
+    actions
+}
+
+/// Run an external tool through `ctx.runner` with the `--env-file` overlay,
+/// failing when it is missing or exits non-zero.
+fn run_tool(
+    ctx: &mut CliContext,
+    program: &str,
+    args: &[OsString],
+) -> Result<std::process::Output> {
+    if ctx.dry_run {
+        let mut line = program.to_string();
+        for arg in args {
+            line.push(' ');
+            line.push_str(&arg.to_string_lossy());
+        }
+        writeln!(
+            ctx.stdout,
+            "{} {}",
+            "Would run".bold().cyan(),
+            line.dimmed()
+        )?;
+        return Ok(std::process::Output {
+            status: Default::default(),
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        });
+    }
+    let output = ctx.runner.run(program, args, &ctx.env).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            anyhow::anyhow!("`{}` not found on PATH", program)
+        } else {
+            anyhow::anyhow!("Failed to run `{}`: {}", program, e)
+        }
+    })?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`{}` exited with {}: {}",
+            program,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output)
+}
+
+/// Layer `--env-file` over `ctx.env`.
+fn load_env_file(ctx: &mut CliContext, project_dir: &Path, options: &RunOptions) -> Result<()> {
+    if let Some(env_file) = options.env_file.as_ref() {
+        let path = if env_file.is_absolute() {
+            env_file.clone()
+        } else {
+            project_dir.join(env_file)
+        };
+        for (key, value) in read_env_file(&path)? {
+            ctx.env.insert(key, value.into());
+        }
+    }
+    Ok(())
+}
+
+/// `--bins-from`: add the names listed in `source` (a file resolved against
+/// the project, or `-` for [`CliContext::stdin`]) to `--bin`. Blank lines and
+/// `#` comments are ignored.
+fn resolve_bins_from(
+    ctx: &mut CliContext,
+    project_dir: &Path,
+    mut options: RunOptions,
+) -> Result<RunOptions> {
+    use std::io::Read;
+
+    let Some(source) = options.bins_from.take() else {
+        return Ok(options);
+    };
+    let contents = if source == Path::new("-") {
+        let mut contents = String::new();
+        ctx.stdin
+            .read_to_string(&mut contents)
+            .context("Failed to read binary names from stdin")?;
+        contents
+    } else {
+        let path = if source.is_absolute() {
+            source.clone()
+        } else {
+            project_dir.join(&source)
+        };
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read --bins-from file {}", path.display()))?
+    };
+    let names: Vec<String> = contents
+        .lines()
+        .filter_map(|line| line.split('#').next())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    // An empty list would otherwise mean "deploy everything".
+    if names.is_empty() {
+        anyhow::bail!("--bins-from {} listed no binary names", source.display());
+    }
+    options.only.extend(names);
+    Ok(options)
+}
+
+/// `--build`: run `cargo build` in `project_dir` for the selected profile,
+/// `--target-triple`, and `--target-dir`, streaming cargo's output unless
+/// `--quiet`. A failed build stops the deploy before anything is scanned.
+fn build_project(ctx: &mut CliContext, project_dir: &Path, options: &RunOptions) -> Result<()> {
+    let project_type = options
+        .project_type
+        .unwrap_or_else(|| detect_project_type(project_dir));
+    if project_type == ProjectType::Tauri {
+        anyhow::bail!(
+            "--build only runs `cargo build`, which does not bundle a Tauri app; run '{}' first",
+            options.profile.cargo_hint(project_type)
+        );
+    }
+    let mut args: Vec<OsString> = vec!["build".into()];
+    if options.profile == BuildProfile::Release {
+        args.push("--release".into());
+    }
+    if let Some(triple) = options.target_triple.as_deref() {
+        args.push("--target".into());
+        args.push(triple.into());
+    }
+    if let Some(dir) = options.cargo_target_dir.as_ref() {
+        args.push("--target-dir".into());
+        args.push(project_dir.join(dir).into());
+    }
+    let line = std::iter::once("cargo".to_string())
+        .chain(args.iter().map(|arg| arg.to_string_lossy().into_owned()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    if ctx.dry_run || options.dry_run {
+        if emit_text {
+            writeln!(
+                ctx.stdout,
+                "{} {}",
+                "Would run".bold().cyan(),
+                line.dimmed()
+            )?;
+        }
+        return Ok(());
+    }
+    if emit_text {
+        writeln!(ctx.stdout, "{} {}", "Building".bold().cyan(), line.dimmed())?;
+    }
+    ctx.flush()?;
+    let output = ctx
+        .runner
+        .run_in("cargo", &args, &ctx.env, project_dir, options.quiet)
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow::anyhow!("`cargo` not found on PATH")
+            } else {
+                anyhow::anyhow!("Failed to run `{}`: {}", line, e)
+            }
+        })?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.trim().is_empty() {
+            anyhow::bail!(
+                "`{}` failed ({}); nothing was deployed",
+                line,
+                output.status
+            );
+        }
+        anyhow::bail!(
+            "`{}` failed ({}); nothing was deployed: {}",
+            line,
+            output.status,
+            stderr.trim()
+        );
+    }
+    Ok(())
+}
+
+/// `--pre-hook`: run `hook` through the shell with `MDRCP_TARGET_DIR` and
+/// `MDRCP_BINARIES` (space-separated file names) set, failing with its exit
+/// code when it does not succeed.
+fn run_pre_hook(
+    ctx: &mut CliContext,
+    hook: &str,
+    target_dir: &Path,
+    binaries: &[String],
+) -> Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut env = ctx.env.clone();
+    env.insert("MDRCP_TARGET_DIR".into(), target_dir.into());
+    env.insert("MDRCP_BINARIES".into(), binaries.join(" ").into());
+    let output = ctx
+        .runner
+        .run(shell, &[flag.into(), hook.into()], &env)
+        .with_context(|| format!("Failed to run pre-hook `{}`", hook))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let code = match output.status.code() {
+        Some(code) => format!("code {}", code),
+        None => output.status.to_string(),
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.trim().is_empty() {
+        anyhow::bail!("Pre-hook `{}` exited with {}", hook, code);
+    }
+    anyhow::bail!(
+        "Pre-hook `{}` exited with {}: {}",
+        hook,
+        code,
+        stderr.trim()
     );
-    let mut default_target: Option<PathBuf> = None;
-    let target_dir = match override_raw.as_ref() {
-        Some(override_dir) => {
-            if let Ok(default_dir) = default_target_dir() {
-                default_target = Some(default_dir);
+}
+
+/// Detached-sign `file` with `gpg`, writing `<file>.sig`.
+#[cfg(feature = "gpg")]
+fn gpg_sign(ctx: &mut CliContext, file: &Path, key: Option<&str>) -> Result<PathBuf> {
+    let mut sig_path = file.as_os_str().to_owned();
+    sig_path.push(".sig");
+    let sig_path = PathBuf::from(sig_path);
+
+    let mut args: Vec<OsString> = vec!["--batch".into(), "--yes".into()];
+    if let Some(key) = key {
+        args.push("--local-user".into());
+        args.push(key.into());
+    }
+    args.push("--output".into());
+    args.push(sig_path.clone().into());
+    args.push("--detach-sign".into());
+    args.push(file.into());
+
+    run_tool(ctx, "gpg", &args)
+        .with_context(|| format!("gpg --detach-sign failed for {}", file.display()))?;
+    Ok(sig_path)
+}
+
+#[cfg(not(feature = "gpg"))]
+fn gpg_sign(_ctx: &mut CliContext, _file: &Path, _key: Option<&str>) -> Result<PathBuf> {
+    anyhow::bail!("--gpg-sign is unavailable: mdrcp was built without the `gpg` feature")
+}
+
+/// Parse a `.env`-style file: `KEY=VALUE` per line, `#` comments and blank
+/// lines skipped, an optional `export ` prefix and matching quotes stripped.
+fn read_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file {}", path.display()))?;
+    let mut pairs = Vec::new();
+    for (lineno, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            anyhow::bail!(
+                "Invalid line {} in env file {}: expected KEY=VALUE",
+                lineno + 1,
+                path.display()
+            );
+        };
+        let value = value.trim();
+        let value = [('"', '"'), ('\'', '\'')]
+            .iter()
+            .find_map(|(open, close)| {
+                value
+                    .strip_prefix(*open)
+                    .and_then(|v| v.strip_suffix(*close))
+            })
+            .unwrap_or(value);
+        pairs.push((key.trim().to_string(), value.to_string()));
+    }
+    Ok(pairs)
+}
+
+/// Deploy binaries per `options` without printing anything: the embedding API.
+/// Copy failures, an aborted `--pre-hook`, and interruption come back as
+/// [`DeployError::Failed`] with the full report. With several targets the
+/// project is built once and the report is the first target's, the others in
+/// [`DeploymentReport::extra_targets`]. Ctrl-C stops the copy loop once
+/// [`interrupt::install`] has run.
+///
+/// ```no_run
+/// let options = mdrcp::RunOptions::default();
+/// match mdrcp::deploy(std::path::Path::new("."), &options) {
+///     Ok(report) => println!("copied {:?}", report.copied),
+///     Err(e) => eprintln!("deploy failed: {}", e),
+/// }
+/// ```
+pub fn deploy(project_dir: &Path, options: &RunOptions) -> Result<DeploymentReport, DeployError> {
+    let mut stdout = std::io::sink();
+    let mut stderr = std::io::sink();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.cancel = interrupt::flag();
+    if options.interactive {
+        return Err(DeployError::Setup(anyhow::anyhow!(
+            "--interactive is not supported by mdrcp::deploy, which has no terminal to prompt on"
+        )));
+    }
+    let options = config::apply_config(project_dir, options).map_err(DeployError::Setup)?;
+    let options = resolve_bins_from(&mut ctx, project_dir, options).map_err(DeployError::Setup)?;
+    let silent = RunOptions {
+        quiet: true,
+        summary: SummaryFormat::Text,
+        progress: false,
+        ..options
+    };
+    let to_deploy_error = |e: anyhow::Error| {
+        e.downcast::<DeployError>()
+            .unwrap_or_else(DeployError::Setup)
+    };
+    let mut reports = if silent.extra_targets.is_empty() {
+        vec![execute(project_dir, &silent, &mut ctx).map_err(to_deploy_error)?]
+    } else {
+        let runs = execute_targets(project_dir, &silent, &mut ctx).map_err(to_deploy_error)?;
+        runs.into_iter()
+            .map(|(_, run)| run.result.map_err(to_deploy_error))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let mut report = reports.remove(0);
+    report.extra_targets = reports;
+    if report.all_targets().all(|r| r.status() == "ok") {
+        Ok(report)
+    } else {
+        Err(DeployError::Failed(Box::new(report)))
+    }
+}
+
+/// Run a deploy, streaming progress to `ctx` and finishing with the text or
+/// JSON summary selected by `options.summary`.
+pub fn run_with_options(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+) -> Result<()> {
+    let options = config::apply_config(project_dir, options)?;
+    let options = &resolve_bins_from(ctx, project_dir, options)?;
+    // Silent and multi-target runs render into buffers, where a prompt would
+    // go unseen while the deploy waits for an answer.
+    if options.interactive && (options.silent || !options.extra_targets.is_empty()) {
+        anyhow::bail!(
+            "--interactive cannot be combined with --silent or several --target directories"
+        );
+    }
+    if options.silent {
+        // Run quietly into buffers, replay stdout (the summary), drop stderr.
+        let quiet = RunOptions {
+            silent: false,
+            quiet: true,
+            ..options.clone()
+        };
+        let runner = std::mem::replace(&mut ctx.runner, Box::new(SystemRunner));
+        let (outcome, runner) = run_buffered(context_parts(ctx), runner, |ctx| {
+            run_with_options(project_dir, &quiet, ctx)
+        });
+        ctx.runner = runner;
+        ctx.stdout.write_all(&outcome.stdout)?;
+        return outcome.result;
+    }
+    if !options.extra_targets.is_empty() {
+        return run_multi_target(project_dir, options, ctx);
+    }
+    let report = execute(project_dir, options, ctx)?;
+    finish_report(ctx, options, report)
+}
+
+/// Print the summary of a finished deploy, then turn an interrupted or failed
+/// one into the matching error.
+fn finish_report(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    report: DeploymentReport,
+) -> Result<()> {
+    if report.plan_only {
+        return Ok(());
+    }
+    write_report(ctx, options, &report)?;
+    report_result(report)
+}
+
+/// How a finished deploy ends: [`interrupt::Interrupted`] when it was cut
+/// short, [`DeployError::Failed`] when a copy failed or the `--pre-hook`
+/// aborted.
+fn report_result(report: DeploymentReport) -> Result<()> {
+    if !report.interrupted.is_empty() {
+        return Err(interrupt::Interrupted {
+            remaining: report.interrupted.len(),
+        }
+        .into());
+    }
+    if !report.failed.is_empty() || report.aborted.is_some() {
+        return Err(DeployError::Failed(Box::new(report)).into());
+    }
+    Ok(())
+}
+
+/// Captured output and result of a run against in-memory stdout/stderr.
+struct Buffered<T> {
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    result: Result<T>,
+}
+
+/// Deploy to every `--target` in turn, or concurrently with
+/// `--parallel-targets`. Each target's progress and text summary are replayed
+/// in the order the targets were given so output stays deterministic; a
+/// structured summary covers all targets in one document. On failure the error
+/// carries the worst [`DeployError::exit_code`] among the targets.
+fn run_multi_target(project_dir: &Path, options: &RunOptions, ctx: &mut CliContext) -> Result<()> {
+    let runs = execute_targets(project_dir, options, ctx)?;
+    let total = runs.len();
+    let exit_code = |e: &anyhow::Error| {
+        e.downcast_ref::<DeployError>()
+            .map_or(1, DeployError::exit_code)
+    };
+    let mut summaries = Vec::new();
+    let mut failed_targets = 0;
+    let mut worst: Option<anyhow::Error> = None;
+    let mut interrupted = None;
+    for (target_options, run) in runs {
+        ctx.stdout.write_all(&run.stdout)?;
+        ctx.stderr.write_all(&run.stderr)?;
+        let result = run.result.and_then(|report| {
+            if report.plan_only {
+                return Ok(());
             }
-            if override_dir.is_absolute() {
-                override_dir.clone()
-            } else {
-                project_dir.join(override_dir)
+            write_text_report(ctx, &target_options, &report)?;
+            if options.summary != SummaryFormat::Text {
+                summaries.push(deployment_summary(&target_options, &report));
+            }
+            if options.summary != SummaryFormat::Ndjson {
+                write_annotations(ctx, &target_options, &report)?;
+            }
+            report_result(report)
+        });
+        let Err(e) = result else { continue };
+        let target = target_options
+            .target_override
+            .as_deref()
+            .unwrap_or_else(|| Path::new(""));
+        writeln!(
+            ctx.stderr,
+            "{} {}: {:#}",
+            "Error:".bold().bright_red(),
+            target.display(),
+            e
+        )?;
+        if e.is::<interrupt::Interrupted>() {
+            interrupted = Some(e);
+        } else {
+            failed_targets += 1;
+            if worst.as_ref().is_none_or(|w| exit_code(&e) > exit_code(w)) {
+                worst = Some(e);
             }
         }
-        None => {
-            let default_dir = default_target_dir()?;
-            default_target = Some(default_dir.clone());
-            default_dir
+    }
+    if let Some(summary) = DeploymentSummary::merge(&summaries) {
+        write_summary_document(ctx, options, &summary)?;
+    }
+    if let Some(e) = interrupted {
+        return Err(e);
+    }
+    if let Some(e) = worst {
+        return Err(e.context(format!(
+            "Deploy failed for {} of {} targets",
+            failed_targets, total
+        )));
+    }
+    Ok(())
+}
+
+/// Build once, then [`execute`] against each of `options.targets()` with its
+/// own buffered context, sequentially or on scoped threads under
+/// `--parallel-targets`. Results come back in target order, each with the
+/// options it ran under.
+fn execute_targets(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+) -> Result<Vec<(RunOptions, Buffered<DeploymentReport>)>> {
+    // Build once up front rather than once per target.
+    if options.build {
+        load_env_file(ctx, project_dir, options)?;
+        build_project(ctx, project_dir, options)?;
+    }
+    let per_target: Vec<RunOptions> = options
+        .targets()
+        .map(|target| RunOptions {
+            target_override: Some(target.clone()),
+            extra_targets: Vec::new(),
+            build: false,
+            ..options.clone()
+        })
+        .collect();
+
+    let runs: Vec<Buffered<DeploymentReport>> = if options.parallel_targets {
+        let parts = &context_parts(ctx);
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = per_target
+                .iter()
+                .map(|target_options| {
+                    // Runners are not shared across threads; each target
+                    // spawns its tools directly.
+                    scope.spawn(move || {
+                        run_buffered(parts.clone(), Box::new(SystemRunner), |ctx| {
+                            execute(project_dir, target_options, ctx)
+                        })
+                        .0
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("target deploy thread panicked"))
+                .collect()
+        })
+    } else {
+        let mut runs = Vec::new();
+        for target_options in &per_target {
+            let runner = std::mem::replace(&mut ctx.runner, Box::new(SystemRunner));
+            let (run, runner) = run_buffered(context_parts(ctx), runner, |ctx| {
+                execute(project_dir, target_options, ctx)
+            });
+            ctx.runner = runner;
+            runs.push(run);
         }
+        runs
     };
+    Ok(per_target.into_iter().zip(runs).collect())
+}
+
+/// The `CliContext` state copied into each per-target run: environment
+/// overrides, current executable, dry-run flag, and cancellation flag.
+type RunContextParts = (
+    HashMap<String, OsString>,
+    Option<PathBuf>,
+    bool,
+    Arc<AtomicBool>,
+);
+
+fn context_parts(ctx: &CliContext) -> RunContextParts {
+    (
+        ctx.env.clone(),
+        ctx.current_exe.clone(),
+        ctx.dry_run,
+        ctx.cancel.clone(),
+    )
+}
+
+/// Run `f` against a fresh context built from `parts`, with stdout and stderr
+/// captured in memory. The runner is handed back so the caller's mock
+/// survives sequential runs.
+fn run_buffered<T>(
+    (env, current_exe, dry_run, cancel): RunContextParts,
+    runner: Box<dyn CommandRunner>,
+    f: impl FnOnce(&mut CliContext) -> Result<T>,
+) -> (Buffered<T>, Box<dyn CommandRunner>) {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.env = env;
+    ctx.current_exe = current_exe;
+    ctx.dry_run = dry_run;
+    ctx.cancel = cancel;
+    ctx.runner = runner;
+    let result = f(&mut ctx);
+    let runner = ctx.runner;
+    (
+        Buffered {
+            stdout,
+            stderr,
+            result,
+        },
+        runner,
+    )
+}
+
+/// The deploy itself. Per-binary progress goes to `ctx` as it happens (gated by
+/// `options`); the final summary is left to the caller.
+fn execute(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+) -> Result<DeploymentReport> {
+    let run_started = std::time::Instant::now();
+    ctx.dry_run |= options.dry_run;
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    let mut plan = plan_deploy(project_dir, options, ctx)?;
+
+    if options.plan {
+        for binary in &plan.built_executables {
+            let exe_name = binary.file_name();
+            let source = display_source(&binary.source, project_dir, options);
+            writeln!(
+                ctx.stdout,
+                "{} {} {}",
+                "Plan".bold().cyan(),
+                exe_name.bold(),
+                format!(
+                    "{} -> {}",
+                    source.display(),
+                    plan.dest_paths[&exe_name].display()
+                )
+                .dimmed()
+            )?;
+        }
+        return Ok(DeploymentReport {
+            target_dir: plan.target_dir,
+            override_used: plan.override_used,
+            plan_only: true,
+            ..Default::default()
+        });
+    }
+
+    if ctx.dry_run {
+        return dry_run_report(project_dir, options, ctx, plan, run_started);
+    }
+
+    if let Some(hook) = options.pre_hook.as_deref() {
+        let planned: Vec<String> = plan
+            .built_executables
+            .iter()
+            .map(|binary| binary.file_name())
+            .collect();
+        if let Err(e) = run_pre_hook(ctx, hook, &plan.target_dir, &planned) {
+            let mut warnings = plan.warnings;
+            warnings.sort_by(|a, b| a.message.cmp(&b.message).then(a.code.cmp(&b.code)));
+            return Ok(DeploymentReport {
+                target_dir: plan.target_dir,
+                override_used: plan.override_used,
+                warnings,
+                unbuilt: plan.unbuilt,
+                aborted: Some(format!("{:#}", e)),
+                elapsed: run_started.elapsed(),
+                ..Default::default()
+            });
+        }
+    }
+
+    let target_dir = &plan.target_dir;
     if !target_dir.exists() {
-        fs::create_dir_all(&target_dir).with_context(|| {
+        fs::create_dir_all(target_dir).with_context(|| {
             format!("Failed to create target directory {}", target_dir.display())
         })?;
     } else if !target_dir.is_dir() {
@@ -697,29 +3898,654 @@ pub fn run_with_options(
             target_dir.display()
         );
     }
+    for dir in plan.dest_paths.values().filter_map(|dest| dest.parent()) {
+        if dir != target_dir {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {}", dir.display()))?;
+        }
+    }
 
     // Print project type if not quiet
-    if emit_text && project_type == ProjectType::Tauri {
-        let mode_indicator = if auto_detected { "[auto]" } else { "[--tauri]" };
+    if emit_text && plan.project_type == ProjectType::Tauri {
+        let mode_indicator = if plan.auto_detected {
+            "[auto]"
+        } else {
+            "[--tauri]"
+        };
         writeln!(
             ctx.stdout,
             "{} {} {}",
             "Detected".bold().cyan(),
-            project_type.label().bold().bright_white(),
+            plan.project_type.label().bold().bright_white(),
             format!("project {}", mode_indicator).dimmed()
         )?;
     }
 
-    let mut copied_count = 0;
+    let copies = copy_binaries(project_dir, options, ctx, &mut plan)?;
+    finish_deploy(project_dir, options, ctx, plan, copies, run_started)
+}
+
+/// What a deploy settled on before writing anything: the binaries, where each
+/// one goes, and the warnings and skips found along the way.
+struct DeployPlan {
+    project_type: ProjectType,
+    /// Whether `project_type` was detected rather than given.
+    auto_detected: bool,
+    built_executables: Vec<BuiltBinary>,
+    unbuilt: Vec<String>,
+    /// File names of the `cdylib` libraries among `built_executables`.
+    library_names: Vec<String>,
+    target_dir: PathBuf,
+    /// `--target`, or else `$MDRCP_TARGET`.
+    override_raw: Option<PathBuf>,
+    /// Which of the two `override_raw` came from, for the override note.
+    override_via: &'static str,
+    /// Whether `--target` was given.
+    override_used: bool,
+    /// The OS default target, when it could be resolved.
+    default_target: Option<PathBuf>,
+    /// Full destination of every binary in `built_executables`, by file name.
+    dest_paths: HashMap<String, PathBuf>,
+    warnings: Vec<Warning>,
+    skipped_binaries: Vec<String>,
+}
+
+/// Planning phase of [`execute`]: discover the built binaries, apply the
+/// filters and checks that can drop or reject them, and resolve every
+/// destination. Nothing is written.
+fn plan_deploy(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+) -> Result<DeployPlan> {
+    if options.strip && options.link_mode == LinkMode::Symlink {
+        // A symlinked destination is the build output itself.
+        anyhow::bail!("--strip cannot be combined with --symlink");
+    }
+    if options.prune && !options.only.is_empty() {
+        // A narrowed deploy would prune every binary it left out.
+        anyhow::bail!("--prune cannot be combined with --bin or --bins-from");
+    }
+
+    // Load --env-file first so it drives target/profile resolution.
+    load_env_file(ctx, project_dir, options)?;
+
+    if options.build {
+        build_project(ctx, project_dir, options)?;
+    }
+
+    let ProjectScan {
+        project_type,
+        auto_detected,
+        source_dir,
+        discovery:
+            Discovery {
+                built: built_executables,
+                unbuilt,
+                duplicates,
+            },
+    } = scan_project(
+        project_dir,
+        options,
+        cargo_target_dir(project_dir, options, |key| ctx.var_os(key)).as_deref(),
+        &mut Trace::new(options.verbosity, ctx.stderr),
+    )?;
+    let profile = options.profile;
+
+    // --prefix / --suffix rename executables on top of any manifest
+    // deploy_name; libraries keep their platform names.
+    let prefix = options.prefix.as_deref().unwrap_or_default();
+    let suffix = options.suffix.as_deref().unwrap_or_default();
+    for (flag, affix) in [("--prefix", prefix), ("--suffix", suffix)] {
+        if affix.contains(['/', '\\']) {
+            anyhow::bail!("{} must not contain path separators: {}", flag, affix);
+        }
+    }
+    let mut built_executables = built_executables;
+    if !prefix.is_empty() || !suffix.is_empty() {
+        for binary in built_executables.iter_mut().filter(|b| !b.library) {
+            let base = binary.deploy_name.as_deref().unwrap_or(&binary.base_name);
+            binary.deploy_name = Some(format!("{}{}{}", prefix, base, suffix));
+        }
+    }
+    let library_names: Vec<String> = built_executables
+        .iter()
+        .filter(|binary| binary.library)
+        .map(BuiltBinary::file_name)
+        .collect();
+
+    if built_executables.is_empty() {
+        let message = if options.profile_dir.is_some() {
+            format!("No built executables found in {}", source_dir.display())
+        } else if let Some(triple) = options.target_triple.as_deref() {
+            format!(
+                "No built {} executables found in {}. Have you run '{} --target {}'?",
+                profile.label(),
+                source_dir.display(),
+                profile.cargo_hint(project_type),
+                triple
+            )
+        } else {
+            format!(
+                "No built {} executables found. Have you run '{}'?",
+                profile.label(),
+                profile.cargo_hint(project_type)
+            )
+        };
+        return Err(DeployError::NoBuiltBinaries(message).into());
+    }
+
+    // Non-fatal issues collected during the run, reported in the JSON summary.
+    let mut warnings: Vec<Warning> = Vec::new();
+    let mut skipped_binaries: Vec<String> = Vec::new();
+
+    // Two members declaring one binary name build to the same file, so which
+    // one is in target/ depends on build order. Fail unless --allow-duplicates.
+    for (name, first, second) in duplicates {
+        let message = format!(
+            "duplicate binary name '{}' in members {} and {}",
+            name, first, second
+        );
+        if !options.allow_duplicates {
+            anyhow::bail!(
+                "{} (pass --allow-duplicates to deploy the one from {})",
+                message,
+                first
+            );
+        }
+        push_warning(
+            ctx,
+            options,
+            &mut warnings,
+            WarningCode::DuplicateBinary,
+            format!("{}; deploying the one from {}", message, first),
+        )?;
+    }
+
+    // Two sources with one destination name would silently overwrite each
+    // other. Fail, or under --allow-clobber keep the first source path.
+    built_executables.sort_by(|a, b| a.source.cmp(&b.source));
+    let mut kept: Vec<BuiltBinary> = Vec::with_capacity(built_executables.len());
+    let mut collisions: Vec<(String, Vec<PathBuf>)> = Vec::new();
+    for binary in built_executables {
+        let name = binary.file_name();
+        if let Some(winner) = kept.iter().find(|b| b.file_name() == name) {
+            match collisions.iter_mut().find(|(dest, _)| *dest == name) {
+                Some((_, sources)) => sources.push(binary.source),
+                None => collisions.push((name, vec![winner.source.clone(), binary.source])),
+            }
+        } else {
+            kept.push(binary);
+        }
+    }
+    for (dest, sources) in collisions {
+        let listed: Vec<String> = sources
+            .iter()
+            .map(|source| format!("`{}`", relative_to(source, project_dir).display()))
+            .collect();
+        let message = format!(
+            "{} binaries map to destination `{}`: {}",
+            if sources.len() == 2 { "two" } else { "several" },
+            dest,
+            listed.join(" and ")
+        );
+        if !options.allow_clobber {
+            anyhow::bail!("{} (pass --allow-clobber to deploy the first)", message);
+        }
+        let message = format!("{}; deploying {}", message, listed[0]);
+        push_warning(
+            ctx,
+            options,
+            &mut warnings,
+            WarningCode::DestinationCollision,
+            message,
+        )?;
+    }
+    if options.strip && !cfg!(unix) {
+        push_warning(
+            ctx,
+            options,
+            &mut warnings,
+            WarningCode::StripUnsupported,
+            "--strip is only supported on Unix; deploying unstripped binaries".to_string(),
+        )?;
+    }
+    kept.sort_by(|a, b| a.base_name.cmp(&b.base_name));
+    let built_executables = kept;
+
+    // --max-file-size: drop oversized sources (usually unstripped artifacts).
+    let mut built_executables = built_executables;
+    if let Some(limit) = options.max_file_size {
+        let mut kept = Vec::with_capacity(built_executables.len());
+        for binary in built_executables {
+            if binary.size <= limit {
+                kept.push(binary);
+                continue;
+            }
+            let exe_name = binary.file_name();
+            let message = format!(
+                "Skipped {}: {} bytes exceeds --max-file-size of {} bytes",
+                exe_name, binary.size, limit
+            );
+            if options.strict && !options.suppresses(WarningCode::OversizedBinary) {
+                anyhow::bail!("{} (--strict)", message);
+            }
+            push_warning(
+                ctx,
+                options,
+                &mut warnings,
+                WarningCode::OversizedBinary,
+                message,
+            )?;
+            skipped_binaries.push(exe_name);
+        }
+        built_executables = kept;
+    }
+
+    // --check-fresh: a source edited after the build means a forgotten rebuild.
+    if options.check_fresh != FreshnessCheck::Off {
+        let mut newest_by_crate: HashMap<PathBuf, Option<(PathBuf, std::time::SystemTime)>> =
+            HashMap::new();
+        for binary in &built_executables {
+            let Some(crate_dir) = binary.crate_dir.as_ref() else {
+                continue;
+            };
+            let newest = newest_by_crate
+                .entry(crate_dir.clone())
+                .or_insert_with(|| newest_source(crate_dir));
+            let (Some((source, source_mtime)), Some(built_mtime)) =
+                (newest.as_ref(), file_mtime(&binary.source))
+            else {
+                continue;
+            };
+            if *source_mtime <= built_mtime {
+                continue;
+            }
+            let message = format!(
+                "{} may be stale: {} was modified after it was built",
+                binary.file_name(),
+                source.display()
+            );
+            if options.check_fresh == FreshnessCheck::Strict {
+                anyhow::bail!("{} (--check-fresh=strict)", message);
+            }
+            push_warning(
+                ctx,
+                options,
+                &mut warnings,
+                WarningCode::StaleBinary,
+                message,
+            )?;
+        }
+    }
+
+    if let Some(expected) = options.expect_count {
+        let found = built_executables.len();
+        if found != expected {
+            let message = format!(
+                "Expected {} executable(s) to deploy but found {}",
+                expected, found
+            );
+            if options.strict && !options.suppresses(WarningCode::ExpectCountMismatch) {
+                anyhow::bail!("{} (--strict)", message);
+            }
+            push_warning(
+                ctx,
+                options,
+                &mut warnings,
+                WarningCode::ExpectCountMismatch,
+                message,
+            )?;
+        }
+    }
+
+    // Without --target, $MDRCP_TARGET is an override of its own; it sits
+    // above the OS default (and $MD_TARGET_DIR) but below the command line.
+    let env_target = match ctx.var_os(TARGET_ENV) {
+        Some(raw) if options.target_override.is_none() => {
+            if raw.is_empty() {
+                anyhow::bail!("{} is set but empty; provide a directory", TARGET_ENV);
+            }
+            Some(PathBuf::from(raw))
+        }
+        _ => None,
+    };
+    let override_via = if env_target.is_some() {
+        TARGET_ENV
+    } else {
+        "--target"
+    };
+    let override_raw = options.target_override.clone().or(env_target);
+    let override_used = options.target_override.is_some();
+    let mut default_target: Option<PathBuf> = None;
+    let target_dir = match override_raw.as_ref() {
+        Some(override_dir) => {
+            if let Ok(default_dir) = platform_default_target(ctx, project_dir, options) {
+                default_target = Some(default_dir);
+            }
+            if override_dir.is_absolute() {
+                override_dir.clone()
+            } else {
+                project_dir.join(override_dir)
+            }
+        }
+        None => {
+            let default_dir = platform_default_target(ctx, project_dir, options)?;
+            default_target = Some(default_dir.clone());
+            default_dir
+        }
+    };
+    // `--target ..` and friends scatter binaries over the project's parents.
+    let resolve_target = !options.no_resolve_target;
+    if target_dir.exists() {
+        let target = comparable_path(&target_dir, resolve_target);
+        let project = comparable_path(project_dir, resolve_target);
+        if project.starts_with(&target) {
+            let message = format!(
+                "Target {} contains the project directory; binaries would land in an ancestor of the project",
+                target_dir.display()
+            );
+            if options.strict && !options.suppresses(WarningCode::TargetContainsProject) {
+                anyhow::bail!("{} (--strict)", message);
+            }
+            push_warning(
+                ctx,
+                options,
+                &mut warnings,
+                WarningCode::TargetContainsProject,
+                message,
+            )?;
+        }
+    }
+
+    // --dest <name>=<dir>: per-binary directories that replace the target.
+    let dest_dirs: HashMap<&str, PathBuf> = options
+        .dest_overrides
+        .iter()
+        .map(|(name, dir)| {
+            let dir = if dir.is_absolute() {
+                dir.clone()
+            } else {
+                project_dir.join(dir)
+            };
+            (name.as_str(), dir)
+        })
+        .collect();
+    let dest_dir_for = |binary: &BuiltBinary| -> PathBuf {
+        if let Some(dir) = dest_dirs.get(binary.base_name.as_str()) {
+            return dir.clone();
+        }
+        match (&binary.package, options.by_package, &binary.dest) {
+            (Some(package), true, _) => target_dir.join(package),
+            // Package metadata sits below --target and $MDRCP_TARGET.
+            (_, _, Some(dest)) if override_raw.is_none() => dest.clone(),
+            _ => target_dir.clone(),
+        }
+    };
+
+    let dest_paths = built_executables
+        .iter()
+        .map(|binary| {
+            (
+                binary.file_name(),
+                dest_dir_for(binary).join(binary.file_name()),
+            )
+        })
+        .collect();
+    Ok(DeployPlan {
+        project_type,
+        auto_detected,
+        built_executables,
+        unbuilt,
+        library_names,
+        target_dir,
+        override_raw,
+        override_via,
+        override_used,
+        default_target,
+        dest_paths,
+        warnings,
+        skipped_binaries,
+    })
+}
+
+/// `--dry-run`: print what `plan` would copy and how that differs from the
+/// deploy recorded in the target manifest, touching nothing.
+fn dry_run_report(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+    plan: DeployPlan,
+    run_started: std::time::Instant,
+) -> Result<DeploymentReport> {
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    let DeployPlan {
+        built_executables,
+        unbuilt,
+        library_names,
+        target_dir,
+        override_used,
+        dest_paths,
+        mut warnings,
+        mut skipped_binaries,
+        ..
+    } = plan;
+    // Compare against the last recorded deploy without touching the target.
+    let planned: Vec<(String, Option<std::time::SystemTime>)> = built_executables
+        .iter()
+        .map(|binary| {
+            let exe_name = binary.file_name();
+            let mtime = file_mtime(&binary.source);
+            (exe_name, mtime)
+        })
+        .collect();
+    let previous = read_target_manifest(&target_dir);
+    let changes = ManifestDiff::between(previous.as_ref(), &planned);
+    // Reported as copied so the summary shows what a real run would deploy.
+    let mut would_copy: Vec<(String, PathBuf)> = built_executables
+        .iter()
+        .map(|binary| {
+            let exe_name = binary.file_name();
+            let dest = dest_paths[&exe_name].clone();
+            (exe_name, dest)
+        })
+        .collect();
+    would_copy.sort();
+    let sources = built_executables
+        .iter()
+        .filter(|binary| {
+            would_copy
+                .iter()
+                .any(|(name, _)| *name == binary.file_name())
+        })
+        .map(|binary| {
+            (
+                binary.file_name(),
+                display_source(&binary.source, project_dir, options),
+            )
+        })
+        .collect();
+    if emit_text {
+        for binary in &built_executables {
+            let exe_name = binary.file_name();
+            let ver_suffix = binary
+                .version
+                .as_deref()
+                .map(|v| format!(" {}", format!("v{}", v).dimmed()))
+                .unwrap_or_default();
+            let verb = match options.link_mode {
+                LinkMode::Copy => "Would copy",
+                LinkMode::Symlink => "Would link",
+            };
+            writeln!(
+                ctx.stdout,
+                "{} {}{} {}",
+                verb.bold().cyan(),
+                exe_name.bold(),
+                ver_suffix,
+                format!("-> {}", dest_paths[&exe_name].display()).dimmed()
+            )?;
+        }
+        let groups = [
+            ("Would add", &changes.added),
+            ("Would update", &changes.updated),
+            ("Unchanged", &changes.unchanged),
+            ("Would remove", &changes.removed),
+        ];
+        for (label, names) in groups {
+            for name in names {
+                writeln!(ctx.stdout, "{} {}", label.bold().cyan(), name.bold())?;
+            }
+        }
+        for action in post_copy_actions(project_dir, options) {
+            writeln!(ctx.stdout, "{} {}", "Would".bold().cyan(), action)?;
+        }
+        let baseline = if previous.is_some() {
+            format!("compared with {}", TARGET_MANIFEST_FILE)
+        } else {
+            format!("no {} recorded yet", TARGET_MANIFEST_FILE)
+        };
+        writeln!(ctx.stdout)?;
+        writeln!(
+            ctx.stdout,
+            "{} {}",
+            "Dry run:".bold().yellow(),
+            format!("nothing written to {} ({})", target_dir.display(), baseline).dimmed()
+        )?;
+    }
+    warnings.sort_by(|a, b| a.message.cmp(&b.message).then(a.code.cmp(&b.code)));
+    skipped_binaries.sort();
+    Ok(DeploymentReport {
+        target_dir,
+        override_used,
+        copied: would_copy.iter().map(|(name, _)| name.clone()).collect(),
+        copied_libraries: would_copy
+            .iter()
+            .filter(|(name, _)| library_names.contains(name))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        destinations: would_copy.into_iter().collect(),
+        sources,
+        skipped: skipped_binaries,
+        warnings,
+        unbuilt,
+        changes: Some(changes),
+        elapsed: run_started.elapsed(),
+        ..Default::default()
+    })
+}
+
+/// What the copy phase of [`execute`] did with each planned binary.
+struct CopyOutcome {
+    copied_binaries: Vec<String>,
+    skipped_binaries: Vec<String>,
+    failed_binaries: Vec<FailedCopy>,
+    /// Binaries never started because the run was interrupted.
+    not_started: Vec<String>,
+    warnings: Vec<Warning>,
+    backed_up: Vec<String>,
+    /// The `--backup-dir` folder for this run, whether or not it was used.
+    backup_stamp: Option<PathBuf>,
+    /// SHA-256 of each copy checked by `--verify`, keyed by file name.
+    hashes: BTreeMap<String, String>,
+    /// `--timings`: how long each copy took, keyed by file name.
+    copy_times: BTreeMap<String, std::time::Duration>,
+    self_update_report: Option<SelfUpdateReport>,
+    /// File name of the running mdrcp when a self-update was deferred.
+    self_update_name: Option<String>,
+    deploy_started: std::time::SystemTime,
+    /// Source size of each planned binary, keyed by file name.
+    source_sizes: HashMap<String, u64>,
+    /// Displayed source of each planned binary, keyed by file name.
+    sources: BTreeMap<String, PathBuf>,
+}
+
+/// Copy phase of [`execute`]: place every planned binary (on up to `--jobs`
+/// threads), commit or discard `--atomic` staging files, then run a deferred
+/// self-update.
+fn copy_binaries(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+    plan: &mut DeployPlan,
+) -> Result<CopyOutcome> {
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    let built_executables = std::mem::take(&mut plan.built_executables);
+    let mut warnings = std::mem::take(&mut plan.warnings);
+    let mut skipped_binaries = std::mem::take(&mut plan.skipped_binaries);
+    let target_dir = &plan.target_dir;
+    let dest_paths = &plan.dest_paths;
+
     let mut copied_binaries: Vec<String> = Vec::new();
     let mut failed_binaries: Vec<FailedCopy> = Vec::new();
+    let mut backed_up: Vec<String> = Vec::new();
+    // SHA-256 of each copy checked by --verify, keyed by file name.
+    let mut hashes: BTreeMap<String, String> = BTreeMap::new();
+    // --timings: how long each copy took, keyed by file name.
+    let mut copy_times: BTreeMap<String, std::time::Duration> = BTreeMap::new();
+    // Copies decided on by the loop below, run afterwards by run_copy_jobs.
+    let mut copy_jobs: Vec<CopyJob> = Vec::new();
+    // --atomic: binaries copied to their staging path, awaiting the commit.
+    let mut staged: Vec<(Placed, PathBuf)> = Vec::new();
     // Track if we need to self-update (deferred until after all copies attempted)
     let mut pending_self_update: Option<(PathBuf, PathBuf)> = None;
+    // --atomic with `replace`: the running executable's file name, staged and
+    // committed with the rest.
+    let mut self_replace: Option<String> = None;
+    let mut self_update_report: Option<SelfUpdateReport> = None;
 
-    let source_dir = rust_base_dir.join("target").join(profile.artifact_dir());
+    // With --since-last-deploy, only sources modified after the recorded deploy
+    // in the target manifest are copied.
+    let deploy_started = std::time::SystemTime::now();
+    // --backup-dir: created only once something is actually backed up.
+    let backup_stamp = options
+        .backup_dir
+        .as_ref()
+        .map(|dir| backup_stamp_dir(&project_dir.join(dir), deploy_started));
+    let last_deploy = if options.since_last_deploy {
+        read_target_manifest(target_dir).map(|m| m.deployed_at_time())
+    } else {
+        None
+    };
 
-    for binary in built_executables {
-        let exe_name = exe_filename(&binary.base_name);
+    // Recorded as deploy provenance on each copied file.
+    let source_project = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+
+    // Byte-based progress with ETA on stderr; never in quiet or JSON modes.
+    let show_progress = options.progress && emit_text;
+    let total_files = built_executables.len();
+    let total_bytes = total_source_bytes(&built_executables);
+    let mut done_files = 0;
+    let mut done_bytes: u64 = 0;
+    let started = std::time::Instant::now();
+    let min_file_size = options.min_file_size.unwrap_or(DEFAULT_MIN_FILE_SIZE);
+    let source_sizes: HashMap<String, u64> = built_executables
+        .iter()
+        .map(|binary| (binary.file_name(), binary.size))
+        .collect();
+    let sources: BTreeMap<String, PathBuf> = built_executables
+        .iter()
+        .map(|binary| {
+            (
+                binary.file_name(),
+                display_source(&binary.source, project_dir, options),
+            )
+        })
+        .collect();
+
+    let mut not_started: Vec<String> = Vec::new();
+    for (index, binary) in built_executables.into_iter().enumerate() {
+        // Push out the previous binary's lines before the next (possibly
+        // long) copy; the loop has too many `continue`s to do it at the end.
+        ctx.flush()?;
+        let counter = deploy_counter(index, total_files);
+        let exe_name = binary.file_name();
+        if ctx.cancel.load(Ordering::SeqCst) {
+            not_started.push(exe_name);
+            continue;
+        }
         // Dimmed " v<version>" suffix for log lines, or empty when unknown.
         let ver_suffix = binary
             .version
@@ -727,16 +4553,198 @@ pub fn run_with_options(
             .map(|v| format!(" {}", format!("v{}", v).dimmed()))
             .unwrap_or_default();
 
-        let source_path = source_dir.join(&exe_name);
-        let target_path = target_dir.join(&exe_name);
+        let source_path = binary.source.clone();
+        let target_path = dest_paths[&exe_name].clone();
+
+        // Copying a file onto itself truncates it; never touch it.
+        if same_file(&source_path, &target_path) {
+            if emit_text {
+                writeln!(
+                    ctx.stderr,
+                    "{}{} {}{} {}",
+                    counter,
+                    "Rejected".bold().bright_red(),
+                    exe_name.bold().yellow(),
+                    ver_suffix,
+                    format!("(-> {})", target_path.display()).dimmed()
+                )?;
+            }
+            failed_binaries.push(FailedCopy {
+                error: format!(
+                    "source and destination are the same file: {}",
+                    target_path.display()
+                ),
+                binary: exe_name,
+            });
+            continue;
+        }
+
+        if binary.size < min_file_size {
+            let error = format!(
+                "binary `{}` is suspiciously small ({} bytes)",
+                binary.base_name, binary.size
+            );
+            if emit_text {
+                writeln!(
+                    ctx.stderr,
+                    "{}{} {}{} {}",
+                    counter,
+                    "Rejected".bold().bright_red(),
+                    exe_name.bold().yellow(),
+                    ver_suffix,
+                    format!("({} bytes, minimum {})", binary.size, min_file_size).dimmed()
+                )?;
+            }
+            failed_binaries.push(FailedCopy {
+                error,
+                binary: exe_name,
+            });
+            continue;
+        }
+
+        if let Some(reason) = incremental_skip_reason(
+            &source_path,
+            &target_path,
+            last_deploy,
+            options.copy_if_newer,
+        ) {
+            if emit_text {
+                writeln!(
+                    ctx.stdout,
+                    "{}{} {}{} {}",
+                    counter,
+                    "Skipped".bold().dimmed(),
+                    exe_name.dimmed(),
+                    ver_suffix,
+                    format!("({})", reason).dimmed()
+                )?;
+            }
+            skipped_binaries.push(exe_name);
+            continue;
+        }
+
+        if options.skip_unchanged && is_unchanged(&source_path, &target_path, options.verify) {
+            if emit_text {
+                writeln!(
+                    ctx.stdout,
+                    "{}{} {}{}",
+                    counter,
+                    "Unchanged".dimmed(),
+                    exe_name.dimmed(),
+                    ver_suffix
+                )?;
+            }
+            skipped_binaries.push(exe_name);
+            continue;
+        }
+
+        if options.verify_format {
+            let verdict = match has_host_exe_magic(&source_path) {
+                Ok(true) => None,
+                Ok(false) => Some("not a native executable for this platform".to_string()),
+                Err(e) => Some(format!("could not read header: {}", e)),
+            };
+            if let Some(reason) = verdict {
+                if emit_text {
+                    writeln!(
+                        ctx.stderr,
+                        "{}{} {}{} {}",
+                        counter,
+                        "Rejected".bold().bright_red(),
+                        exe_name.bold().yellow(),
+                        ver_suffix,
+                        format!("({})", reason).dimmed()
+                    )?;
+                }
+                failed_binaries.push(FailedCopy {
+                    error: format!("Refused to deploy {}: {}", source_path.display(), reason),
+                    binary: exe_name,
+                });
+                continue;
+            }
+        }
 
-        // Check if this is a self-update scenario
-        if is_self_update_target(&target_path, ctx.current_exe.as_deref()) {
+        // Check if this is a self-update scenario. Under --atomic, `replace`
+        // is an ordinary staged copy that the commit renames into place.
+        let atomic_replace = options.atomic && options.self_update == SelfUpdateStrategy::Replace;
+        if atomic_replace && is_self_update_target(&target_path, ctx.current_exe.as_deref()) {
+            self_replace = Some(exe_name.clone());
+        }
+        if options.self_update != SelfUpdateStrategy::Copy
+            && !atomic_replace
+            && is_self_update_target(&target_path, ctx.current_exe.as_deref())
+        {
+            match options.self_update {
+                SelfUpdateStrategy::Defer | SelfUpdateStrategy::Copy => {}
+                SelfUpdateStrategy::Skip => {
+                    let message = format!(
+                        "Skipped {}: destination is the running mdrcp executable",
+                        target_path.display()
+                    );
+                    push_warning(
+                        ctx,
+                        options,
+                        &mut warnings,
+                        WarningCode::SelfUpdateSkipped,
+                        message,
+                    )?;
+                    skipped_binaries.push(exe_name);
+                    self_update_report = Some(SelfUpdateReport::new(false, "skipped", None));
+                    continue;
+                }
+                SelfUpdateStrategy::Replace => {
+                    match replace_in_place(&source_path, &target_path) {
+                        Ok(()) => {
+                            if emit_text {
+                                writeln!(
+                                    ctx.stdout,
+                                    "{}{} {}{} {}",
+                                    counter,
+                                    "Replaced".bold().green(),
+                                    exe_name.bold().green(),
+                                    ver_suffix,
+                                    format!("-> {} (running executable)", target_path.display())
+                                        .dimmed()
+                                )?;
+                            }
+                            emit_copied_event(ctx, options, &exe_name, &target_path)?;
+                            copied_binaries.push(exe_name);
+                            self_update_report =
+                                Some(SelfUpdateReport::new(true, "replaced", None));
+                        }
+                        Err(e) => {
+                            self_update_report =
+                                Some(SelfUpdateReport::new(true, "failed", Some(e.to_string())));
+                            if emit_text {
+                                writeln!(
+                                    ctx.stderr,
+                                    "{}{} {}{} {}",
+                                    counter,
+                                    "Failed".bold().bright_red(),
+                                    exe_name.bold().yellow(),
+                                    ver_suffix,
+                                    format!("-> {}: {}", target_path.display(), e).dimmed()
+                                )?;
+                            }
+                            failed_binaries.push(FailedCopy {
+                                error: format!(
+                                    "Failed to replace running executable {}: {}",
+                                    target_path.display(),
+                                    e
+                                ),
+                                binary: exe_name,
+                            });
+                        }
+                    }
+                    continue;
+                }
+            }
             // Defer self-update until after all other copies
             if emit_text {
                 writeln!(
                     ctx.stdout,
-                    "{} {}{} {}",
+                    "{}{} {}{} {}",
+                    counter,
                     "Deferred".bold().cyan(),
                     exe_name.bold().cyan(),
                     ver_suffix,
@@ -747,40 +4755,214 @@ pub fn run_with_options(
             continue;
         }
 
-        let source_mtime = format_file_mtime(&source_path);
-
-        match fs::copy(&source_path, &target_path) {
-            Ok(_) => {
+        // Set when the old destination was moved aside, so a failed copy can
+        // put it back.
+        let mut backup_path: Option<PathBuf> = None;
+        if target_path.exists() {
+            let replacing = matches!(
+                options.on_conflict,
+                ConflictPolicy::Overwrite | ConflictPolicy::Backup
+            );
+            if replacing
+                && options.interactive
+                && !options.yes
+                && ctx.stdin_is_terminal
+                && !ctx.confirm_overwrite(&target_path)?
+            {
                 if emit_text {
-                    let mtime_str = source_mtime.as_deref().unwrap_or("unknown");
                     writeln!(
                         ctx.stdout,
-                        "{} {}{} {} {}",
-                        "Copied".bold().green(),
-                        exe_name.bold().green(),
+                        "{}{} {}{} {}",
+                        counter,
+                        "Skipped".bold().dimmed(),
+                        exe_name.dimmed(),
+                        ver_suffix,
+                        "(overwrite declined)".dimmed()
+                    )?;
+                }
+                skipped_binaries.push(exe_name);
+                continue;
+            }
+            let conflict_error = match options.on_conflict {
+                ConflictPolicy::Overwrite => None,
+                ConflictPolicy::Skip => {
+                    if emit_text {
+                        writeln!(
+                            ctx.stdout,
+                            "{}{} {}{} {}",
+                            counter,
+                            "Skipped".bold().dimmed(),
+                            exe_name.dimmed(),
+                            ver_suffix,
+                            "(destination exists)".dimmed()
+                        )?;
+                    }
+                    skipped_binaries.push(exe_name);
+                    continue;
+                }
+                ConflictPolicy::Fail => Some(format!(
+                    "Destination {} already exists (--on-conflict fail)",
+                    target_path.display()
+                )),
+                // --atomic leaves the installed binary in place until the
+                // commit, which takes the backup just before renaming.
+                ConflictPolicy::Backup if options.atomic => None,
+                ConflictPolicy::Backup => {
+                    let moved = match backup_stamp.as_deref() {
+                        Some(dir) => backup_into(&target_path, target_dir, dir),
+                        None => backup_existing(&target_path),
+                    };
+                    match moved {
+                        Ok(backup) => {
+                            if emit_text {
+                                writeln!(
+                                    ctx.stdout,
+                                    "{} {} {}",
+                                    "Backed up".bold().cyan(),
+                                    exe_name.bold(),
+                                    format!("-> {}", backup.display()).dimmed()
+                                )?;
+                            }
+                            backup_path = Some(backup);
+                            None
+                        }
+                        Err(e) => Some(format!(
+                            "Failed to back up {}: {}",
+                            target_path.display(),
+                            e
+                        )),
+                    }
+                }
+            };
+            if let Some(error) = conflict_error {
+                if emit_text {
+                    writeln!(
+                        ctx.stderr,
+                        "{}{} {}{} {}",
+                        counter,
+                        "Failed".bold().bright_red(),
+                        exe_name.bold().yellow(),
                         ver_suffix,
-                        format!("-> {}", target_path.display()).dimmed(),
-                        format!("({})", mtime_str).dimmed()
+                        format!("-> {}", error).dimmed()
                     )?;
                 }
-                copied_count += 1;
-                copied_binaries.push(exe_name);
+                failed_binaries.push(FailedCopy {
+                    binary: exe_name,
+                    error,
+                });
+                continue;
+            }
+        }
+
+        let source_mtime = format_file_mtime(&source_path);
+
+        // Under --atomic every binary lands beside its destination first and
+        // is only renamed into place after the loop, once none has failed.
+        let place_path = if options.atomic {
+            staging_path(&target_path)
+        } else {
+            target_path.clone()
+        };
+        let placed = Placed {
+            exe_name: exe_name.clone(),
+            ver_suffix: ver_suffix.clone(),
+            version: binary.version.clone(),
+            source_path: source_path.clone(),
+            target_path: target_path.clone(),
+            source_mtime,
+            sha256: None,
+            counter,
+        };
+        copy_jobs.push(CopyJob {
+            placed,
+            source_path,
+            place_path,
+            backup_path,
+            size: binary.size,
+        });
+    }
+
+    // The copies themselves run on up to --jobs worker threads; each result is
+    // handled here, in discovery order, so output and summaries stay
+    // deterministic and still stream as copies finish.
+    let workers = options.jobs.unwrap_or_else(default_jobs);
+    let cancel = ctx.cancel.clone();
+    run_copy_jobs(&copy_jobs, options, workers, &cancel, |job, result| {
+        ctx.flush()?;
+        let exe_name = job.placed.exe_name.clone();
+        let target_path = &job.placed.target_path;
+        let Some(result) = result else {
+            // Interrupted before this copy started; undo its backup.
+            if let Some(backup) = &job.backup_path {
+                let _ = fs::rename(backup, target_path);
+            }
+            not_started.push(exe_name);
+            return Ok(());
+        };
+        if let (true, Ok((_, elapsed))) = (options.timings, &result) {
+            copy_times.insert(exe_name.clone(), *elapsed);
+        }
+        match result {
+            Ok((sha256, _)) if options.atomic => {
+                let placed = Placed {
+                    sha256,
+                    ..job.placed.clone()
+                };
+                staged.push((placed, job.place_path.clone()));
+            }
+            Ok((sha256, _)) => {
+                if let Some(backup) = &job.backup_path {
+                    backed_up.push(backup.display().to_string());
+                }
+                let placed = Placed {
+                    sha256,
+                    ..job.placed.clone()
+                };
+                match finish_placed(ctx, options, &placed, &source_project, &mut warnings)? {
+                    None => {
+                        if let Some(sha256) = placed.sha256 {
+                            hashes.insert(exe_name.clone(), sha256);
+                        }
+                        emit_copied_event(ctx, options, &exe_name, &job.placed.target_path)?;
+                        copied_binaries.push(exe_name);
+                    }
+                    Some(error) => failed_binaries.push(FailedCopy {
+                        binary: exe_name,
+                        error,
+                    }),
+                }
             }
             Err(e) => {
                 // Normal copy failure
-                let error_msg = format!(
+                let mut error_msg = format!(
                     "Failed to copy {} to {}: {}",
-                    source_path.display(),
+                    job.source_path.display(),
                     target_path.display(),
                     e
                 );
+                if options.atomic {
+                    let _ = fs::remove_file(&job.place_path);
+                }
+                // Never leave the user without the binary they had before.
+                if let Some(backup) = &job.backup_path {
+                    let _ = fs::remove_file(target_path);
+                    match fs::rename(backup, target_path) {
+                        Ok(()) => error_msg.push_str("; previous binary restored"),
+                        Err(restore) => error_msg.push_str(&format!(
+                            "; previous binary left at {} ({})",
+                            backup.display(),
+                            restore
+                        )),
+                    }
+                }
                 if emit_text {
                     writeln!(
                         ctx.stderr,
-                        "{} {}{} {}",
+                        "{}{} {}{} {}",
+                        job.placed.counter,
                         "Failed".bold().bright_red(),
                         exe_name.bold().yellow(),
-                        ver_suffix,
+                        job.placed.ver_suffix,
                         format!("-> {}: {}", target_path.display(), e).dimmed()
                     )?;
                 }
@@ -790,8 +4972,199 @@ pub fn run_with_options(
                 });
             }
         }
+
+        done_files += 1;
+        done_bytes += job.size;
+        if show_progress {
+            writeln!(
+                ctx.stderr,
+                "{}",
+                format_progress(
+                    done_files,
+                    total_files,
+                    done_bytes,
+                    total_bytes,
+                    started.elapsed()
+                )
+                .dimmed()
+            )?;
+        }
+        Ok(())
+    })?;
+    ctx.flush()?;
+
+    if !not_started.is_empty() {
+        // Interrupted: no new copies (including a deferred self-update), and
+        // nothing staged left beside the destinations.
+        if let Some((_, target_path)) = pending_self_update.take() {
+            if let Some(name) = target_path.file_name() {
+                not_started.push(name.to_string_lossy().into_owned());
+            }
+        }
+        for dest in dest_paths.values() {
+            let _ = fs::remove_file(staging_path(dest));
+        }
+        if emit_text {
+            writeln!(
+                ctx.stderr,
+                "{} {}",
+                "Interrupted:".bold().yellow(),
+                format!(
+                    "stopped before {} binar{}: {}",
+                    not_started.len(),
+                    if not_started.len() == 1 { "y" } else { "ies" },
+                    not_started.join(", ")
+                )
+                .dimmed()
+            )?;
+        }
+    }
+
+    if options.atomic {
+        if failed_binaries.is_empty() && not_started.is_empty() {
+            // Rename every staged copy into place. What each one replaces is
+            // kept (as its backup, or aside beside it) until all renames have
+            // succeeded, so a failure part-way puts the earlier ones back.
+            let mut committed: Vec<(Placed, Option<PathBuf>, Option<PathBuf>)> = Vec::new();
+            let mut commit_error: Option<FailedCopy> = None;
+            let mut staged = staged.into_iter();
+            for (placed, stage) in staged.by_ref() {
+                let mut backup = None;
+                let mut aside = None;
+                if placed.target_path.exists() {
+                    let moved = if options.on_conflict == ConflictPolicy::Backup {
+                        match backup_stamp.as_deref() {
+                            Some(dir) => backup_into(&placed.target_path, target_dir, dir),
+                            None => backup_existing(&placed.target_path),
+                        }
+                        .map(|path| backup = Some(path))
+                    } else {
+                        let path = aside_path(&placed.target_path);
+                        fs::rename(&placed.target_path, &path).map(|()| aside = Some(path))
+                    };
+                    if let Err(e) = moved {
+                        let _ = fs::remove_file(&stage);
+                        commit_error = Some(FailedCopy {
+                            error: format!(
+                                "Failed to back up {}: {}",
+                                placed.target_path.display(),
+                                e
+                            ),
+                            binary: placed.exe_name,
+                        });
+                        break;
+                    }
+                }
+                if let Err(e) = fs::rename(&stage, &placed.target_path) {
+                    let _ = fs::remove_file(&stage);
+                    if let Some(previous) = backup.as_ref().or(aside.as_ref()) {
+                        let _ = fs::rename(previous, &placed.target_path);
+                    }
+                    commit_error = Some(FailedCopy {
+                        binary: placed.exe_name,
+                        error: format!(
+                            "Failed to move {} into place: {}",
+                            placed.target_path.display(),
+                            e
+                        ),
+                    });
+                    break;
+                }
+                committed.push((placed, backup, aside));
+            }
+
+            if let Some(error) = commit_error {
+                for (_, stage) in staged {
+                    let _ = fs::remove_file(&stage);
+                }
+                let rolled_back = committed.len();
+                for (placed, backup, aside) in committed.into_iter().rev() {
+                    let _ = match backup.or(aside) {
+                        Some(previous) => fs::rename(previous, &placed.target_path),
+                        None => fs::remove_file(&placed.target_path),
+                    };
+                }
+                failed_binaries.push(error);
+                if emit_text {
+                    writeln!(
+                        ctx.stderr,
+                        "{} {}",
+                        "Aborted:".bold().bright_red(),
+                        format!(
+                            "commit failed (--atomic); rolled back {} binar{}",
+                            rolled_back,
+                            if rolled_back == 1 { "y" } else { "ies" }
+                        )
+                        .dimmed()
+                    )?;
+                }
+            } else {
+                // Committed: strip, sign, and tag. A failure here marks that
+                // binary failed but no longer rolls anything back.
+                for (placed, backup, aside) in committed {
+                    if let Some(aside) = aside {
+                        let _ = fs::remove_file(aside);
+                    }
+                    if let Some(backup) = backup {
+                        if emit_text {
+                            writeln!(
+                                ctx.stdout,
+                                "{} {} {}",
+                                "Backed up".bold().cyan(),
+                                placed.exe_name.bold(),
+                                format!("-> {}", backup.display()).dimmed()
+                            )?;
+                        }
+                        backed_up.push(backup.display().to_string());
+                    }
+                    if self_replace.as_ref() == Some(&placed.exe_name) {
+                        self_update_report = Some(SelfUpdateReport::new(true, "replaced", None));
+                    }
+                    match finish_placed(ctx, options, &placed, &source_project, &mut warnings)? {
+                        None => {
+                            if let Some(sha256) = placed.sha256 {
+                                hashes.insert(placed.exe_name.clone(), sha256);
+                            }
+                            emit_copied_event(ctx, options, &placed.exe_name, &placed.target_path)?;
+                            copied_binaries.push(placed.exe_name);
+                        }
+                        Some(error) => failed_binaries.push(FailedCopy {
+                            binary: placed.exe_name,
+                            error,
+                        }),
+                    }
+                }
+            }
+        } else {
+            // Abort: drop every staged copy. Nothing was backed up yet, so the
+            // installed binaries are exactly as they were.
+            let discarded = staged.len();
+            for (_, stage) in staged {
+                let _ = fs::remove_file(&stage);
+            }
+            if emit_text {
+                writeln!(
+                    ctx.stderr,
+                    "{} {}",
+                    "Aborted:".bold().bright_red(),
+                    format!(
+                        "no binaries were replaced (--atomic); discarded {} staged cop{}",
+                        discarded,
+                        if discarded == 1 { "y" } else { "ies" }
+                    )
+                    .dimmed()
+                )?;
+            }
+        }
     }
 
+    // The running mdrcp may still be being replaced by a spawned updater, so
+    // --prune must leave it alone.
+    let self_update_name = pending_self_update
+        .as_ref()
+        .and_then(|(_, target_path)| target_path.file_name())
+        .map(|name| name.to_string_lossy().into_owned());
+
     // Handle pending self-update after all other copies
     if let Some((source_path, target_path)) = pending_self_update {
         let exe_name = target_path
@@ -811,10 +5184,11 @@ pub fn run_with_options(
                             "Spawned updater process. Update will complete momentarily.".dimmed()
                         )?;
                     }
-                    // Return Ok so the process exits cleanly
-                    return Ok(());
+                    self_update_report = Some(SelfUpdateReport::new(true, "spawned", None));
                 }
                 SelfUpdateResult::Failed(msg) => {
+                    self_update_report =
+                        Some(SelfUpdateReport::new(true, "failed", Some(msg.clone())));
                     let error_msg = format!(
                         "Failed to self-update {} to {}: {}",
                         source_path.display(),
@@ -842,6 +5216,11 @@ pub fn run_with_options(
                         source_path.display(),
                         target_path.display()
                     );
+                    self_update_report = Some(SelfUpdateReport::new(
+                        true,
+                        "failed",
+                        Some("file in use".to_string()),
+                    ));
                     failed_binaries.push(FailedCopy {
                         binary: exe_name,
                         error: error_msg,
@@ -858,33 +5237,510 @@ pub fn run_with_options(
                     "Fix other copy failures first, then re-run.".dimmed()
                 )?;
             }
+            let reason = "Self-update skipped due to other failures".to_string();
+            self_update_report = Some(SelfUpdateReport::new(
+                false,
+                "skipped",
+                Some(reason.clone()),
+            ));
             failed_binaries.push(FailedCopy {
                 binary: exe_name,
-                error: "Self-update skipped due to other failures".to_string(),
+                error: reason,
+            });
+        }
+    }
+
+    Ok(CopyOutcome {
+        copied_binaries,
+        skipped_binaries,
+        failed_binaries,
+        not_started,
+        warnings,
+        backed_up,
+        backup_stamp,
+        hashes,
+        copy_times,
+        self_update_report,
+        self_update_name,
+        deploy_started,
+        source_sizes,
+        sources,
+    })
+}
+
+/// Post-copy steps of [`execute`]: the sizes table, checksum file, artifact
+/// manifest, `--prune`, the target manifest, and the PATH check; then the
+/// sorted report.
+/// Whether `path`, recorded as `name` by an earlier deploy, is still the
+/// file mdrcp put there from this project: the recorded project and any
+/// provenance source must be this one, and the recorded (or sidecar) hash
+/// must match the file's contents.
+fn prune_candidate_matches(
+    path: &Path,
+    recorded: &TargetManifest,
+    name: &str,
+    source_project: &Path,
+) -> bool {
+    let project = source_project.display().to_string();
+    let provenance = provenance::read_provenance(path).unwrap_or_default();
+    if [&recorded.project, &provenance.source]
+        .into_iter()
+        .flatten()
+        .any(|source| *source != project)
+    {
+        return false;
+    }
+    match recorded.hashes.get(name).or(provenance.sha256.as_ref()) {
+        Some(expected) => provenance::sha256_hex(path).is_ok_and(|actual| actual == *expected),
+        None => true,
+    }
+}
+
+fn finish_deploy(
+    project_dir: &Path,
+    options: &RunOptions,
+    ctx: &mut CliContext,
+    plan: DeployPlan,
+    copies: CopyOutcome,
+    run_started: std::time::Instant,
+) -> Result<DeploymentReport> {
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    let DeployPlan {
+        unbuilt,
+        library_names,
+        target_dir,
+        override_raw,
+        override_via,
+        override_used,
+        default_target,
+        dest_paths,
+        ..
+    } = plan;
+    let CopyOutcome {
+        mut copied_binaries,
+        mut skipped_binaries,
+        mut failed_binaries,
+        mut not_started,
+        mut warnings,
+        mut backed_up,
+        backup_stamp,
+        hashes,
+        copy_times,
+        self_update_report,
+        self_update_name,
+        deploy_started,
+        source_sizes,
+        sources,
+    } = copies;
+
+    if options.sizes && emit_text && !copied_binaries.is_empty() {
+        let mut rows: Vec<(String, u64, PathBuf)> = copied_binaries
+            .iter()
+            .map(|name| {
+                (
+                    name.clone(),
+                    source_sizes.get(name).copied().unwrap_or_default(),
+                    dest_paths
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_else(|| target_dir.join(name)),
+                )
+            })
+            .collect();
+        rows.sort();
+        writeln!(ctx.stdout)?;
+        writeln!(ctx.stdout, "{}", "Sizes:".bold().cyan())?;
+        for line in format_sizes_table(&rows) {
+            writeln!(ctx.stdout, "{}", line)?;
+        }
+    }
+
+    // SHA256SUMS-style record of what actually landed in the target.
+    if let Some(checksum_file) = options.checksum_file.as_ref() {
+        let path = if checksum_file.is_absolute() {
+            checksum_file.clone()
+        } else {
+            project_dir.join(checksum_file)
+        };
+        // Names are relative to the target; binaries sent outside it by
+        // --dest are listed by absolute path.
+        let mut names: Vec<String> = copied_binaries
+            .iter()
+            .map(|name| match dest_paths.get(name) {
+                Some(dest) => match dest.strip_prefix(&target_dir) {
+                    Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+                    Err(_) => dest.display().to_string(),
+                },
+                None => name.clone(),
+            })
+            .collect();
+        names.sort();
+        provenance::write_checksum_file(&path, &target_dir, &names)?;
+        if emit_text {
+            writeln!(
+                ctx.stdout,
+                "{} {}",
+                "Checksums".bold().cyan(),
+                format!("-> {}", path.display()).dimmed()
+            )?;
+        }
+    }
+
+    // The copies already succeeded, so a manifest that cannot be written is
+    // only a warning.
+    if let Some(manifest_out) = options.manifest_out.as_ref() {
+        let path = project_dir.join(manifest_out);
+        let mut artifacts: Vec<DeployedArtifact> = copied_binaries
+            .iter()
+            .map(|name| {
+                let dest = dest_paths
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| target_dir.join(name));
+                DeployedArtifact {
+                    name: name.clone(),
+                    size: fs::metadata(&dest).map(|m| m.len()).unwrap_or_default(),
+                    // Not canonicalized: a --symlink destination would
+                    // resolve to its source.
+                    path: std::path::absolute(&dest)
+                        .unwrap_or(dest)
+                        .display()
+                        .to_string(),
+                    sha256: hashes.get(name).cloned(),
+                }
+            })
+            .collect();
+        artifacts.sort_by(|a, b| a.name.cmp(&b.name));
+        let manifest = ArtifactManifest {
+            deploy_id: options.deploy_id.clone(),
+            target_dir: target_dir.display().to_string(),
+            artifacts,
+        };
+        let mut json = Vec::new();
+        let written = write_summary(&mut json, SummaryFormat::JsonPretty, None, &manifest)
+            .and_then(|()| {
+                fs::write(&path, json)
+                    .with_context(|| format!("Failed to write {}", path.display()))
             });
+        match written {
+            Ok(()) if emit_text => {
+                writeln!(
+                    ctx.stdout,
+                    "{} {}",
+                    "Manifest".bold().cyan(),
+                    format!("-> {}", path.display()).dimmed()
+                )?;
+            }
+            Ok(()) => {}
+            Err(e) => {
+                let message = format!("Could not write artifact manifest: {:#}", e);
+                push_warning(
+                    ctx,
+                    options,
+                    &mut warnings,
+                    WarningCode::ManifestWriteFailed,
+                    message,
+                )?;
+            }
+        }
+    }
+
+    // --prune: delete what the last recorded deploy installed but this one
+    // no longer includes. Only names from the manifest are candidates, so
+    // files mdrcp never installed are never touched; a partial run prunes
+    // nothing.
+    let complete = failed_binaries.is_empty() && not_started.is_empty();
+    let mut pruned: Vec<String> = Vec::new();
+    let mut prune_failed: Vec<String> = Vec::new();
+    let source_project = project_dir
+        .canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf());
+    if options.prune && complete {
+        let recorded = read_target_manifest(&target_dir).unwrap_or_default();
+        // The manifest is only trusted for directories this run deploys to,
+        // including --dest directories of binaries it no longer builds.
+        let dest_dirs: Vec<PathBuf> = options
+            .dest_overrides
+            .values()
+            .map(|dir| project_dir.join(dir))
+            .collect();
+        let prune_dirs: Vec<&Path> = std::iter::once(target_dir.as_path())
+            .chain(dest_paths.values().filter_map(|dest| dest.parent()))
+            .chain(dest_dirs.iter().map(PathBuf::as_path))
+            .collect();
+        for name in &recorded.binaries {
+            if copied_binaries.contains(name)
+                || skipped_binaries.contains(name)
+                || self_update_name.as_ref() == Some(name)
+            {
+                continue;
+            }
+            // A hand-edited manifest may only name files called like the
+            // binary itself.
+            let path = recorded.destination(&target_dir, name);
+            if Path::new(name).file_name() != Some(std::ffi::OsStr::new(name))
+                || path.file_name() != Some(std::ffi::OsStr::new(name))
+            {
+                continue;
+            }
+            match fs::symlink_metadata(&path) {
+                Ok(meta) if !meta.is_dir() => {}
+                // Already gone (or replaced by a directory): nothing to do.
+                _ => continue,
+            }
+            let refusal = if !path.parent().is_some_and(|dir| prune_dirs.contains(&dir)) {
+                Some("outside the directories this deploy writes to")
+            } else if !prune_candidate_matches(&path, &recorded, name, &source_project) {
+                Some("it no longer matches what mdrcp deployed")
+            } else {
+                None
+            };
+            if let Some(reason) = refusal {
+                let message = format!("Not pruning {}: {}", path.display(), reason);
+                push_warning(
+                    ctx,
+                    options,
+                    &mut warnings,
+                    WarningCode::PruneFailed,
+                    message,
+                )?;
+                continue;
+            }
+            match fs::remove_file(&path) {
+                Ok(()) => {
+                    if emit_text {
+                        writeln!(
+                            ctx.stdout,
+                            "{} {} {}",
+                            "Pruned".bold().yellow(),
+                            name.bold(),
+                            format!("({})", path.display()).dimmed()
+                        )?;
+                    }
+                    pruned.push(name.clone());
+                }
+                Err(e) => {
+                    let message = format!("Could not prune {}: {}", path.display(), e);
+                    push_warning(
+                        ctx,
+                        options,
+                        &mut warnings,
+                        WarningCode::PruneFailed,
+                        message,
+                    )?;
+                    prune_failed.push(name.clone());
+                }
+            }
+        }
+    }
+
+    // Every complete deploy records when mdrcp last ran here. An interrupted
+    // run keeps the previous manifest so the next --since-last-deploy run
+    // still picks up the binaries it never reached.
+    if complete {
+        // Names that could not be pruned stay recorded for the next run.
+        let previous = read_target_manifest(&target_dir).unwrap_or_default();
+        let deployed: Vec<String> = copied_binaries
+            .iter()
+            .chain(&skipped_binaries)
+            .chain(&prune_failed)
+            .chain(&self_update_name)
+            .cloned()
+            .collect();
+        let mut manifest = TargetManifest::new(
+            deploy_started,
+            format_utc(deploy_started.into()).unwrap_or_default(),
+            deployed,
+        );
+        manifest.project = Some(source_project.display().to_string());
+        // Binaries left in place keep the path recorded for them last time.
+        manifest.destinations = manifest
+            .binaries
+            .iter()
+            .filter_map(|name| {
+                let path = dest_paths
+                    .get(name)
+                    .cloned()
+                    .or_else(|| previous.destinations.get(name).cloned())?;
+                (path != target_dir.join(name)).then(|| (name.clone(), path))
+            })
+            .collect();
+        // Copies are hashed as they now sit on disk (reusing --verify's hash
+        // unless strip rewrote the file); binaries left in place keep theirs.
+        manifest.hashes = manifest
+            .binaries
+            .iter()
+            .filter_map(|name| {
+                let copied = copied_binaries.contains(name);
+                let known = if copied {
+                    hashes.get(name).filter(|_| !options.strip)
+                } else {
+                    previous.hashes.get(name)
+                };
+                let sha256 = match known {
+                    Some(sha256) => sha256.clone(),
+                    None => {
+                        provenance::sha256_hex(&manifest.destination(&target_dir, name)).ok()?
+                    }
+                };
+                Some((name.clone(), sha256))
+            })
+            .collect();
+        if let Err(e) = write_target_manifest(&target_dir, &manifest) {
+            let message = format!("Could not record deploy time: {:#}", e);
+            push_warning(
+                ctx,
+                options,
+                &mut warnings,
+                WarningCode::ManifestWriteFailed,
+                message,
+            )?;
+        }
+    }
+
+    // Deployed binaries are only runnable by name when the target is on PATH.
+    // An unset PATH gives nothing meaningful to compare against.
+    if !copied_binaries.is_empty() {
+        if let Some(path_var) = ctx.var_os("PATH") {
+            if !dir_on_path(&target_dir, &path_var) {
+                let message = format!(
+                    "{} is not on PATH; add it so deployed binaries can be run by name",
+                    target_dir.display()
+                );
+                push_warning(ctx, options, &mut warnings, WarningCode::NotOnPath, message)?;
+            }
+        }
+    }
+
+    let override_note = override_raw.map(|raw| {
+        // A suppressed redundant_target warning is dropped by not comparing at all.
+        let default_for_note = default_target
+            .as_deref()
+            .filter(|_| !options.suppresses(WarningCode::RedundantTarget));
+        build_override_note(
+            &raw,
+            &target_dir,
+            default_for_note,
+            !options.no_resolve_target,
+            override_via,
+        )
+    });
+    if let Some(note) = override_note.as_ref() {
+        warnings.extend(note.warnings.iter().cloned());
+    }
+
+    // Sort every collection so identical deploys serialize byte-for-byte.
+    warnings.sort_by(|a, b| a.message.cmp(&b.message).then(a.code.cmp(&b.code)));
+    copied_binaries.sort();
+    skipped_binaries.sort();
+    failed_binaries.sort();
+    not_started.sort();
+    backed_up.sort();
+    pruned.sort();
+    let backup_dir = match backup_stamp {
+        Some(dir) if !backed_up.is_empty() => Some(dir),
+        Some(dir) => {
+            // Every backup was restored after a failed copy; drop the folder
+            // if that left it empty.
+            let _ = fs::remove_dir(&dir);
+            None
         }
+        None => None,
+    };
+
+    let destinations = copied_binaries
+        .iter()
+        .filter_map(|name| Some((name.clone(), dest_paths.get(name)?.clone())))
+        .collect();
+
+    Ok(DeploymentReport {
+        target_dir,
+        override_used,
+        destinations,
+        interrupted: not_started,
+        backed_up,
+        backup_dir,
+        sha256: hashes,
+        timings: copy_times
+            .into_iter()
+            .filter(|(name, _)| copied_binaries.contains(name))
+            .collect(),
+        sources: sources
+            .into_iter()
+            .filter(|(name, _)| copied_binaries.contains(name))
+            .collect(),
+        copied_libraries: copied_binaries
+            .iter()
+            .filter(|name| library_names.contains(name))
+            .cloned()
+            .collect(),
+        copied: copied_binaries,
+        skipped: skipped_binaries,
+        failed: failed_binaries,
+        warnings,
+        unbuilt,
+        self_update: self_update_report,
+        changes: None,
+        elapsed: run_started.elapsed(),
+        override_note,
+        plan_only: false,
+        aborted: None,
+        pruned,
+        extra_targets: Vec::new(),
+    })
+}
+/// Render the end-of-run summary for `report`: text lines, or the JSON object.
+fn write_report(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    report: &DeploymentReport,
+) -> Result<()> {
+    write_text_report(ctx, options, report)?;
+    if options.summary != SummaryFormat::Text {
+        write_summary_document(ctx, options, &deployment_summary(options, report))?;
     }
+    if options.summary != SummaryFormat::Ndjson {
+        write_annotations(ctx, options, report)?;
+    }
+    Ok(())
+}
 
-    if emit_text {
+/// The text part of the summary: counts, failures, unbuilt and slowest
+/// binaries, and the override note (as warnings under a structured summary).
+fn write_text_report(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    report: &DeploymentReport,
+) -> Result<()> {
+    let emit_text = options.summary == SummaryFormat::Text && !options.quiet;
+    if emit_text && report.changes.is_none() && report.aborted.is_none() {
         writeln!(ctx.stdout)?;
         writeln!(
             ctx.stdout,
             "{}",
-            format_deployment_summary(copied_count, &target_dir, override_used)
+            format_deployment_summary(
+                report.copied.len(),
+                report.copied.len()
+                    + report.skipped.len()
+                    + report.failed.len()
+                    + report.interrupted.len(),
+                &report.target_dir,
+                report.override_used
+            )
         )?;
 
         // Report failures if any
-        if !failed_binaries.is_empty() {
+        if !report.failed.is_empty() {
             writeln!(ctx.stdout)?;
             writeln!(
                 ctx.stderr,
                 "{} {}",
                 "Failed to copy".bold().bright_red(),
-                format!("{} executable(s):", failed_binaries.len())
+                format!("{} executable(s):", report.failed.len())
                     .bold()
                     .bright_red()
             )?;
-            for failed in &failed_binaries {
+            for failed in &report.failed {
                 writeln!(
                     ctx.stderr,
                     "  {} {}",
@@ -893,72 +5749,201 @@ pub fn run_with_options(
                 )?;
             }
         }
+
+        if options.report_unbuilt && !report.unbuilt.is_empty() {
+            writeln!(ctx.stdout)?;
+            writeln!(
+                ctx.stdout,
+                "{} {}",
+                "Unbuilt".bold().yellow(),
+                format!("{} declared executable(s):", report.unbuilt.len()).dimmed()
+            )?;
+            for name in &report.unbuilt {
+                writeln!(ctx.stdout, "  {} {}", "•".yellow(), name)?;
+            }
+        }
+
+        if options.print_sources && !report.sources.is_empty() {
+            writeln!(ctx.stdout)?;
+            writeln!(ctx.stdout, "{}", "Sources:".bold().cyan())?;
+            for (name, source) in &report.sources {
+                writeln!(
+                    ctx.stdout,
+                    "  {} {} {}",
+                    "•".cyan(),
+                    name,
+                    format!("<- {}", source.display()).dimmed()
+                )?;
+            }
+        }
+
+        if options.timings && !report.timings.is_empty() {
+            writeln!(ctx.stdout)?;
+            writeln!(ctx.stdout, "{}", "Slowest copies:".bold().cyan())?;
+            for line in format_slowest(&report.timings, 3) {
+                writeln!(ctx.stdout, "{}", line)?;
+            }
+        }
     }
 
-    let mut override_note: Option<OverrideNote> = None;
-    if let Some(raw) = override_raw {
-        let note = build_override_note(&raw, &target_dir, default_target.as_deref());
+    if let Some(note) = report.override_note.as_ref() {
         if emit_text {
             for line in &note.lines {
                 writeln!(ctx.stdout, "{}", line)?;
             }
         } else {
             for warning in &note.warnings {
-                writeln!(ctx.stderr, "Warning: {}", warning)?;
+                writeln!(ctx.stderr, "Warning: {}", warning.message)?;
             }
         }
-        override_note = Some(note);
     }
 
-    if produce_json {
-        let warnings = override_note
+    Ok(())
+}
+
+/// The machine-readable summary of `report`.
+fn deployment_summary(options: &RunOptions, report: &DeploymentReport) -> DeploymentSummary {
+    let target_dir = report.target_dir.display().to_string();
+    DeploymentSummary {
+        schema_version: SUMMARY_SCHEMA_VERSION,
+        tool_version: cli::version_metadata().version.to_string(),
+        status: report.status().to_string(),
+        deploy_id: options.deploy_id.clone(),
+        copied_count: report.copied.len() - report.copied_libraries.len(),
+        target_dirs: vec![target_dir.clone()],
+        target_dir,
+        override_used: report.override_used,
+        host: HostInfo::current(),
+        copied_binaries: report
+            .copied
+            .iter()
+            .filter(|name| !report.copied_libraries.contains(name))
+            .cloned()
+            .collect(),
+        skipped_binaries: report.skipped.clone(),
+        failed_binaries: report.failed.clone(),
+        warnings: report.warnings.clone(),
+        unbuilt: options.report_unbuilt.then(|| report.unbuilt.clone()),
+        self_update: report.self_update.clone(),
+        dry_run: report.changes.is_some(),
+        changes: report.changes.clone(),
+        destinations: if options.dest_overrides.is_empty() && !options.by_package {
+            BTreeMap::new()
+        } else {
+            report
+                .destinations
+                .iter()
+                .map(|(name, path)| (name.clone(), path.display().to_string()))
+                .collect()
+        },
+        interrupted: report.interrupted.clone(),
+        backed_up: report.backed_up.clone(),
+        pruned: report.pruned.clone(),
+        backup_dir: report
+            .backup_dir
             .as_ref()
-            .map(|n| n.warnings.clone())
-            .unwrap_or_default();
-        let status = if failed_binaries.is_empty() {
-            "ok"
-        } else if copied_count > 0 {
-            "partial"
+            .map(|dir| dir.display().to_string()),
+        copied: if options.verify || options.strip || options.timings || options.print_sources {
+            let stripped = options.strip && cfg!(unix) && report.changes.is_none();
+            report
+                .copied
+                .iter()
+                .map(|name| CopiedBinary {
+                    name: name.clone(),
+                    sha256: report.sha256.get(name).cloned(),
+                    stripped,
+                    duration_ms: report
+                        .timings
+                        .get(name)
+                        .map(|elapsed| elapsed.as_millis() as u64),
+                    source: report
+                        .sources
+                        .get(name)
+                        .filter(|_| options.print_sources)
+                        .map(|source| source.display().to_string()),
+                })
+                .collect()
         } else {
-            "failed"
-        };
-        let summary = DeploymentSummary {
-            status,
-            copied_count,
-            target_dir: target_dir.display().to_string(),
-            override_used,
-            copied_binaries,
-            failed_binaries: failed_binaries.clone(),
-            warnings,
-        };
-        let summary_json = match summary_format {
-            SummaryFormat::Json => {
-                serde_json::to_string(&summary).context("Failed to serialize deployment summary")?
-            }
-            SummaryFormat::JsonPretty => serde_json::to_string_pretty(&summary)
-                .context("Failed to serialize deployment summary")?,
-            SummaryFormat::Text => unreachable!(),
-        };
-        writeln!(ctx.stdout, "{}", summary_json)?;
+            Vec::new()
+        },
+        copied_libraries: report.copied_libraries.clone(),
+        aborted: report.aborted.clone(),
     }
+}
 
-    // Return error if any copies failed
-    if !failed_binaries.is_empty() {
-        if copied_count > 0 {
-            anyhow::bail!(
-                "Failed to copy {} of {} executables (copied {} successfully)",
-                failed_binaries.len(),
-                copied_count + failed_binaries.len(),
-                copied_count
-            );
-        } else {
-            anyhow::bail!("Failed to copy {} executable(s)", failed_binaries.len());
+/// Write `summary` in the structured `options.summary` format: JSON, YAML,
+/// Markdown, or the closing NDJSON line.
+fn write_summary_document(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    summary: &DeploymentSummary,
+) -> Result<()> {
+    let format = match options.summary {
+        SummaryFormat::JsonPretty if options.summary_compact => SummaryFormat::Json,
+        SummaryFormat::Ndjson => {
+            let event = NdjsonEvent {
+                event: "summary",
+                data: summary,
+            };
+            return write_summary(ctx.stdout, SummaryFormat::Ndjson, None, &event);
         }
-    }
+        SummaryFormat::Markdown => {
+            writeln!(ctx.stdout, "{}", markdown::deployment(summary))?;
+            return Ok(());
+        }
+        format => format,
+    };
+    write_summary(ctx.stdout, format, options.summary_indent, summary)
+}
 
+/// `--format github`: GitHub Actions annotations for the failures (or the
+/// success) in `report`. They go to stdout unless a machine-readable summary
+/// owns it; the runner picks workflow commands up from stderr too.
+fn write_annotations(
+    ctx: &mut CliContext,
+    options: &RunOptions,
+    report: &DeploymentReport,
+) -> std::io::Result<()> {
+    if options.ci_format != Some(CiFormat::Github) || report.changes.is_some() {
+        return Ok(());
+    }
+    let out: &mut dyn Write = if options.summary == SummaryFormat::Text {
+        ctx.stdout
+    } else {
+        ctx.stderr
+    };
+    if let Some(reason) = &report.aborted {
+        writeln!(out, "::error::{}", github_escape(reason))?;
+    }
+    for failed in &report.failed {
+        writeln!(
+            out,
+            "::error::{}",
+            github_escape(&format!(
+                "Failed to copy {}: {}",
+                failed.binary, failed.error
+            ))
+        )?;
+    }
+    if report.status() == "ok" {
+        writeln!(
+            out,
+            "::notice::Deployed {} executables",
+            report.copied.len() - report.copied_libraries.len()
+        )?;
+    }
     Ok(())
 }
 
+/// Escape a workflow command message: `%`, CR, and LF would otherwise end or
+/// corrupt the command.
+fn github_escape(message: &str) -> String {
+    message
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
 pub fn run(project_dir: &Path) -> Result<()> {
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
@@ -972,13 +5957,27 @@ mod tests {
 
     #[test]
     fn test_format_deployment_summary_override_flag() {
-        let summary = format_deployment_summary(1, Path::new("/tmp/bin"), true);
+        let summary = format_deployment_summary(1, 1, Path::new("/tmp/bin"), true);
         assert!(summary.contains("[--target]"));
     }
 
+    #[test]
+    fn test_format_deployment_summary_of_attempted() {
+        let summary = format_deployment_summary(8, 10, Path::new("/tmp/bin"), false);
+        assert!(summary.contains("8 of 10"), "{summary}");
+        let single = format_deployment_summary(1, 1, Path::new("/tmp/bin"), false);
+        assert!(!single.contains(" of "), "{single}");
+    }
+
+    #[test]
+    fn test_deploy_counter() {
+        assert_eq!(deploy_counter(0, 1), "");
+        assert!(deploy_counter(2, 10).contains("[3/10]"));
+    }
+
     #[test]
     fn test_format_deployment_summary_default_no_flag() {
-        let summary = format_deployment_summary(1, Path::new("/tmp/bin"), false);
+        let summary = format_deployment_summary(1, 1, Path::new("/tmp/bin"), false);
         assert!(!summary.contains("[--target]"));
     }
 
@@ -988,14 +5987,16 @@ mod tests {
             Path::new("/tmp/bin"),
             Path::new("/tmp/bin"),
             Some(Path::new("/tmp/bin")),
+            false,
+            "--target",
         );
         assert!(note.lines.iter().any(|l| l.contains("Warning:")));
         assert_eq!(
             note.warnings,
-            vec![
+            vec![Warning::new(
+                WarningCode::RedundantTarget,
                 "Resolved target matches default destination; override may be redundant."
-                    .to_string()
-            ]
+            )]
         );
     }
 
@@ -1005,11 +6006,41 @@ mod tests {
             Path::new("/tmp/bin"),
             Path::new("/tmp/out"),
             Some(Path::new("/tmp/default")),
+            true,
+            "--target",
         );
         assert!(!note.lines.iter().any(|l| l.contains("Warning:")));
         assert!(note.warnings.is_empty());
     }
 
+    #[test]
+    fn test_warning_codes_serialize_as_stable_strings() {
+        for code in WarningCode::ALL {
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::Value::String(code.as_str().to_string())
+            );
+        }
+        let json = serde_json::to_value(Warning::new(WarningCode::OversizedBinary, "big")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"code": "oversized_binary", "message": "big"})
+        );
+    }
+
+    #[test]
+    fn test_dir_on_path_matches_canonical_entries() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir(&bin).unwrap();
+        let other = temp.path().join("other");
+        let path_var =
+            std::env::join_paths([other.clone(), temp.path().join(".").join("bin")]).unwrap();
+        assert!(dir_on_path(&bin, &path_var));
+        assert!(!dir_on_path(&other.join("missing"), &path_var));
+        assert!(!dir_on_path(&bin, std::ffi::OsStr::new("")));
+    }
+
     #[test]
     fn test_labels() {
         assert_eq!(ProjectType::Standard.label(), "standard");
@@ -1036,6 +6067,94 @@ mod tests {
         assert!(names.contains(&"my-pkg".to_string()));
     }
 
+    #[test]
+    fn test_candidate_metadata_deploy_name_and_dest() {
+        let toml_str = r#"
+            [package]
+            name = "my-pkg"
+
+            [package.metadata.mdrcp]
+            deploy_name = { custom-bin = "short" }
+            dest = "out"
+        "#;
+        let val: Value = toml::from_str(toml_str).unwrap();
+        let candidate = Candidate::of(&val, &val, Path::new("/crate"));
+        assert_eq!(
+            candidate.deploy_name_for("custom-bin").as_deref(),
+            Some("short")
+        );
+        assert_eq!(candidate.deploy_name_for("my-pkg"), None);
+        assert_eq!(candidate.dest, Some(PathBuf::from("/crate/out")));
+    }
+
+    #[test]
+    fn test_same_file_resolves_dot_dot() {
+        let temp = tempfile::tempdir().unwrap();
+        let bin = temp.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("demo"), "x").unwrap();
+        assert!(same_file(
+            &bin.join("demo"),
+            &bin.join("..").join("bin").join("demo")
+        ));
+        assert!(!same_file(&bin.join("demo"), &temp.path().join("demo")));
+    }
+
+    #[test]
+    fn test_github_escape() {
+        assert_eq!(github_escape("50% done\r\nnext"), "50%25 done%0D%0Anext");
+    }
+
+    #[test]
+    fn test_candidate_metadata_target_rename_and_exclude() {
+        let toml_str = r#"
+            [package]
+            name = "my-pkg"
+
+            [package.metadata.mdrcp]
+            rename = "short"
+            deploy_name = "ignored"
+            target = "bin"
+            dest = "ignored"
+            exclude = ["debug-*", "["]
+        "#;
+        let val: Value = toml::from_str(toml_str).unwrap();
+        let candidate = Candidate::of(&val, &val, Path::new("/crate"));
+        assert_eq!(
+            candidate.deploy_name_for("my-pkg").as_deref(),
+            Some("short")
+        );
+        assert_eq!(candidate.dest, Some(PathBuf::from("/crate/bin")));
+        assert_eq!(candidate.exclude.len(), 1);
+        assert!(candidate.exclude[0].matches("debug-dump"));
+
+        let single: Value =
+            toml::from_str("[package.metadata.mdrcp]\nexclude = \"bench\"").unwrap();
+        assert_eq!(
+            Candidate::of(&single, &single, Path::new("/"))
+                .exclude
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_manifest_cdylib_name() {
+        let named: Value =
+            toml::from_str("[package]\nname = \"my-pkg\"\n[lib]\nname = \"core\"\ncrate-type = [\"cdylib\", \"rlib\"]")
+                .unwrap();
+        assert_eq!(manifest_cdylib_name(&named).as_deref(), Some("core"));
+        let from_package: Value =
+            toml::from_str("[package]\nname = \"my-pkg\"\n[lib]\ncrate-type = [\"cdylib\"]")
+                .unwrap();
+        assert_eq!(
+            manifest_cdylib_name(&from_package).as_deref(),
+            Some("my_pkg")
+        );
+        let rlib: Value = toml::from_str("[package]\nname = \"my-pkg\"\n[lib]\n").unwrap();
+        assert_eq!(manifest_cdylib_name(&rlib), None);
+    }
+
     #[test]
     fn test_manifest_bin_names_explicit_bin_same_as_package() {
         let toml_str = r#"
@@ -1158,7 +6277,15 @@ mod tests {
     fn test_find_built_executables_empty() {
         let root = Path::new(".");
         let data = Value::Table(toml::map::Map::new());
-        let res = find_built_executables(root, &data, BuildProfile::Release, &[]);
+        let res = find_built_executables(
+            root,
+            &data,
+            Path::new("target/release"),
+            &[],
+            &[],
+            Selection::default(),
+            &mut Trace::off(),
+        );
         assert!(res.is_err());
         assert!(res
             .unwrap_err()
@@ -1195,6 +6322,301 @@ mod tests {
         assert!(autobin_names(temp.path()).is_empty());
     }
 
+    #[test]
+    fn test_read_ignore_patterns_skips_comments_and_blanks() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join(IGNORE_FILE),
+            "# internal tooling\ninternal-*\n\n  bench  \n",
+        )
+        .unwrap();
+        let patterns = read_ignore_patterns(temp.path()).unwrap();
+        let raw: Vec<&str> = patterns.iter().map(|p| p.as_str()).collect();
+        assert_eq!(raw, vec!["internal-*", "bench"]);
+    }
+
+    #[test]
+    fn test_read_ignore_patterns_missing_and_invalid() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(read_ignore_patterns(temp.path()).unwrap().is_empty());
+
+        std::fs::write(temp.path().join(IGNORE_FILE), "ok\n[unclosed\n").unwrap();
+        let err = read_ignore_patterns(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_all_executables_skips_cargo_subdirs() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp = tempfile::tempdir().unwrap();
+        let rel = temp.path().join("target").join("release");
+        let make_exe = |path: &Path| {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "x").unwrap();
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        make_exe(&rel.join("tool"));
+        make_exe(&rel.join("deps").join("tool-1a2b3c"));
+        make_exe(&rel.join("build").join("script"));
+        make_exe(&rel.join("bundle").join("helper"));
+        make_exe(&rel.join("scratch").join("junk"));
+        std::fs::write(rel.join("tool.d"), "deps").unwrap();
+
+        let names = |excludes: &[String]| -> Vec<String> {
+            find_all_executables(temp.path(), &rel, excludes)
+                .unwrap()
+                .into_iter()
+                .map(|b| b.base_name)
+                .collect()
+        };
+        assert_eq!(names(&[]), vec!["helper", "junk", "tool"]);
+        assert_eq!(names(&["scratch".to_string()]), vec!["helper", "tool"]);
+    }
+
+    #[test]
+    fn test_total_source_bytes_matches_file_sizes() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[[bin]]\nname = \"tool\"\n",
+        )
+        .unwrap();
+        let rel = temp.path().join("target").join("release");
+        std::fs::create_dir_all(&rel).unwrap();
+        std::fs::write(rel.join(exe_filename("app")), vec![0u8; 1500]).unwrap();
+        std::fs::write(rel.join(exe_filename("tool")), vec![0u8; 250]).unwrap();
+
+        let data: Value =
+            toml::from_str(&std::fs::read_to_string(temp.path().join("Cargo.toml")).unwrap())
+                .unwrap();
+        let found = find_built_executables(
+            temp.path(),
+            &data,
+            &rel,
+            &[],
+            &[],
+            Selection::default(),
+            &mut Trace::off(),
+        )
+        .unwrap();
+        assert_eq!(total_source_bytes(&found.built), 1750);
+    }
+
+    #[test]
+    fn test_format_size_units() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 << 30), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_format_sizes_table_aligns_columns() {
+        let rows = vec![
+            ("a".to_string(), 10, PathBuf::from("/d/a")),
+            ("longer".to_string(), 2048, PathBuf::from("/d/longer")),
+        ];
+        assert_eq!(
+            format_sizes_table(&rows),
+            vec![
+                "  a          10 B  /d/a".to_string(),
+                "  longer  2.0 KiB  /d/longer".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_slowest_sorts_and_truncates() {
+        let timings: BTreeMap<String, std::time::Duration> =
+            [("a", 5), ("bb", 120), ("c", 40), ("d", 40)]
+                .into_iter()
+                .map(|(name, ms)| (name.to_string(), std::time::Duration::from_millis(ms)))
+                .collect();
+        assert_eq!(
+            format_slowest(&timings, 3),
+            vec![
+                "  bb  120 ms".to_string(),
+                "  c   40 ms".to_string(),
+                "  d   40 ms".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_copy_removes_mismatched_copy() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("source");
+        let copy = temp.path().join("copy");
+        fs::write(&source, "same").unwrap();
+        fs::write(&copy, "same").unwrap();
+        assert_eq!(
+            verify_copy(&source, &copy).unwrap(),
+            provenance::sha256_hex(&source).unwrap()
+        );
+
+        fs::write(&copy, "different").unwrap();
+        let err = verify_copy(&source, &copy).unwrap_err();
+        assert!(err.to_string().contains("SHA-256 mismatch"), "{err}");
+        assert!(!copy.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_deployed_mode_keeps_stricter_bits_and_adds_exec() {
+        assert_eq!(deployed_mode(0o100644, None), 0o755);
+        assert_eq!(deployed_mode(0o100600, None), 0o700);
+        assert_eq!(deployed_mode(0o100777, None), 0o755);
+        assert_eq!(deployed_mode(0o100755, Some(0o750)), 0o750);
+    }
+
+    #[test]
+    fn test_format_progress_percent_and_eta() {
+        use std::time::Duration;
+        let line = format_progress(1, 4, 250, 1000, Duration::from_secs(1));
+        assert_eq!(line, "[1/4] 25% (250 of 1000 bytes) ETA 3s");
+        // No throughput yet -> no ETA; empty totals count as complete.
+        assert_eq!(
+            format_progress(0, 2, 0, 100, Duration::ZERO),
+            "[0/2] 0% (0 of 100 bytes)"
+        );
+        assert_eq!(
+            format_progress(1, 1, 0, 0, Duration::ZERO),
+            "[1/1] 100% (0 of 0 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_has_host_exe_magic() {
+        let temp = tempfile::tempdir().unwrap();
+        let native = temp.path().join("native");
+        let mut bytes = HOST_EXE_MAGIC[0].to_vec();
+        bytes.extend_from_slice(&[0u8; 16]);
+        std::fs::write(&native, bytes).unwrap();
+        assert!(has_host_exe_magic(&native).unwrap());
+
+        let text = temp.path().join("text");
+        std::fs::write(&text, "#!/bin/sh\necho hi\n").unwrap();
+        assert!(!has_host_exe_magic(&text).unwrap());
+
+        let tiny = temp.path().join("tiny");
+        std::fs::write(&tiny, "M").unwrap();
+        assert!(!has_host_exe_magic(&tiny).unwrap());
+
+        assert!(has_host_exe_magic(&temp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_incremental_skip_reason() {
+        use std::time::{Duration, SystemTime};
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("src");
+        let target = temp.path().join("dst");
+        std::fs::write(&source, "x").unwrap();
+        let now = SystemTime::now();
+        let set = |path: &Path, t: SystemTime| {
+            fs::File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(t)
+                .unwrap();
+        };
+        set(&source, now);
+
+        // Missing destination is always copied.
+        assert_eq!(incremental_skip_reason(&source, &target, None, true), None);
+
+        std::fs::write(&target, "x").unwrap();
+        set(&target, now);
+        assert_eq!(
+            incremental_skip_reason(&source, &target, None, true),
+            Some("destination is up to date")
+        );
+        assert_eq!(incremental_skip_reason(&source, &target, None, false), None);
+
+        set(&target, now - Duration::from_secs(60));
+        assert_eq!(incremental_skip_reason(&source, &target, None, true), None);
+        assert_eq!(
+            incremental_skip_reason(&source, &target, Some(now), false),
+            Some("not modified since last deploy")
+        );
+    }
+
+    #[test]
+    fn test_replace_in_place_swaps_contents() {
+        let temp = tempfile::tempdir().unwrap();
+        let source = temp.path().join("new");
+        let target = temp.path().join("tool");
+        std::fs::write(&source, "new").unwrap();
+        std::fs::write(&target, "old").unwrap();
+        replace_in_place(&source, &target).unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+        assert!(!temp.path().join(".tool.mdrcp-new").exists());
+
+        // A missing source leaves the target untouched.
+        let err = replace_in_place(&temp.path().join("absent"), &target);
+        assert!(err.is_err());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_relative_to_strips_project_prefix() {
+        let temp = tempfile::tempdir().unwrap();
+        let rel = temp.path().join("target").join("release");
+        std::fs::create_dir_all(&rel).unwrap();
+        std::fs::write(rel.join("foo"), "x").unwrap();
+        assert_eq!(
+            relative_to(&rel.join("foo"), temp.path()),
+            Path::new("target").join("release").join("foo")
+        );
+
+        let elsewhere = tempfile::tempdir().unwrap();
+        assert_eq!(
+            relative_to(elsewhere.path(), temp.path()),
+            elsewhere.path().canonicalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_env_file_parses_pairs() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(".env");
+        std::fs::write(
+            &path,
+            "# deploy env\nMD_TARGET_DIR=/opt/bin\n\nexport HOME = \"/home/ci\"\nEMPTY=\n",
+        )
+        .unwrap();
+        let pairs = read_env_file(&path).unwrap();
+        assert_eq!(
+            pairs,
+            vec![
+                ("MD_TARGET_DIR".to_string(), "/opt/bin".to_string()),
+                ("HOME".to_string(), "/home/ci".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+
+        std::fs::write(&path, "OK=1\nnot a pair\n").unwrap();
+        let err = read_env_file(&path).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_cli_context_env_overrides_process_env() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        let mut ctx = CliContext::new(&mut out, &mut err);
+        ctx.env
+            .insert("MDRCP_TEST_ONLY_VAR".to_string(), OsString::from("set"));
+        assert_eq!(
+            ctx.var_os("MDRCP_TEST_ONLY_VAR"),
+            Some(OsString::from("set"))
+        );
+        assert_eq!(ctx.var_os("MDRCP_TEST_ONLY_UNSET"), None);
+    }
+
     #[test]
     fn test_write_error_paths() {
         struct FailingWriter;
@@ -1260,4 +6682,25 @@ mod tests {
         .unwrap();
         assert!(read_tauri_product_name(root).is_none());
     }
+
+    #[test]
+    fn test_interrupted_report_is_a_failure() {
+        let report = DeploymentReport {
+            copied: vec!["alpha".into()],
+            interrupted: vec!["beta".into()],
+            ..Default::default()
+        };
+        assert_eq!(report.status(), "partial");
+        let err = DeployError::Failed(Box::new(report.clone()));
+        assert_eq!(err.exit_code(), interrupt::EXIT_CODE);
+        assert_eq!(err.to_string(), "Interrupted; 1 binary not deployed");
+
+        let multi = DeploymentReport {
+            extra_targets: vec![report, DeploymentReport::default()],
+            ..Default::default()
+        };
+        let err = DeployError::Failed(Box::new(multi));
+        assert_eq!(err.exit_code(), interrupt::EXIT_CODE);
+        assert_eq!(err.to_string(), "Deploy failed for 1 of 3 targets");
+    }
 }
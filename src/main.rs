@@ -19,16 +19,73 @@ fn main() {
             let _ = mdrcp::write_help(&mut stdout);
             process::exit(0);
         }
-        Ok(mdrcp::Command::ShowVersion) => {
-            let _ = mdrcp::write_version_banner(&mut stdout);
+        Ok(mdrcp::Command::ShowVersion { timestamp_format }) => {
+            let _ = mdrcp::write_version_banner_with(&mut stdout, timestamp_format);
             process::exit(0);
         }
-        Ok(mdrcp::Command::Deploy(options)) => {
-            if !options.quiet {
+        Ok(mdrcp::Command::ShowVersionShort) => {
+            let _ = mdrcp::write_version_short(&mut stdout);
+            process::exit(0);
+        }
+        Ok(mdrcp::Command::Deploy(mut options)) => {
+            mdrcp::interrupt::install();
+            // Annotate by default inside GitHub Actions unless --format says otherwise.
+            if options.ci_format.is_none()
+                && std::env::var_os("GITHUB_ACTIONS").is_some_and(|v| v == "true")
+            {
+                options.ci_format = Some(mdrcp::CiFormat::Github);
+            }
+            // Honour `quiet` from the project config before the banner prints.
+            let quiet = mdrcp::config::apply_config(Path::new("."), &options)
+                .map_or(options.quiet, |merged| merged.quiet);
+            if !quiet && !options.no_banner {
                 let _ = mdrcp::write_deploy_banner(&mut stdout);
             }
             process::exit(mdrcp::do_main_with_options(Path::new("."), &options));
         }
+        Ok(mdrcp::Command::Init { force }) => match mdrcp::init_config(Path::new("."), force) {
+            Ok(path) => {
+                println!("Created {}", path.display());
+                process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        },
+        Ok(mdrcp::Command::Query { path }) => {
+            match mdrcp::provenance::write_query(&mut stdout, &path) {
+                Ok(true) => process::exit(0),
+                _ => process::exit(1),
+            }
+        }
+        Ok(mdrcp::Command::MergeSummaries { files, pretty }) => {
+            let format = if pretty {
+                mdrcp::SummaryFormat::JsonPretty
+            } else {
+                mdrcp::SummaryFormat::Json
+            };
+            match mdrcp::write_merged_summaries(&mut stdout, &files, format) {
+                Ok(()) => process::exit(0),
+                Err(e) => {
+                    eprintln!("Error: {:#}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Ok(mdrcp::Command::List(options)) => {
+            match mdrcp::write_binary_list(&mut stdout, Path::new("."), &options) {
+                Ok(()) => process::exit(0),
+                Err(e) => {
+                    eprintln!("Error: {:#}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Ok(mdrcp::Command::Completions(shell)) => {
+            let _ = mdrcp::print_completions(&mut stdout, shell);
+            process::exit(0);
+        }
         Ok(mdrcp::Command::FinishUpdate { source, dest }) => {
             process::exit(finish_update(&source, &dest));
         }
@@ -50,10 +107,18 @@ fn cleanup_old_updater() {
 }
 
 /// Perform the actual copy for a self-update, with retries.
-/// Called by the temp updater executable.
+/// Called by the temp updater executable once the deploying process exits,
+/// which closes the pipe on our stdin.
 fn finish_update(source: &Path, dest: &Path) -> i32 {
+    use std::io::IsTerminal;
     use std::{thread, time::Duration};
 
+    // Run by hand from a terminal, there is no parent to wait for.
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        let _ = std::io::copy(&mut stdin.lock(), &mut std::io::sink());
+    }
+
     const MAX_RETRIES: u32 = 10;
     const RETRY_DELAY_MS: u64 = 100;
 
@@ -0,0 +1,151 @@
+//! Markdown renderer for `--summary markdown`, sized for a PR comment.
+//!
+//! The deploy summary becomes a bold header line with the count, a
+//! `Binary | Status | Target` table with one row per binary (sorted by name),
+//! and a bulleted warnings section when there is anything to warn about.
+
+use std::path::Path;
+
+use crate::{BinaryList, DeploymentSummary};
+
+/// Render `summary` as Markdown (no trailing newline).
+pub(crate) fn deployment(summary: &DeploymentSummary) -> String {
+    let verb = if summary.dry_run {
+        "Would deploy"
+    } else {
+        "Deployed"
+    };
+    let mut out = format!(
+        "**{} {} executable(s) to `{}`** ({})\n",
+        verb,
+        summary.copied_count,
+        cell(&summary.target_dir),
+        summary.status
+    );
+    if let Some(reason) = &summary.aborted {
+        out.push_str(&format!("\nAborted: {}\n", cell(reason)));
+    }
+
+    let copied = if summary.dry_run {
+        "would copy"
+    } else {
+        "copied"
+    };
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    rows.extend(
+        summary
+            .copied_binaries
+            .iter()
+            .map(|name| (name.as_str(), copied.to_string())),
+    );
+    rows.extend(
+        summary
+            .skipped_binaries
+            .iter()
+            .map(|name| (name.as_str(), "skipped".to_string())),
+    );
+    rows.extend(
+        summary
+            .failed_binaries
+            .iter()
+            .map(|failed| (failed.binary.as_str(), format!("failed: {}", failed.error))),
+    );
+    rows.extend(
+        summary
+            .interrupted
+            .iter()
+            .map(|name| (name.as_str(), "interrupted".to_string())),
+    );
+    rows.sort();
+    if !rows.is_empty() {
+        out.push_str("\n| Binary | Status | Target |\n|--------|--------|--------|\n");
+        for (name, status) in rows {
+            let target = summary.destinations.get(name).cloned().unwrap_or_else(|| {
+                Path::new(&summary.target_dir)
+                    .join(name)
+                    .display()
+                    .to_string()
+            });
+            out.push_str(&format!(
+                "| {} | {} | `{}` |\n",
+                cell(name),
+                cell(&status),
+                cell(&target)
+            ));
+        }
+    }
+
+    if !summary.warnings.is_empty() {
+        out.push_str("\n**Warnings**\n\n");
+        for warning in &summary.warnings {
+            out.push_str(&format!(
+                "- {} (`{}`)\n",
+                cell(&warning.message),
+                warning.code.as_str()
+            ));
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Render `mdrcp list` as a `Binary | Status | Path` table (no trailing
+/// newline).
+pub(crate) fn binary_list(list: &BinaryList) -> String {
+    let mut out = String::from("| Binary | Status | Path |\n|--------|--------|------|");
+    for binary in &list.binaries {
+        out.push_str(&format!(
+            "\n| {} | {} | `{}` |",
+            cell(&binary.name),
+            if binary.exists { "built" } else { "not built" },
+            cell(&binary.path)
+        ));
+    }
+    out
+}
+
+/// Text safe inside a table cell: pipes escaped, line breaks flattened.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deployment_table_layout() {
+        let summary: DeploymentSummary = serde_json::from_value(json!({
+            "status": "partial",
+            "copied_count": 1,
+            "target_dir": "/opt/bin",
+            "override_used": true,
+            "host": {"os": "linux", "arch": "x86_64"},
+            "copied_binaries": ["beta"],
+            "skipped_binaries": ["alpha"],
+            "failed_binaries": [{"binary": "gamma", "error": "disk | full\nretry"}],
+            "warnings": [{"code": "not_on_path", "message": "/opt/bin is not on PATH"}]
+        }))
+        .unwrap();
+        let target = |name: &str| Path::new("/opt/bin").join(name).display().to_string();
+        let expected = [
+            "**Deployed 1 executable(s) to `/opt/bin`** (partial)".to_string(),
+            String::new(),
+            "| Binary | Status | Target |".to_string(),
+            "|--------|--------|--------|".to_string(),
+            format!("| alpha | skipped | `{}` |", target("alpha")),
+            format!("| beta | copied | `{}` |", target("beta")),
+            format!(
+                "| gamma | failed: disk \\| full retry | `{}` |",
+                target("gamma")
+            ),
+            String::new(),
+            "**Warnings**".to_string(),
+            String::new(),
+            "- /opt/bin is not on PATH (`not_on_path`)".to_string(),
+        ]
+        .join("\n");
+        assert_eq!(deployment(&summary), expected);
+    }
+}
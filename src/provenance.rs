@@ -0,0 +1,236 @@
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extended attribute recording the project a binary was deployed from.
+pub const XATTR_SOURCE: &str = "user.mdrcp.source";
+/// Extended attribute recording the mdrcp version that deployed a binary.
+pub const XATTR_VERSION: &str = "user.mdrcp.version";
+
+/// Suffix appended to a deployed file's name for its provenance sidecar.
+pub const SIDECAR_SUFFIX: &str = ".mdrcp.json";
+
+/// Where and how a deployed binary was produced. Every field is optional so a
+/// record can be assembled from a sidecar, xattrs, or both.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mdrcp_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_id: Option<String>,
+}
+
+impl Provenance {
+    fn is_empty(&self) -> bool {
+        *self == Provenance::default()
+    }
+}
+
+/// Sidecar path for a deployed file: `<file name>.mdrcp.json` beside it.
+pub fn sidecar_path(deployed: &Path) -> PathBuf {
+    let mut name = deployed.file_name().unwrap_or_default().to_os_string();
+    name.push(SIDECAR_SUFFIX);
+    deployed.with_file_name(name)
+}
+
+/// Write `record` as pretty JSON into the sidecar next to `deployed`.
+pub fn write_sidecar(deployed: &Path, record: &Provenance) -> Result<()> {
+    let path = sidecar_path(deployed);
+    let json = serde_json::to_string_pretty(record).context("Failed to serialize provenance")?;
+    fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Collect provenance for `deployed` from its sidecar, filling any gaps from
+/// xattrs. Returns `None` when neither source holds anything.
+pub fn read_provenance(deployed: &Path) -> Option<Provenance> {
+    let mut record = fs::read_to_string(sidecar_path(deployed))
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Provenance>(&raw).ok())
+        .unwrap_or_default();
+    if record.source.is_none() {
+        record.source = read_xattr(deployed, XATTR_SOURCE);
+    }
+    if record.mdrcp_version.is_none() {
+        record.mdrcp_version = read_xattr(deployed, XATTR_VERSION);
+    }
+    (!record.is_empty()).then_some(record)
+}
+
+/// Print the provenance of `deployed`. Returns `false` (after saying so) when
+/// no mdrcp provenance was found.
+pub fn write_query(writer: &mut impl std::io::Write, deployed: &Path) -> std::io::Result<bool> {
+    let Some(record) = read_provenance(deployed) else {
+        writeln!(writer, "no mdrcp provenance for {}", deployed.display())?;
+        return Ok(false);
+    };
+    writeln!(
+        writer,
+        "{}",
+        deployed.display().to_string().bold().bright_white()
+    )?;
+    let fields = [
+        ("Source:", &record.source),
+        ("Binary version:", &record.binary_version),
+        ("Deployed by:", &record.mdrcp_version),
+        ("Deployed at:", &record.deployed_at),
+        ("SHA-256:", &record.sha256),
+        ("Deploy ID:", &record.deploy_id),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            writeln!(writer, "  {} {}", label.bold().cyan(), value)?;
+        }
+    }
+    Ok(true)
+}
+
+/// Lowercase hex SHA-256 of the file at `path`, streamed in chunks.
+pub fn sha256_hex(path: &Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Write a coreutils `sha256sum -c` compatible file: one `<hex>  <name>` line
+/// per file in `names`, hashed from `dir` (absolute names are used as-is).
+pub fn write_checksum_file(path: &Path, dir: &Path, names: &[String]) -> Result<()> {
+    let mut contents = String::new();
+    for name in names {
+        let digest = sha256_hex(&dir.join(name))
+            .with_context(|| format!("Failed to hash {}", dir.join(name).display()))?;
+        contents.push_str(&format!("{}  {}\n", digest, name));
+    }
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Tag a deployed file with its source project and the deploying mdrcp
+/// version. Fails on platforms or filesystems without extended attributes;
+/// callers downgrade that to a warning.
+#[cfg(unix)]
+pub fn tag_xattrs(deployed: &Path, source_project: &Path) -> Result<()> {
+    let source = source_project.display().to_string();
+    xattr::set(deployed, XATTR_SOURCE, source.as_bytes())?;
+    xattr::set(
+        deployed,
+        XATTR_VERSION,
+        env!("CARGO_PKG_VERSION").as_bytes(),
+    )?;
+    Ok(())
+}
+
+/// Read a UTF-8 extended attribute from `path`, or `None` when it is absent or
+/// xattrs are unavailable.
+#[cfg(unix)]
+pub fn read_xattr(path: &Path, name: &str) -> Option<String> {
+    let raw = xattr::get(path, name).ok()??;
+    String::from_utf8(raw).ok()
+}
+
+#[cfg(not(unix))]
+pub fn read_xattr(_path: &Path, _name: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(unix))]
+pub fn tag_xattrs(_deployed: &Path, _source_project: &Path) -> Result<()> {
+    anyhow::bail!("extended attributes are not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_path_appends_suffix() {
+        assert_eq!(
+            sidecar_path(Path::new("/bin/tool")),
+            PathBuf::from("/bin/tool.mdrcp.json")
+        );
+        assert_eq!(
+            sidecar_path(Path::new("apps/tool.exe")),
+            PathBuf::from("apps/tool.exe.mdrcp.json")
+        );
+    }
+
+    #[test]
+    fn test_sidecar_round_trip_and_query() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("tool");
+        std::fs::write(&file, "x").unwrap();
+        let record = Provenance {
+            source: Some("/src/project".to_string()),
+            binary_version: Some("1.2.3".to_string()),
+            deploy_id: Some("pipeline-42".to_string()),
+            ..Default::default()
+        };
+        write_sidecar(&file, &record).unwrap();
+        assert_eq!(read_provenance(&file).unwrap().source, record.source);
+
+        let mut out = Vec::new();
+        assert!(write_query(&mut out, &file).unwrap());
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("/src/project"));
+        assert!(out.contains("1.2.3"));
+        assert!(out.contains("pipeline-42"));
+    }
+
+    #[test]
+    fn test_query_without_provenance() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("stray");
+        std::fs::write(&file, "x").unwrap();
+        let mut out = Vec::new();
+        assert!(!write_query(&mut out, &file).unwrap());
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .starts_with("no mdrcp provenance for"));
+    }
+
+    #[test]
+    fn test_sha256_hex_known_digest() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("abc");
+        std::fs::write(&file, "abc").unwrap();
+        assert_eq!(
+            sha256_hex(&file).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_tag_xattrs_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("tool");
+        std::fs::write(&file, "x").unwrap();
+        if tag_xattrs(&file, Path::new("/src/project")).is_err() {
+            // tmp filesystem without user xattr support; nothing to verify.
+            return;
+        }
+        assert_eq!(
+            read_xattr(&file, XATTR_SOURCE).as_deref(),
+            Some("/src/project")
+        );
+        assert_eq!(
+            read_xattr(&file, XATTR_VERSION).as_deref(),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(read_xattr(&file, "user.mdrcp.absent"), None);
+    }
+}
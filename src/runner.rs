@@ -0,0 +1,63 @@
+//! External-process execution. Every tool mdrcp shells out to (such as `gpg`)
+//! goes through [`CommandRunner`] on the `CliContext`, so tests can swap in a
+//! mock that records invocations and returns canned output. Two exceptions:
+//! the self-update helper must outlive mdrcp, so it is spawned detached rather
+//! than run to completion; and under `--parallel-targets` each target deploys
+//! on its own thread with a fresh [`SystemRunner`], since the context's runner
+//! is not `Send`, so a mock there is never consulted.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Runs an external program to completion and captures its output.
+pub trait CommandRunner {
+    /// Run `program` with `args`; `env` is layered over the inherited
+    /// environment.
+    fn run(
+        &mut self,
+        program: &str,
+        args: &[OsString],
+        env: &HashMap<String, OsString>,
+    ) -> io::Result<Output>;
+
+    /// Run `program` with `dir` as its working directory. With `capture`
+    /// its output is collected as by [`CommandRunner::run`]; otherwise it
+    /// writes straight to this process's stdout and stderr and the returned
+    /// output buffers are empty.
+    fn run_in(
+        &mut self,
+        program: &str,
+        args: &[OsString],
+        env: &HashMap<String, OsString>,
+        dir: &Path,
+        capture: bool,
+    ) -> io::Result<Output> {
+        let mut command = Command::new(program);
+        command.args(args).envs(env).current_dir(dir);
+        if capture {
+            return command.output();
+        }
+        command.status().map(|status| Output {
+            status,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+        })
+    }
+}
+
+/// Default runner backed by `std::process::Command`.
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        args: &[OsString],
+        env: &HashMap<String, OsString>,
+    ) -> io::Result<Output> {
+        Command::new(program).args(args).envs(env).output()
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Record of the last deploy, kept inside the target directory.
+pub const TARGET_MANIFEST_FILE: &str = ".mdrcp-manifest.json";
+
+/// What mdrcp last deployed into a target directory, and when.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetManifest {
+    /// Human-readable UTC time the last successful deploy started.
+    #[serde(default)]
+    pub deployed_at: String,
+    /// The same instant as milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub deployed_at_unix_ms: u64,
+    /// Sorted executable file names present after that deploy.
+    #[serde(default)]
+    pub binaries: Vec<String>,
+    /// Where each binary was placed, by file name; `--dest` and
+    /// `--by-package` put some outside the target directory. Manifests
+    /// without an entry mean `<target>/<name>`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub destinations: BTreeMap<String, PathBuf>,
+    /// The project directory the deploy was run from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// Hex SHA-256 of each binary as deployed, by file name. `--prune` only
+    /// deletes a file that still matches.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, String>,
+}
+
+impl TargetManifest {
+    /// Build a record for a deploy that started at `started`.
+    pub fn new(started: SystemTime, deployed_at: String, mut binaries: Vec<String>) -> Self {
+        binaries.sort();
+        binaries.dedup();
+        let deployed_at_unix_ms = started
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Self {
+            deployed_at,
+            deployed_at_unix_ms,
+            binaries,
+            destinations: BTreeMap::new(),
+            project: None,
+            hashes: BTreeMap::new(),
+        }
+    }
+
+    /// Where `name` was deployed, given the manifest lives in `target_dir`.
+    pub fn destination(&self, target_dir: &Path, name: &str) -> PathBuf {
+        self.destinations
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| target_dir.join(name))
+    }
+
+    /// The recorded deploy start as a `SystemTime`.
+    pub fn deployed_at_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.deployed_at_unix_ms)
+    }
+}
+
+/// How a planned deploy differs from the one recorded in the target manifest.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestDiff {
+    /// Planned binaries the last deploy did not include.
+    pub added: Vec<String>,
+    /// Recorded binaries whose source changed after the last deploy.
+    pub updated: Vec<String>,
+    /// Recorded binaries whose source is no newer than the last deploy.
+    pub unchanged: Vec<String>,
+    /// Recorded binaries that are no longer part of the deploy.
+    pub removed: Vec<String>,
+}
+
+impl ManifestDiff {
+    /// Compare `planned` (file name plus source mtime, when known) against
+    /// `previous`. With no previous manifest every planned binary is added; a
+    /// source with an unknown mtime counts as updated.
+    pub fn between(
+        previous: Option<&TargetManifest>,
+        planned: &[(String, Option<SystemTime>)],
+    ) -> Self {
+        let mut diff = ManifestDiff::default();
+        let recorded: &[String] = previous.map(|m| m.binaries.as_slice()).unwrap_or(&[]);
+        let last_deploy = previous.map(TargetManifest::deployed_at_time);
+        for (name, mtime) in planned {
+            if !recorded.contains(name) {
+                diff.added.push(name.clone());
+            } else if matches!((mtime, last_deploy), (Some(m), Some(at)) if *m <= at) {
+                diff.unchanged.push(name.clone());
+            } else {
+                diff.updated.push(name.clone());
+            }
+        }
+        diff.removed = recorded
+            .iter()
+            .filter(|name| !planned.iter().any(|(planned, _)| planned == *name))
+            .cloned()
+            .collect();
+        for list in [
+            &mut diff.added,
+            &mut diff.updated,
+            &mut diff.unchanged,
+            &mut diff.removed,
+        ] {
+            list.sort();
+        }
+        diff
+    }
+}
+
+/// Load the manifest from `target_dir`; a missing or unreadable file is `None`.
+pub fn read_target_manifest(target_dir: &Path) -> Option<TargetManifest> {
+    let raw = fs::read_to_string(target_dir.join(TARGET_MANIFEST_FILE)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Persist `manifest` into `target_dir`.
+pub fn write_target_manifest(target_dir: &Path, manifest: &TargetManifest) -> Result<()> {
+    let path = target_dir.join(TARGET_MANIFEST_FILE);
+    let json =
+        serde_json::to_string_pretty(manifest).context("Failed to serialize target manifest")?;
+    fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_manifest_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let started = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let manifest = TargetManifest::new(
+            started,
+            "2023-11-14 22:13:20 UTC".to_string(),
+            vec!["b".to_string(), "a".to_string(), "b".to_string()],
+        );
+        assert_eq!(manifest.binaries, vec!["a", "b"]);
+        assert_eq!(manifest.deployed_at_time(), started);
+        assert_eq!(
+            manifest.destination(temp.path(), "a"),
+            temp.path().join("a")
+        );
+        let mut manifest = manifest;
+        manifest
+            .destinations
+            .insert("b".to_string(), PathBuf::from("/opt/tools/b"));
+        assert_eq!(
+            manifest.destination(temp.path(), "b"),
+            PathBuf::from("/opt/tools/b")
+        );
+
+        write_target_manifest(temp.path(), &manifest).unwrap();
+        assert_eq!(read_target_manifest(temp.path()), Some(manifest));
+    }
+
+    #[test]
+    fn test_manifest_diff_between() {
+        let at = UNIX_EPOCH + Duration::from_secs(1_000);
+        let previous = TargetManifest::new(
+            at,
+            String::new(),
+            vec!["kept".into(), "rebuilt".into(), "gone".into()],
+        );
+        let planned = vec![
+            ("rebuilt".to_string(), Some(at + Duration::from_secs(5))),
+            ("kept".to_string(), Some(at - Duration::from_secs(5))),
+            ("fresh".to_string(), Some(at)),
+        ];
+        let diff = ManifestDiff::between(Some(&previous), &planned);
+        assert_eq!(diff.added, vec!["fresh"]);
+        assert_eq!(diff.updated, vec!["rebuilt"]);
+        assert_eq!(diff.unchanged, vec!["kept"]);
+        assert_eq!(diff.removed, vec!["gone"]);
+
+        let first = ManifestDiff::between(None, &planned);
+        assert_eq!(first.added, vec!["fresh", "kept", "rebuilt"]);
+        assert!(first.removed.is_empty());
+    }
+
+    #[test]
+    fn test_read_target_manifest_missing_or_invalid() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(read_target_manifest(temp.path()), None);
+        fs::write(temp.path().join(TARGET_MANIFEST_FILE), "{ nope").unwrap();
+        assert_eq!(read_target_manifest(temp.path()), None);
+    }
+}
@@ -0,0 +1,148 @@
+//! Minimal YAML emitter for `--summary yaml`.
+//!
+//! The summary is first converted to a `serde_json::Value`, then written in
+//! block style. Strings are always double-quoted using JSON escaping, which is
+//! valid YAML, so no value can be misread as a number, boolean, or null.
+
+use serde_json::Value;
+
+/// Render `value` as a YAML document (no leading `---`, no trailing newline).
+pub(crate) fn to_yaml(value: &Value) -> String {
+    let mut out = String::new();
+    match value {
+        Value::Object(map) if !map.is_empty() => write_mapping(&mut out, map, 0),
+        Value::Array(items) if !items.is_empty() => write_sequence(&mut out, items, 0),
+        scalar => out.push_str(&scalar_text(scalar)),
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Scalars and empty collections, which fit on the key's line.
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => true,
+    }
+}
+
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => Value::String(s.clone()).to_string(),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+    }
+}
+
+/// Keys stay bare when they are plain identifiers; anything else (such as a
+/// file name with spaces in `destinations`) is quoted.
+fn key_text(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        && key.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+    if plain {
+        key.to_string()
+    } else {
+        Value::String(key.to_string()).to_string()
+    }
+}
+
+fn write_mapping(out: &mut String, map: &serde_json::Map<String, Value>, indent: usize) {
+    let pad = " ".repeat(indent);
+    for (key, value) in map {
+        out.push_str(&pad);
+        out.push_str(&key_text(key));
+        out.push(':');
+        write_value(out, value, indent + 2);
+    }
+}
+
+fn write_sequence(out: &mut String, items: &[Value], indent: usize) {
+    let pad = " ".repeat(indent);
+    for item in items {
+        if is_inline(item) {
+            out.push_str(&format!("{}- {}\n", pad, scalar_text(item)));
+            continue;
+        }
+        // Render the nested block two deeper, then fold its first line onto
+        // the dash: "  key: v" becomes "- key: v".
+        let mut nested = String::new();
+        match item {
+            Value::Object(map) => write_mapping(&mut nested, map, indent + 2),
+            Value::Array(items) => write_sequence(&mut nested, items, indent + 2),
+            _ => unreachable!(),
+        }
+        out.push_str(&pad);
+        out.push_str("- ");
+        out.push_str(&nested[indent + 2..]);
+    }
+}
+
+/// The rest of a `key:` line: the inline value, or a newline and the block.
+fn write_value(out: &mut String, value: &Value, indent: usize) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push('\n');
+            write_mapping(out, map, indent);
+        }
+        Value::Array(items) if !items.is_empty() => {
+            out.push('\n');
+            write_sequence(out, items, indent);
+        }
+        scalar => {
+            out.push(' ');
+            out.push_str(&scalar_text(scalar));
+            out.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_yaml_block_layout() {
+        let value = json!({
+            "status": "ok",
+            "copied_count": 2,
+            "override_used": false,
+            "copied_binaries": ["a", "b"],
+            "failed_binaries": [{"binary": "c", "error": "no: \"space\""}],
+            "warnings": [],
+            "host": {"os": "linux"},
+            "destinations": {"my tool": "/opt/my tool"}
+        });
+        let expected = [
+            "copied_binaries:",
+            "  - \"a\"",
+            "  - \"b\"",
+            "copied_count: 2",
+            "destinations:",
+            "  \"my tool\": \"/opt/my tool\"",
+            "failed_binaries:",
+            "  - binary: \"c\"",
+            "    error: \"no: \\\"space\\\"\"",
+            "host:",
+            "  os: \"linux\"",
+            "override_used: false",
+            "status: \"ok\"",
+            "warnings: []",
+        ]
+        .join("\n");
+        assert_eq!(to_yaml(&value), expected);
+    }
+
+    #[test]
+    fn test_to_yaml_nested_sequences() {
+        let value = json!({"groups": [["a"], [], {"k": []}]});
+        assert_eq!(to_yaml(&value), "groups:\n  - - \"a\"\n  - []\n  - k: []");
+    }
+}
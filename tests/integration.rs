@@ -1,6 +1,6 @@
 use mdrcp::{
-    do_main, exe_filename, run, run_with_options, BuildProfile, ProjectType, RunOptions,
-    SummaryFormat,
+    do_main, exe_filename, run, run_with_options, BuildProfile, CiFormat, ConflictPolicy,
+    FreshnessCheck, LinkMode, ProjectType, RunOptions, SummaryFormat, WarningCode,
 };
 use serde_json::Value;
 use std::ffi::OsString;
@@ -784,6 +784,7 @@ fn test_run_with_summary_json_quiet() {
         quiet: true,
         summary: SummaryFormat::Json,
         target_override: Some(PathBuf::from("dist/bin")),
+        suppress_warnings: vec![WarningCode::NotOnPath],
         ..Default::default()
     };
 
@@ -834,12 +835,16 @@ fn test_run_with_summary_json_pretty() {
     std::env::set_var("HOME", tmp_home.path());
     let output = std::process::Command::new(bin)
         .current_dir(temp_project.path())
+        // Inside GitHub Actions the binary would add annotations to stderr.
+        .env_remove("GITHUB_ACTIONS")
         .args([
             "--quiet",
             "--summary",
             "json-pretty",
             "--target",
             "dist/bin",
+            "--suppress-warning",
+            "not_on_path",
         ])
         .output()
         .unwrap();
@@ -974,7 +979,7 @@ fn test_do_main_error_hint_home_missing_integration() {
     let tmp = tempdir().unwrap();
     let old = std::env::var_os("HOME");
     std::env::remove_var("HOME");
-    assert_eq!(do_main(tmp.path()), 1);
+    assert_eq!(do_main(tmp.path()), 2);
     match old {
         Some(v) => std::env::set_var("HOME", v),
         None => std::env::remove_var("HOME"),
@@ -1042,9 +1047,9 @@ fn test_target_dir_creation_failure() {
 
 #[test]
 fn test_do_main_error_and_success() {
-    // error path
+    // error path: not a Rust project
     let temp_dir = tempdir().unwrap();
-    assert_eq!(do_main(temp_dir.path()), 1);
+    assert_eq!(do_main(temp_dir.path()), 2);
 
     // success path
     let _guard = ENV_MUTEX.get_or_init(|| Mutex::new(())).lock().unwrap();
@@ -1324,3 +1329,4987 @@ fn test_tauri_not_detected_without_tauri_conf() {
     assert!(result.is_ok(), "Standard deploy failed: {:?}", result);
     assert!(target_dir.path().join(&exe).exists());
 }
+
+#[test]
+fn test_report_unbuilt_lists_missing_workspace_bin() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"server\",\"worker\"]",
+    )
+    .unwrap();
+    for m in ["server", "worker"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+        create_and_write_file(
+            &temp_dir.path().join(m).join("Cargo.toml"),
+            &format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"", m),
+        )
+        .unwrap();
+    }
+    // Only `server` was built; `worker` failed to build.
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("server")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        report_unbuilt: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["copied_count"], 1);
+    assert_eq!(json["unbuilt"], serde_json::json!(["worker"]));
+}
+
+#[test]
+fn test_unbuilt_omitted_without_flag() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"helper\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert!(json.get("unbuilt").is_none());
+}
+
+#[test]
+fn test_json_pretty_identical_across_runs() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"zeta\",\"alpha\",\"mid\"]",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["zeta", "alpha", "mid"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+        create_and_write_file(
+            &temp_dir.path().join(m).join("Cargo.toml"),
+            &format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"", m),
+        )
+        .unwrap();
+        create_and_write_file(&rel.join(exe_filename(m)), "x").unwrap();
+    }
+
+    let options = RunOptions {
+        summary: SummaryFormat::JsonPretty,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let mut runs = Vec::new();
+    for _ in 0..2 {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        runs.push(String::from_utf8(stdout).unwrap());
+    }
+    assert_eq!(runs[0], runs[1]);
+
+    let json: Value = serde_json::from_str(&runs[0]).unwrap();
+    let expected: Vec<String> = ["alpha", "mid", "zeta"]
+        .iter()
+        .map(|n| exe_filename(n))
+        .collect();
+    assert_eq!(json["copied_binaries"], serde_json::json!(expected));
+}
+
+#[test]
+fn test_expect_count_mismatch_warns() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        expect_count: Some(2),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let stderr = String::from_utf8(stderr).unwrap();
+    assert!(stderr.contains("Expected 2 executable(s) to deploy but found 1"));
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["copied_count"], 1);
+    assert_eq!(json["warnings"][0]["code"], "expect_count_mismatch");
+    assert!(json["warnings"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Expected 2 executable(s)"));
+}
+
+#[test]
+fn test_expect_count_mismatch_strict_errors() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        expect_count: Some(3),
+        strict: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("Expected 3 executable(s)"));
+    assert!(!temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("demo"))
+        .exists());
+}
+
+#[test]
+fn test_mdrcpignore_skips_matching_binaries() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"server\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"internal-tool\"",
+    )
+    .unwrap();
+    create_and_write_file(
+        &temp_dir.path().join(".mdrcpignore"),
+        "# never ship internal helpers\ninternal-*\n",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["server", "internal-tool"] {
+        create_and_write_file(&rel.join(exe_filename(name)), "x").unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join(exe_filename("server")).exists());
+    assert!(!dist.join(exe_filename("internal-tool")).exists());
+}
+
+#[test]
+fn test_init_command_creates_config_and_refuses_overwrite() {
+    let temp_project = tempdir().unwrap();
+    let bin = env!("CARGO_BIN_EXE_mdrcp");
+
+    let first = std::process::Command::new(bin)
+        .current_dir(temp_project.path())
+        .arg("init")
+        .output()
+        .unwrap();
+    assert!(first.status.success());
+    assert!(temp_project.path().join("mdrcp.toml").exists());
+
+    let second = std::process::Command::new(bin)
+        .current_dir(temp_project.path())
+        .arg("init")
+        .output()
+        .unwrap();
+    assert!(!second.status.success());
+    assert!(String::from_utf8_lossy(&second.stderr).contains("already exists"));
+
+    let forced = std::process::Command::new(bin)
+        .current_dir(temp_project.path())
+        .args(["init", "--force"])
+        .output()
+        .unwrap();
+    assert!(forced.status.success());
+}
+
+#[test]
+fn test_no_banner_keeps_copy_output() {
+    let temp_project = tempdir().unwrap();
+    create_and_write_file(
+        &temp_project.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_project.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let bin = env!("CARGO_BIN_EXE_mdrcp");
+    let output = std::process::Command::new(bin)
+        .current_dir(temp_project.path())
+        .args(["--no-banner", "--target", "dist/bin"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Copied"));
+    assert!(!stdout.contains(env!("CARGO_PKG_VERSION")));
+}
+
+#[test]
+fn test_version_short_prints_bare_semver() {
+    let bin = env!("CARGO_BIN_EXE_mdrcp");
+    let output = std::process::Command::new(bin)
+        .args(["--version", "--short"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_tag_xattr_records_provenance_or_warns() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        tag_xattr: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let deployed = temp_dir.path().join("dist").join(exe_filename("demo"));
+    assert!(deployed.exists());
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    let warned = json["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|w| w["code"] == "xattr_failed");
+    if !warned {
+        let version = mdrcp::provenance::read_xattr(&deployed, mdrcp::provenance::XATTR_VERSION);
+        assert_eq!(version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+        let source = mdrcp::provenance::read_xattr(&deployed, mdrcp::provenance::XATTR_SOURCE);
+        assert_eq!(
+            source.map(PathBuf::from),
+            Some(temp_dir.path().canonicalize().unwrap())
+        );
+    }
+}
+
+#[test]
+fn test_query_reports_sidecar_provenance() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"2.3.4\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let bin = env!("CARGO_BIN_EXE_mdrcp");
+    let deploy = std::process::Command::new(bin)
+        .current_dir(temp_dir.path())
+        .args(["--quiet", "--sidecar", "--target", "dist"])
+        .output()
+        .unwrap();
+    assert!(deploy.status.success());
+
+    let deployed = temp_dir.path().join("dist").join(exe_filename("demo"));
+    let query = std::process::Command::new(bin)
+        .arg("query")
+        .arg(&deployed)
+        .output()
+        .unwrap();
+    assert!(query.status.success());
+    let stdout = String::from_utf8(query.stdout).unwrap();
+    let project = temp_dir.path().canonicalize().unwrap();
+    assert!(stdout.contains(&project.display().to_string()));
+    assert!(stdout.contains("2.3.4"));
+    let record = mdrcp::provenance::read_provenance(&deployed).unwrap();
+    assert_eq!(
+        record.sha256.as_deref(),
+        Some("2d711642b726b04401627ca9fbac32f5c8530fb1903cc4db02258717921a4881")
+    );
+    assert!(stdout.contains("SHA-256:"), "{stdout}");
+
+    let missing = std::process::Command::new(bin)
+        .arg("query")
+        .arg(rel.join(exe_filename("demo")))
+        .output()
+        .unwrap();
+    assert!(!missing.status.success());
+    assert!(String::from_utf8_lossy(&missing.stdout).contains("no mdrcp provenance for"));
+}
+
+#[test]
+fn test_since_last_deploy_copies_only_touched_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"server\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"client\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let old = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    for name in ["server", "client"] {
+        let path = rel.join(exe_filename(name));
+        create_and_write_file(&path, "v1").unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+    }
+
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        since_last_deploy: true,
+        ..Default::default()
+    };
+    let deploy = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    // First run: no manifest yet, so everything is copied and recorded.
+    let first = deploy();
+    assert_eq!(first["copied_count"], 2);
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(".mdrcp-manifest.json")
+        .exists());
+
+    // Rebuild only the client.
+    let client = rel.join(exe_filename("client"));
+    create_and_write_file(&client, "v2").unwrap();
+    File::options()
+        .write(true)
+        .open(&client)
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(5))
+        .unwrap();
+
+    let second = deploy();
+    assert_eq!(second["copied_count"], 1);
+    assert_eq!(
+        second["copied_binaries"],
+        serde_json::json!([exe_filename("client")])
+    );
+    assert_eq!(
+        second["skipped_binaries"],
+        serde_json::json!([exe_filename("server")])
+    );
+}
+
+#[test]
+fn test_verify_format_rejects_non_executable() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "just some notes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        verify_format: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+    assert!(result.is_err());
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["status"], "failed");
+    assert!(json["failed_binaries"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("not a native executable"));
+    assert!(!temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("demo"))
+        .exists());
+}
+
+#[test]
+fn test_copy_if_newer_second_run_copies_nothing() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        copy_if_newer: true,
+        ..Default::default()
+    };
+    let deploy = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    assert_eq!(deploy()["copied_count"], 1);
+    let second = deploy();
+    assert_eq!(second["copied_count"], 0);
+    assert_eq!(
+        second["skipped_binaries"],
+        serde_json::json!([exe_filename("demo")])
+    );
+}
+
+#[test]
+fn test_profile_dir_overrides_source_directory() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    // Built somewhere unusual; target/release stays empty.
+    let custom = temp_dir.path().join("build-output").join("x86");
+    fs::create_dir_all(&custom).unwrap();
+    create_and_write_file(&custom.join(exe_filename("demo")), "custom").unwrap();
+    create_and_write_file(&custom.join(exe_filename("stray")), "ignored").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        profile_dir: Some(PathBuf::from("build-output/x86")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("demo"))).unwrap(),
+        "custom"
+    );
+    // Only manifest-declared names are deployed.
+    assert!(!dist.join(exe_filename("stray")).exists());
+}
+
+#[test]
+fn test_json_summary_includes_host() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        quiet: true,
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["host"]["os"], std::env::consts::OS);
+    assert_eq!(json["host"]["arch"], std::env::consts::ARCH);
+}
+
+#[test]
+fn test_plan_relativizes_sources() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"foo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("foo")), "x").unwrap();
+
+    let plan = |relativize_sources: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            plan: true,
+            relativize_sources,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        String::from_utf8(stdout).unwrap()
+    };
+
+    let relative = Path::new("target")
+        .join("release")
+        .join(exe_filename("foo"))
+        .display()
+        .to_string();
+    let absolute = temp_dir
+        .path()
+        .canonicalize()
+        .unwrap()
+        .join(&relative)
+        .display()
+        .to_string();
+
+    let out = plan(true);
+    assert!(out.contains(&format!("{} ->", relative)));
+    assert!(!out.contains(&absolute));
+    assert!(plan(false).contains(&absolute));
+    // Planning never creates or writes the target.
+    assert!(!temp_dir.path().join("dist").exists());
+
+    // --print-sources lists the same relative path, in text and JSON.
+    let deploy = |summary: SummaryFormat| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            print_sources: true,
+            relativize_sources: true,
+            summary,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        String::from_utf8(stdout).unwrap()
+    };
+    let out = deploy(SummaryFormat::Text);
+    assert!(out.contains(&format!("<- {}", relative)), "{out}");
+    let json: Value = serde_json::from_str(&deploy(SummaryFormat::Json)).unwrap();
+    assert_eq!(json["copied"][0]["source"], relative);
+}
+
+#[test]
+fn test_env_file_drives_target_directory() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let dest = temp_dir.path().join("from-env");
+    create_and_write_file(
+        &temp_dir.path().join("deploy.env"),
+        &format!(
+            "# pinned destination\n{}={}\n",
+            TARGET_OVERRIDE_ENV,
+            dest.display()
+        ),
+    )
+    .unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        env_file: Some(PathBuf::from("deploy.env")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    assert!(dest.join(exe_filename("demo")).exists());
+}
+
+#[test]
+fn test_max_file_size_skips_oversized_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"small\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"small\"\n\n[[bin]]\nname=\"huge\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("small")), "tiny").unwrap();
+    create_and_write_file(&rel.join(exe_filename("huge")), &"x".repeat(2048)).unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        max_file_size: Some(1024),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join(exe_filename("small")).exists());
+    assert!(!dist.join(exe_filename("huge")).exists());
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["copied_count"], 1);
+    assert_eq!(json["skipped_binaries"][0], exe_filename("huge"));
+    assert_eq!(json["warnings"][0]["code"], "oversized_binary");
+    assert!(json["warnings"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("exceeds --max-file-size of 1024 bytes"));
+
+    // Under --strict the oversized binary fails the run before anything is copied.
+    let strict = RunOptions {
+        target_override: Some(PathBuf::from("strict-dist")),
+        max_file_size: Some(1024),
+        strict: true,
+        ..Default::default()
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &strict, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("--strict"));
+    assert!(!temp_dir.path().join("strict-dist").exists());
+}
+
+#[test]
+fn test_min_file_size_rejects_empty_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"good\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"good\"\n\n[[bin]]\nname=\"stub\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("good")), "binary").unwrap();
+    fs::File::create(rel.join(exe_filename("stub"))).unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    assert!(run_with_options(temp_dir.path(), &options, &mut ctx).is_err());
+
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join(exe_filename("good")).exists());
+    assert!(!dist.join(exe_filename("stub")).exists());
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["failed_binaries"][0]["binary"], exe_filename("stub"));
+    assert_eq!(
+        json["failed_binaries"][0]["error"],
+        "binary `stub` is suspiciously small (0 bytes)"
+    );
+}
+
+#[test]
+fn test_redundant_target_warning_has_code() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let dest = temp_dir.path().join("dist");
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(dest.clone()),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.env.insert(
+        TARGET_OVERRIDE_ENV.to_string(),
+        dest.clone().into_os_string(),
+    );
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["warnings"][0]["code"], "redundant_target");
+    assert!(json["warnings"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("override may be redundant"));
+}
+
+#[test]
+fn test_suppress_warning_removes_not_on_path() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let run_json = |suppress_warnings: Vec<WarningCode>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            summary: SummaryFormat::Json,
+            target_override: Some(PathBuf::from("dist")),
+            suppress_warnings,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env
+            .insert("PATH".to_string(), temp_dir.path().join("elsewhere").into());
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        let json: Value = serde_json::from_slice(&stdout).unwrap();
+        (json, String::from_utf8(stderr).unwrap())
+    };
+
+    let (json, stderr) = run_json(Vec::new());
+    assert_eq!(json["warnings"][0]["code"], "not_on_path");
+    assert!(stderr.contains("is not on PATH"));
+
+    let (json, stderr) = run_json(vec![WarningCode::NotOnPath]);
+    assert!(json["warnings"].as_array().unwrap().is_empty());
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn test_dry_run_reports_removed_binary_from_manifest() {
+    use mdrcp::target_manifest::{write_target_manifest, TargetManifest};
+
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    let previous = TargetManifest::new(
+        std::time::SystemTime::now() + std::time::Duration::from_secs(60),
+        String::new(),
+        vec![exe_filename("demo"), exe_filename("retired")],
+    );
+    write_target_manifest(&dist, &previous).unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        dry_run: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["copied_count"], 1);
+    assert_eq!(json["copied_binaries"][0], exe_filename("demo"));
+    assert_eq!(json["changes"]["removed"][0], exe_filename("retired"));
+    assert_eq!(json["changes"]["unchanged"][0], exe_filename("demo"));
+    assert!(!dist.join(exe_filename("demo")).exists());
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let text = RunOptions {
+        summary: SummaryFormat::Text,
+        ..options
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &text, &mut ctx).unwrap();
+    let stdout = String::from_utf8(stdout).unwrap();
+    assert!(stdout.contains("Would remove"));
+    assert!(stdout.contains("retired"));
+}
+
+#[test]
+fn test_dry_run_after_plain_deploy_compares_with_it() {
+    let temp_dir = tempdir().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    create_and_write_file(
+        &manifest,
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"demo\"\npath=\"src/main.rs\"\n\n[[bin]]\nname=\"retired\"\npath=\"src/retired.rs\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+    create_and_write_file(&rel.join(exe_filename("retired")), "y").unwrap();
+    // Built well before the deploy, so the record's millisecond timestamp
+    // cannot round below the build.
+    File::options()
+        .write(true)
+        .open(rel.join(exe_filename("demo")))
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600))
+        .unwrap();
+
+    let deploy = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &deploy, &mut ctx).unwrap();
+
+    // Nothing asked for --since-last-deploy or --prune, yet the deploy is recorded.
+    let dist = temp_dir.path().join("dist");
+    assert!(dist
+        .join(mdrcp::target_manifest::TARGET_MANIFEST_FILE)
+        .exists());
+
+    fs::write(
+        &manifest,
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"demo\"\npath=\"src/main.rs\"",
+    )
+    .unwrap();
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let dry_run = RunOptions {
+        summary: SummaryFormat::Json,
+        dry_run: true,
+        ..deploy
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &dry_run, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        json["changes"]["unchanged"],
+        serde_json::json!([exe_filename("demo")])
+    );
+    assert_eq!(
+        json["changes"]["removed"],
+        serde_json::json!([exe_filename("retired")])
+    );
+}
+
+#[test]
+fn test_deploy_id_in_json_summary_and_sidecar() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        sidecar: true,
+        deploy_id: Some("pipeline-run-981".to_string()),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["deploy_id"], "pipeline-run-981");
+    let deployed = temp_dir.path().join("dist").join(exe_filename("demo"));
+    let record = mdrcp::provenance::read_provenance(&deployed).unwrap();
+    assert_eq!(record.deploy_id.as_deref(), Some("pipeline-run-981"));
+}
+
+#[test]
+fn test_deploy_returns_report_matching_disk() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "a").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "b").unwrap();
+
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        ..Default::default()
+    };
+    let report = mdrcp::deploy(temp_dir.path(), &options).unwrap();
+
+    // Beside the binaries, even a plain deploy records itself in the manifest.
+    let mut on_disk: Vec<String> = fs::read_dir(&report.target_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name != ".mdrcp-manifest.json")
+        .collect();
+    on_disk.sort();
+    assert_eq!(report.copied, on_disk);
+    let manifest = mdrcp::target_manifest::read_target_manifest(&report.target_dir).unwrap();
+    assert_eq!(manifest.binaries, report.copied);
+    assert_eq!(report.status(), "ok");
+
+    // A failed binary surfaces as DeployError::Failed carrying the report.
+    fs::write(rel.join(exe_filename("beta")), "").unwrap();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist2")),
+        ..Default::default()
+    };
+    match mdrcp::deploy(temp_dir.path(), &options) {
+        Err(mdrcp::DeployError::Failed(report)) => {
+            assert_eq!(report.copied, vec![exe_filename("alpha")]);
+            assert_eq!(report.failed[0].binary, exe_filename("beta"));
+        }
+        other => panic!(
+            "expected a partial failure, got {:?}",
+            other.map(|r| r.copied)
+        ),
+    }
+}
+
+#[test]
+fn test_deploy_covers_extra_targets() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "a").unwrap();
+
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("one")),
+        extra_targets: vec![PathBuf::from("two")],
+        ..Default::default()
+    };
+    let report = mdrcp::deploy(temp_dir.path(), &options).unwrap();
+    assert_eq!(report.target_dir, temp_dir.path().join("one"));
+    assert_eq!(report.extra_targets.len(), 1);
+    assert_eq!(
+        report.extra_targets[0].target_dir,
+        temp_dir.path().join("two")
+    );
+    for dir in ["one", "two"] {
+        assert!(temp_dir
+            .path()
+            .join(dir)
+            .join(exe_filename("alpha"))
+            .exists());
+    }
+
+    // A failure in any target fails the whole deploy.
+    fs::create_dir_all(temp_dir.path().join("four").join(exe_filename("alpha"))).unwrap();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("three")),
+        extra_targets: vec![PathBuf::from("four")],
+        ..Default::default()
+    };
+    match mdrcp::deploy(temp_dir.path(), &options) {
+        Err(err @ mdrcp::DeployError::Failed(_)) => {
+            assert_eq!(err.to_string(), "Deploy failed for 1 of 2 targets");
+            assert_eq!(err.exit_code(), 4);
+        }
+        other => panic!("expected a failure, got {:?}", other.map(|r| r.copied)),
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_all_files_ignores_deps_subdirectory() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(rel.join("deps")).unwrap();
+    for path in [
+        rel.join("extra-tool"),
+        rel.join("deps").join("demo-0f1e2d3c"),
+    ] {
+        create_and_write_file(&path, "x").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        all_files: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["copied_binaries"], serde_json::json!(["extra-tool"]));
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join("extra-tool").exists());
+    assert!(!dist.join("demo-0f1e2d3c").exists());
+}
+
+#[test]
+fn test_source_glob_selects_matching_files() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"foo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join("foo-cli"), "cli").unwrap();
+    create_and_write_file(&rel.join("foo-lib"), "lib").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        source_globs: vec!["target/release/*-cli".to_string()],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join("foo-cli").exists());
+    assert!(!dist.join("foo-lib").exists());
+}
+
+#[test]
+fn test_destination_collision_errors_unless_allowed() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    for dir in ["a", "b"] {
+        fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        create_and_write_file(&temp_dir.path().join(dir).join("tool"), dir).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        source_globs: vec!["a/*".to_string(), "b/*".to_string()],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    let a = Path::new("a").join("tool");
+    let b = Path::new("b").join("tool");
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "two binaries map to destination `tool`: `{}` and `{}` (pass --allow-clobber to deploy the first)",
+            a.display(),
+            b.display()
+        )
+    );
+    assert!(!temp_dir.path().join("dist").exists());
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        allow_clobber: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..options
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["warnings"][0]["code"], "destination_collision");
+    let deployed = fs::read_to_string(temp_dir.path().join("dist").join("tool")).unwrap();
+    assert_eq!(deployed, "a");
+}
+
+#[test]
+fn test_checksum_file_matches_deployed_files() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "beta bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        checksum_file: Some(PathBuf::from("SHA256SUMS")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let sums = fs::read_to_string(temp_dir.path().join("SHA256SUMS")).unwrap();
+    let lines: Vec<&str> = sums.lines().collect();
+    assert_eq!(lines.len(), 2);
+    let dist = temp_dir.path().join("dist");
+    for (line, name) in lines.iter().zip(["alpha", "beta"]) {
+        // coreutils format: 64 lowercase hex digits, two spaces, file name.
+        let (digest, file) = line.split_once("  ").unwrap();
+        assert_eq!(digest.len(), 64);
+        assert!(digest
+            .chars()
+            .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
+        assert_eq!(file, exe_filename(name));
+        let actual = mdrcp::provenance::sha256_hex(&dist.join(file)).unwrap();
+        assert_eq!(digest, actual);
+    }
+}
+
+/// One call seen by `MockRunner`: program, args, and the env overlay.
+#[cfg(unix)]
+type RecordedCommand = (
+    String,
+    Vec<OsString>,
+    std::collections::HashMap<String, OsString>,
+);
+
+/// Records every invocation instead of spawning it. Writes any `--output`
+/// file (as `gpg` would) and exits with `exit_code`.
+#[cfg(unix)]
+struct MockRunner {
+    calls: std::rc::Rc<std::cell::RefCell<Vec<RecordedCommand>>>,
+    exit_code: i32,
+}
+
+#[cfg(unix)]
+impl mdrcp::CommandRunner for MockRunner {
+    fn run(
+        &mut self,
+        program: &str,
+        args: &[OsString],
+        env: &std::collections::HashMap<String, OsString>,
+    ) -> std::io::Result<std::process::Output> {
+        use std::os::unix::process::ExitStatusExt;
+        self.calls
+            .borrow_mut()
+            .push((program.to_string(), args.to_vec(), env.clone()));
+        if let Some(i) = args.iter().position(|a| a == "--output") {
+            fs::write(&args[i + 1], "-----BEGIN PGP SIGNATURE-----\n")?;
+        }
+        Ok(std::process::Output {
+            // Raw wait status: the exit code lives in the high byte.
+            status: std::process::ExitStatus::from_raw(self.exit_code << 8),
+            stdout: Vec::new(),
+            stderr: b"mock failure".to_vec(),
+        })
+    }
+
+    fn run_in(
+        &mut self,
+        program: &str,
+        args: &[OsString],
+        env: &std::collections::HashMap<String, OsString>,
+        _dir: &Path,
+        _capture: bool,
+    ) -> std::io::Result<std::process::Output> {
+        self.run(program, args, env)
+    }
+}
+
+#[cfg(all(unix, feature = "gpg"))]
+#[test]
+fn test_gpg_sign_writes_signature_per_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "beta bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        gpg_sign: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.runner = Box::new(MockRunner {
+        calls: Default::default(),
+        exit_code: 0,
+    });
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    for name in ["alpha", "beta"] {
+        assert!(dist.join(exe_filename(name)).exists());
+        assert!(dist.join(format!("{}.sig", exe_filename(name))).exists());
+    }
+}
+
+#[cfg(all(unix, feature = "gpg"))]
+#[test]
+fn test_mock_runner_receives_gpg_invocation() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    create_and_write_file(&temp_dir.path().join(".env"), "GNUPGHOME=/tmp/keys").unwrap();
+
+    let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        env_file: Some(PathBuf::from(".env")),
+        gpg_sign: true,
+        gpg_key: Some("ABCD1234".to_string()),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.runner = Box::new(MockRunner {
+        calls: calls.clone(),
+        exit_code: 2,
+    });
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(
+        err.to_string().contains("Failed to copy 1 executable"),
+        "{err}"
+    );
+
+    let calls = calls.borrow();
+    assert_eq!(calls.len(), 1);
+    let (program, args, env) = &calls[0];
+    assert_eq!(program, "gpg");
+    let deployed = temp_dir.path().join("dist").join("alpha");
+    let expected: Vec<OsString> = vec![
+        "--batch".into(),
+        "--yes".into(),
+        "--local-user".into(),
+        "ABCD1234".into(),
+        "--output".into(),
+        deployed.with_extension("sig").into(),
+        "--detach-sign".into(),
+        deployed.into(),
+    ];
+    assert_eq!(args, &expected);
+    assert_eq!(env.get("GNUPGHOME"), Some(&OsString::from("/tmp/keys")));
+    // A non-zero exit fails the binary with gpg's stderr in the message.
+    assert!(String::from_utf8_lossy(&stderr).contains("mock failure"));
+}
+
+#[cfg(all(unix, feature = "gpg"))]
+#[test]
+fn test_dry_run_suppresses_every_side_effect() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+
+    let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        dry_run: true,
+        gpg_sign: true,
+        sidecar: true,
+        since_last_deploy: true,
+        checksum_file: Some(PathBuf::from("SHA256SUMS")),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.runner = Box::new(MockRunner {
+        calls: calls.clone(),
+        exit_code: 0,
+    });
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    assert!(calls.borrow().is_empty());
+    assert!(!temp_dir.path().join("dist").exists());
+    assert!(!temp_dir.path().join("SHA256SUMS").exists());
+    let out = String::from_utf8_lossy(&stdout);
+    let planned = temp_dir.path().join("dist").join(exe_filename("alpha"));
+    assert!(out.contains(&format!("-> {}", planned.display())), "{out}");
+    assert!(out.contains("sign each deployed binary"), "{out}");
+    assert!(out.contains("write checksums to"), "{out}");
+    assert!(out.contains("refresh .mdrcp-manifest.json"), "{out}");
+}
+
+#[test]
+fn test_parallel_targets_deploy_to_every_target() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "beta bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("one")),
+        extra_targets: vec![PathBuf::from("two"), PathBuf::from("three")],
+        parallel_targets: true,
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    for target in ["one", "two", "three"] {
+        for name in ["alpha", "beta"] {
+            assert!(temp_dir
+                .path()
+                .join(target)
+                .join(exe_filename(name))
+                .exists());
+        }
+    }
+    // One summary for all targets, listed in the order they were given.
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    let targets: Vec<&str> = summary["target_dirs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dir| dir.as_str().unwrap())
+        .collect();
+    assert_eq!(targets.len(), 3);
+    for (target, expected) in targets.iter().zip(["one", "two", "three"]) {
+        assert!(target.ends_with(expected), "{target}");
+    }
+    assert_eq!(summary["target_dir"], targets.join(", "));
+    assert_eq!(summary["copied_count"], 6);
+    assert_eq!(summary["status"], "ok");
+}
+
+#[test]
+fn test_merge_summaries_sums_counts_and_takes_worst_status() {
+    let temp_dir = tempdir().unwrap();
+    let ok = temp_dir.path().join("ok.json");
+    let partial = temp_dir.path().join("partial.json");
+    create_and_write_file(
+        &ok,
+        r#"{"status":"ok","copied_count":2,"target_dir":"/bin","override_used":false,
+            "host":{"os":"linux","arch":"x86_64"},"copied_binaries":["a","b"],
+            "skipped_binaries":[],"failed_binaries":[],"warnings":[]}"#,
+    )
+    .unwrap();
+    create_and_write_file(
+        &partial,
+        r#"{"status":"partial","copied_count":1,"target_dir":"/bin","override_used":true,
+            "host":{"os":"linux","arch":"x86_64"},"copied_binaries":["c"],
+            "skipped_binaries":["d"],"failed_binaries":[{"binary":"e","error":"boom"}],
+            "warnings":[{"code":"not_on_path","message":"nope"}]}"#,
+    )
+    .unwrap();
+
+    let mut out = Vec::new();
+    mdrcp::write_merged_summaries(&mut out, &[ok, partial], SummaryFormat::Json).unwrap();
+    let merged: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(merged["status"], "partial");
+    assert_eq!(merged["copied_count"], 3);
+    assert_eq!(merged["target_dir"], "/bin");
+    assert_eq!(merged["target_dirs"], serde_json::json!(["/bin"]));
+    assert_eq!(merged["override_used"], true);
+    assert_eq!(
+        merged["copied_binaries"],
+        serde_json::json!(["a", "b", "c"])
+    );
+    assert_eq!(merged["skipped_binaries"], serde_json::json!(["d"]));
+    assert_eq!(merged["failed_binaries"][0]["binary"], "e");
+    assert_eq!(merged["warnings"][0]["code"], "not_on_path");
+}
+
+#[test]
+fn test_dest_override_sends_one_binary_elsewhere() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "beta bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        dest_overrides: [("beta".to_string(), PathBuf::from("special/bin"))].into(),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let alpha = temp_dir.path().join("dist").join(exe_filename("alpha"));
+    let beta = temp_dir
+        .path()
+        .join("special/bin")
+        .join(exe_filename("beta"));
+    assert!(alpha.exists());
+    assert!(beta.exists());
+    assert!(!temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("beta"))
+        .exists());
+
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["copied_count"], 2);
+    let destinations = &summary["destinations"];
+    assert_eq!(
+        destinations[exe_filename("alpha")],
+        alpha.display().to_string()
+    );
+    assert_eq!(
+        destinations[exe_filename("beta")],
+        beta.display().to_string()
+    );
+}
+
+#[test]
+fn test_by_package_nests_binaries_under_package_dirs() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"server\",\"cli\"]",
+    )
+    .unwrap();
+    for m in ["server", "cli"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+        create_and_write_file(
+            &temp_dir.path().join(m).join("Cargo.toml"),
+            &format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"", m),
+        )
+        .unwrap();
+    }
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["server", "cli"] {
+        create_and_write_file(&rel.join(exe_filename(m)), m).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        by_package: true,
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    for m in ["server", "cli"] {
+        let nested = dist.join(m).join(exe_filename(m));
+        assert!(nested.exists(), "missing {}", nested.display());
+        assert!(dist.join(m).is_dir());
+        assert_eq!(
+            summary["destinations"][exe_filename(m)],
+            nested.display().to_string()
+        );
+    }
+}
+
+#[test]
+fn test_manifest_metadata_deploy_name_renames_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"server\",\"cli\"]",
+    )
+    .unwrap();
+    for m in ["server", "cli"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+    }
+    create_and_write_file(
+        &temp_dir.path().join("server").join("Cargo.toml"),
+        "[package]\nname=\"server\"\nversion=\"0.1.0\"\n\n[package.metadata.mdrcp]\ndeploy_name=\"renamed\"",
+    )
+    .unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("cli").join("Cargo.toml"),
+        "[package]\nname=\"cli\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["server", "cli"] {
+        create_and_write_file(&rel.join(exe_filename(m)), m).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("renamed"))).unwrap(),
+        "server"
+    );
+    assert!(!dist.join(exe_filename("server")).exists());
+    assert!(dist.join(exe_filename("cli")).exists());
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("cli"), exe_filename("renamed")])
+    );
+}
+
+#[test]
+fn test_manifest_metadata_skip_excludes_built_member() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"app\",\"fixture\"]",
+    )
+    .unwrap();
+    for m in ["app", "fixture"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+    }
+    create_and_write_file(
+        &temp_dir.path().join("app").join("Cargo.toml"),
+        "[package]\nname=\"app\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("fixture").join("Cargo.toml"),
+        "[package]\nname=\"fixture\"\nversion=\"0.1.0\"\n\n[package.metadata.mdrcp]\nskip=true",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["app", "fixture"] {
+        create_and_write_file(&rel.join(exe_filename(m)), m).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        report_unbuilt: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join(exe_filename("app")).exists());
+    assert!(!dist.join(exe_filename("fixture")).exists());
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("app")])
+    );
+    assert_eq!(summary["unbuilt"], serde_json::json!([]));
+}
+
+#[test]
+fn test_summary_indent_four_spaces() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        summary: SummaryFormat::JsonPretty,
+        summary_indent: Some(4),
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let out = String::from_utf8(stdout).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "{");
+    assert!(lines[1].starts_with("    \"schema_version\""), "{out}");
+    // Nested levels step by four as well.
+    assert!(
+        lines.iter().any(|l| l.starts_with("        \"os\"")),
+        "{out}"
+    );
+    serde_json::from_str::<Value>(&out).unwrap();
+}
+
+#[test]
+fn test_summary_compact_forces_single_line() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        summary: SummaryFormat::JsonPretty,
+        summary_compact: true,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let out = String::from_utf8(stdout).unwrap();
+    let summary = out.strip_suffix('\n').expect("summary ends its line");
+    assert!(!summary.contains(['\n', '\r']), "{out:?}");
+    assert_eq!(summary, summary.trim_end());
+    serde_json::from_str::<Value>(summary).unwrap();
+}
+
+#[test]
+fn test_target_parent_of_project_warns() {
+    let outer = tempdir().unwrap();
+    let project = outer.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    create_and_write_file(
+        &project.join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = project.join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("..")),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(&project, &options, &mut ctx).unwrap();
+
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["warnings"][0]["code"], "target_contains_project");
+    assert!(String::from_utf8_lossy(&stderr).contains("contains the project directory"));
+
+    let strict = RunOptions {
+        strict: true,
+        ..options
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(&project, &strict, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("(--strict)"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlinked_target_matches_default_after_resolution() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    // ~/.local/bin style link into a dotfiles checkout.
+    let real = temp_dir.path().join("dotfiles").join("bin");
+    fs::create_dir_all(&real).unwrap();
+    let link = temp_dir.path().join("local-bin");
+    std::os::unix::fs::symlink(&real, &link).unwrap();
+
+    let run_against_link = |no_resolve_target: bool| -> Value {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            summary: SummaryFormat::Json,
+            target_override: Some(link.clone()),
+            no_resolve_target,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env.insert(
+            TARGET_OVERRIDE_ENV.to_string(),
+            real.clone().into_os_string(),
+        );
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        serde_json::from_slice(&stdout).unwrap()
+    };
+
+    let resolved = run_against_link(false);
+    assert_eq!(resolved["warnings"][0]["code"], "redundant_target");
+    let literal = run_against_link(true);
+    assert_eq!(literal["warnings"], serde_json::json!([]));
+}
+
+/// Deploy `demo` into a `dist` that already holds an "old" copy; returns the
+/// run result, the JSON summary, and the project directory.
+fn deploy_over_existing(policy: ConflictPolicy) -> (anyhow::Result<()>, Value, tempfile::TempDir) {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "new").unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    create_and_write_file(&dist.join(exe_filename("demo")), "old").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        on_conflict: policy,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+    drop(ctx);
+    (result, serde_json::from_slice(&stdout).unwrap(), temp_dir)
+}
+
+#[test]
+fn test_on_conflict_overwrite_replaces_destination() {
+    let (result, summary, temp_dir) = deploy_over_existing(ConflictPolicy::Overwrite);
+    let dist = temp_dir.path().join("dist");
+    result.unwrap();
+    assert_eq!(summary["copied_count"], 1);
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("demo"))).unwrap(),
+        "new"
+    );
+}
+
+#[test]
+fn test_on_conflict_skip_leaves_destination() {
+    let (result, summary, temp_dir) = deploy_over_existing(ConflictPolicy::Skip);
+    let dist = temp_dir.path().join("dist");
+    result.unwrap();
+    assert_eq!(summary["copied_count"], 0);
+    assert_eq!(summary["skipped_binaries"][0], exe_filename("demo"));
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("demo"))).unwrap(),
+        "old"
+    );
+}
+
+#[test]
+fn test_on_conflict_backup_moves_destination_aside() {
+    let (result, summary, temp_dir) = deploy_over_existing(ConflictPolicy::Backup);
+    let dist = temp_dir.path().join("dist");
+    result.unwrap();
+    assert_eq!(summary["copied_count"], 1);
+    let exe = exe_filename("demo");
+    let backup = dist.join(format!("{}.bak", exe));
+    assert_eq!(fs::read_to_string(dist.join(&exe)).unwrap(), "new");
+    assert_eq!(fs::read_to_string(&backup).unwrap(), "old");
+    assert_eq!(
+        summary["backed_up"],
+        serde_json::json!([backup.display().to_string()])
+    );
+}
+
+#[test]
+fn test_backup_is_restored_when_copy_fails() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    // A directory where the binary should be: discovery accepts it, the copy fails.
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(rel.join(exe_filename("demo"))).unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    create_and_write_file(&dist.join(exe_filename("demo")), "installed").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        on_conflict: ConflictPolicy::Backup,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    assert!(run_with_options(temp_dir.path(), &options, &mut ctx).is_err());
+
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert!(summary["failed_binaries"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("previous binary restored"));
+    assert!(summary.get("backed_up").is_none());
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("demo"))).unwrap(),
+        "installed"
+    );
+    assert!(!dist.join(format!("{}.bak", exe_filename("demo"))).exists());
+}
+
+#[test]
+fn test_on_conflict_fail_records_failure() {
+    let (result, summary, temp_dir) = deploy_over_existing(ConflictPolicy::Fail);
+    let dist = temp_dir.path().join("dist");
+    assert!(result.unwrap_err().to_string().contains("Failed to copy"));
+    assert!(summary["failed_binaries"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("--on-conflict fail"));
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("demo"))).unwrap(),
+        "old"
+    );
+}
+
+#[test]
+fn test_sizes_table_shows_human_size() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), &"x".repeat(1536)).unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        sizes: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let out = String::from_utf8_lossy(&stdout);
+    let row = out
+        .lines()
+        .find(|line| line.contains("1.5 KiB"))
+        .expect("sizes table row");
+    assert!(row.contains(&exe_filename("demo")));
+    assert!(row.contains("dist"));
+}
+
+/// Records the buffered length at every flush.
+#[derive(Default)]
+struct FlushLog {
+    buf: Vec<u8>,
+    flushed_at: Vec<usize>,
+}
+
+impl Write for FlushLog {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flushed_at.push(self.buf.len());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_each_copy_line_is_flushed() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"one\"\n\n[[bin]]\nname=\"two\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("one")), "1").unwrap();
+    create_and_write_file(&rel.join(exe_filename("two")), "2").unwrap();
+
+    let mut stdout = FlushLog::default();
+    let mut stderr = FlushLog::default();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let out = String::from_utf8_lossy(&stdout.buf).into_owned();
+    let mut copy_line_ends = Vec::new();
+    let mut offset = 0;
+    for line in out.split_inclusive('\n') {
+        offset += line.len();
+        if line.contains("Copied") {
+            copy_line_ends.push(offset);
+        }
+    }
+    assert_eq!(copy_line_ends.len(), 2);
+    for end in copy_line_ends {
+        assert!(
+            stdout.flushed_at.contains(&end),
+            "no flush right after copy line ending at {}",
+            end
+        );
+    }
+}
+
+/// Raises `cancel` as soon as a copy line is written, simulating Ctrl-C
+/// between the first and second binary.
+struct CancelAfterCopy {
+    buf: Vec<u8>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl Write for CancelAfterCopy {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if String::from_utf8_lossy(&self.buf).contains("Copied") {
+            self.cancel.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_interrupt_between_binaries_stops_cleanly() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"one\"\n\n[[bin]]\nname=\"two\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("one")), "1").unwrap();
+    create_and_write_file(&rel.join(exe_filename("two")), "2").unwrap();
+    let dist = temp_dir.path().join("dist");
+    // A staging file left behind by an earlier, killed replace.
+    fs::create_dir_all(&dist).unwrap();
+    let stale = dist.join(format!(".{}.mdrcp-new", exe_filename("two")));
+    create_and_write_file(&stale, "partial").unwrap();
+
+    let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let mut stdout = CancelAfterCopy {
+        buf: Vec::new(),
+        cancel: cancel.clone(),
+    };
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        since_last_deploy: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        // One copy at a time, so the second has not started when the flag is raised.
+        jobs: Some(1),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.cancel = cancel;
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+
+    let interrupted = err.downcast_ref::<mdrcp::interrupt::Interrupted>().unwrap();
+    assert_eq!(interrupted.remaining, 1);
+    assert!(dist.join(exe_filename("one")).exists());
+    assert!(!dist.join(exe_filename("two")).exists());
+    let leftovers: Vec<_> = fs::read_dir(&dist)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".mdrcp-new"))
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+    // The deploy time is not advanced past binaries that were never copied.
+    assert!(!dist.join(".mdrcp-manifest.json").exists());
+    assert!(String::from_utf8_lossy(&stderr).contains("Interrupted:"));
+}
+
+#[test]
+fn test_run_with_summary_yaml() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "x").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Yaml,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let out = String::from_utf8(stdout).unwrap();
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines.contains(&"status: \"ok\""), "{out}");
+    assert!(lines.contains(&"copied_count: 1"), "{out}");
+    assert!(lines.contains(&"override_used: true"), "{out}");
+    assert!(lines.contains(&"failed_binaries: []"), "{out}");
+    assert!(lines.contains(&"warnings: []"), "{out}");
+    let copied = lines.iter().position(|l| *l == "copied_binaries:").unwrap();
+    assert_eq!(
+        lines[copied + 1],
+        format!("  - \"{}\"", exe_filename("demo"))
+    );
+    assert!(
+        lines.iter().any(|l| l.starts_with("target_dir: \"")),
+        "{out}"
+    );
+}
+
+#[test]
+fn test_exclude_patterns_skip_matching_binaries() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"tool\"\n\n[[bin]]\nname=\"tool-test\"\n\n[[bin]]\nname=\"internal-gen\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["tool", "tool-test", "internal-gen"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let run_excluding = |exclude: &[&str]| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            exclude: exclude.iter().map(|p| p.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        (result, stdout)
+    };
+
+    let (result, stdout) = run_excluding(&["*-test", "internal-*"]);
+    result.unwrap();
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("tool")])
+    );
+
+    let (result, _) = run_excluding(&["*"]);
+    let err = result.unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("All binaries excluded by --exclude patterns"));
+}
+
+#[test]
+fn test_bin_selects_named_binaries_and_rejects_unknown() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"api\"\n\n[[bin]]\nname=\"cli\"\n\n[[bin]]\nname=\"worker\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["api", "cli", "worker"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let run_only = |only: &[&str]| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            only: only.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        (result, stdout)
+    };
+
+    let (result, stdout) = run_only(&["worker", "api"]);
+    result.unwrap();
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("api"), exe_filename("worker")])
+    );
+
+    let (result, _) = run_only(&["api", "wroker"]);
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("requested binary 'wroker' not declared in any manifest"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_mode_links_destination_to_build() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let source = rel.join(exe_filename("demo"));
+    create_and_write_file(&source, "v1").unwrap();
+    // An earlier copied deploy is replaced by the link.
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    create_and_write_file(&dist.join(exe_filename("demo")), "old copy").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        link_mode: LinkMode::Symlink,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let deployed = dist.join(exe_filename("demo"));
+    assert!(fs::symlink_metadata(&deployed)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    assert_eq!(
+        fs::read_link(&deployed).unwrap(),
+        source.canonicalize().unwrap()
+    );
+    // Rebuilds show through without redeploying.
+    fs::write(&source, "v2").unwrap();
+    assert_eq!(fs::read_to_string(&deployed).unwrap(), "v2");
+    let out = String::from_utf8_lossy(&stdout);
+    assert!(out.contains("Linked"), "{out}");
+    assert!(!out.contains("Copied"), "{out}");
+}
+
+/// A project with `good` and `bad` binaries already deployed to `dist`; `bad`
+/// is built as a directory when `break_bad`, so its copy fails.
+fn atomic_project(break_bad: bool) -> tempfile::TempDir {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"bad\"\n\n[[bin]]\nname=\"good\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("good")), "new good").unwrap();
+    if break_bad {
+        fs::create_dir_all(rel.join(exe_filename("bad"))).unwrap();
+    } else {
+        create_and_write_file(&rel.join(exe_filename("bad")), "new bad").unwrap();
+    }
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    create_and_write_file(&dist.join(exe_filename("good")), "old good").unwrap();
+    create_and_write_file(&dist.join(exe_filename("bad")), "old bad").unwrap();
+    temp_dir
+}
+
+fn run_atomic(project: &Path) -> (anyhow::Result<()>, Value) {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        atomic: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let result = run_with_options(project, &options, &mut ctx);
+    drop(ctx);
+    (result, serde_json::from_slice(&stdout).unwrap())
+}
+
+#[test]
+fn test_atomic_deploy_replaces_all_when_every_copy_succeeds() {
+    let temp_dir = atomic_project(false);
+    let (result, summary) = run_atomic(temp_dir.path());
+    result.unwrap();
+    assert_eq!(summary["status"], "ok");
+    assert_eq!(summary["copied_count"], 2);
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("good"))).unwrap(),
+        "new good"
+    );
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("bad"))).unwrap(),
+        "new bad"
+    );
+}
+
+#[test]
+fn test_atomic_deploy_replaces_nothing_on_failure() {
+    let temp_dir = atomic_project(true);
+    let (result, summary) = run_atomic(temp_dir.path());
+    assert!(result.is_err());
+    assert_eq!(summary["status"], "failed");
+    assert_eq!(summary["copied_binaries"], serde_json::json!([]));
+    assert_eq!(summary["failed_binaries"][0]["binary"], exe_filename("bad"));
+
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("good"))).unwrap(),
+        "old good"
+    );
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("bad"))).unwrap(),
+        "old bad"
+    );
+    let leftovers: Vec<_> = fs::read_dir(&dist)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".mdrcp-new"))
+        .collect();
+    assert!(leftovers.is_empty(), "{leftovers:?}");
+}
+
+#[test]
+fn test_atomic_backup_is_taken_only_at_commit() {
+    let run = |project: &Path| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            atomic: true,
+            on_conflict: ConflictPolicy::Backup,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let result = run_with_options(project, &options, &mut ctx);
+        drop(ctx);
+        (result, String::from_utf8(stdout).unwrap())
+    };
+
+    let temp_dir = atomic_project(true);
+    let (result, stdout) = run(temp_dir.path());
+    assert!(result.is_err());
+    assert!(!stdout.contains("Backed up"), "{stdout}");
+    let dist = temp_dir.path().join("dist");
+    let mut names: Vec<_> = fs::read_dir(&dist)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec![exe_filename("bad"), exe_filename("good")]);
+
+    let temp_dir = atomic_project(false);
+    let (result, stdout) = run(temp_dir.path());
+    result.unwrap();
+    assert!(stdout.contains("Backed up"), "{stdout}");
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(
+        fs::read_to_string(dist.join(format!("{}.bak", exe_filename("good")))).unwrap(),
+        "old good"
+    );
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("good"))).unwrap(),
+        "new good"
+    );
+}
+
+#[test]
+fn test_atomic_commit_failure_rolls_back_earlier_renames() {
+    let temp_dir = atomic_project(false);
+    let dist = temp_dir.path().join("dist");
+    // `bad` commits first; a directory where good's backup must go then fails
+    // the commit of `good`.
+    let blocker = dist.join(format!("{}.bak", exe_filename("good")));
+    fs::create_dir_all(&blocker).unwrap();
+    create_and_write_file(&blocker.join("keep"), "").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        atomic: true,
+        on_conflict: ConflictPolicy::Backup,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+    drop(ctx);
+    assert!(result.is_err());
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["status"], "failed");
+    assert_eq!(summary["copied_binaries"], serde_json::json!([]));
+    assert_eq!(
+        summary["failed_binaries"][0]["binary"],
+        exe_filename("good")
+    );
+
+    for (name, contents) in [("bad", "old bad"), ("good", "old good")] {
+        assert_eq!(
+            fs::read_to_string(dist.join(exe_filename(name))).unwrap(),
+            contents
+        );
+    }
+    let mut names: Vec<_> = fs::read_dir(&dist)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            exe_filename("bad"),
+            exe_filename("good"),
+            format!("{}.bak", exe_filename("good"))
+        ]
+    );
+}
+
+#[test]
+fn test_atomic_replace_of_running_executable_waits_for_commit() {
+    let run = |project: &Path| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            atomic: true,
+            self_update: mdrcp::SelfUpdateStrategy::Replace,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.current_exe = Some(project.join("dist").join(exe_filename("good")));
+        let result = run_with_options(project, &options, &mut ctx);
+        drop(ctx);
+        (result, serde_json::from_slice::<Value>(&stdout).unwrap())
+    };
+
+    // Another binary fails: the running executable is left alone too.
+    let temp_dir = atomic_project(true);
+    let (result, summary) = run(temp_dir.path());
+    assert!(result.is_err());
+    assert_eq!(summary["copied_binaries"], serde_json::json!([]));
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("dist").join(exe_filename("good"))).unwrap(),
+        "old good"
+    );
+
+    let temp_dir = atomic_project(false);
+    let (result, summary) = run(temp_dir.path());
+    result.unwrap();
+    assert_eq!(summary["self_update"]["status"], "replaced");
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("dist").join(exe_filename("good"))).unwrap(),
+        "new good"
+    );
+}
+
+#[test]
+fn test_parallel_jobs_keep_deterministic_order() {
+    let names: Vec<String> = (0..12).map(|i| format!("bin{:02}", i)).collect();
+    let temp_dir = tempdir().unwrap();
+    let mut manifest = "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n".to_string();
+    for name in names.iter().rev() {
+        manifest.push_str(&format!("\n[[bin]]\nname=\"{}\"\n", name));
+    }
+    create_and_write_file(&temp_dir.path().join("Cargo.toml"), &manifest).unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in &names {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let run_with_jobs = |summary: SummaryFormat| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            jobs: Some(4),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        String::from_utf8(stdout).unwrap()
+    };
+
+    let expected: Vec<String> = names.iter().map(|n| exe_filename(n)).collect();
+    let summary: Value = serde_json::from_str(&run_with_jobs(SummaryFormat::Json)).unwrap();
+    assert_eq!(summary["copied_binaries"], serde_json::json!(expected));
+
+    let text = run_with_jobs(SummaryFormat::Text);
+    let copied_order: Vec<&str> = text
+        .lines()
+        .filter(|line| line.contains("Copied"))
+        .map(|line| {
+            names
+                .iter()
+                .find(|name| line.contains(name.as_str()))
+                .unwrap()
+                .as_str()
+        })
+        .collect();
+    assert_eq!(copied_order, names);
+    for name in &names {
+        let deployed = temp_dir.path().join("dist").join(exe_filename(name));
+        assert_eq!(fs::read_to_string(deployed).unwrap(), *name);
+    }
+}
+
+#[test]
+fn test_verify_reports_sha256_per_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo contents").unwrap();
+
+    let run = |verify: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            verify,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    let summary = run(false);
+    assert!(summary.get("copied").is_none());
+
+    let summary = run(true);
+    let expected = mdrcp::provenance::sha256_hex(&rel.join(exe_filename("demo"))).unwrap();
+    assert_eq!(
+        summary["copied"],
+        serde_json::json!([{"name": exe_filename("demo"), "sha256": expected}])
+    );
+}
+
+#[test]
+fn test_skip_unchanged_leaves_identical_destination() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let source = rel.join(exe_filename("demo"));
+    create_and_write_file(&source, "v1").unwrap();
+
+    let run = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            skip_unchanged: true,
+            verify: true,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    let first = run();
+    assert_eq!(first["copied_count"], 1);
+
+    let second = run();
+    assert_eq!(second["copied_count"], 0);
+    assert_eq!(
+        second["skipped_binaries"],
+        serde_json::json!([exe_filename("demo")])
+    );
+
+    create_and_write_file(&source, "v2 is longer").unwrap();
+    let third = run();
+    assert_eq!(third["copied_count"], 1);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("dist").join(exe_filename("demo"))).unwrap(),
+        "v2 is longer"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_skip_unchanged_with_read_only_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let source = rel.join("demo");
+    create_and_write_file(&source, "v1").unwrap();
+    // A read-only source makes fs::copy create a read-only copy too, so the
+    // mtime must be set without a writable handle even for non-root users.
+    fs::set_permissions(&source, fs::Permissions::from_mode(0o555)).unwrap();
+
+    let run = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            skip_unchanged: true,
+            mode: Some(0o555),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    assert_eq!(run()["copied_count"], 1);
+    let deployed = temp_dir.path().join("dist").join("demo");
+    let metadata = fs::metadata(&deployed).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o7777, 0o555);
+    assert_eq!(
+        metadata.modified().unwrap(),
+        fs::metadata(&source).unwrap().modified().unwrap()
+    );
+    let second = run();
+    assert_eq!(second["copied_count"], 0);
+    assert_eq!(second["skipped_binaries"], serde_json::json!(["demo"]));
+}
+
+#[cfg(unix)]
+fn run_strip(exit_code: i32) -> (tempfile::TempDir, Vec<RecordedCommand>, Value, bool) {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join("demo"), "demo with symbols").unwrap();
+
+    let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        strip: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.runner = Box::new(MockRunner {
+        calls: calls.clone(),
+        exit_code,
+    });
+    let ok = run_with_options(temp_dir.path(), &options, &mut ctx).is_ok();
+    drop(ctx);
+    let calls = calls.borrow().clone();
+    (
+        temp_dir,
+        calls,
+        serde_json::from_slice(&stdout).unwrap(),
+        ok,
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn test_strip_runs_on_deployed_copy_only() {
+    let (temp_dir, calls, summary, ok) = run_strip(0);
+    assert!(ok);
+    let deployed = temp_dir.path().join("dist").join("demo");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].0, "strip");
+    assert_eq!(calls[0].1, vec![OsString::from(&deployed)]);
+    assert_eq!(
+        summary["copied"],
+        serde_json::json!([{"name": "demo", "stripped": true}])
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_strip_failure_fails_binary() {
+    let (_temp_dir, _calls, summary, ok) = run_strip(1);
+    assert!(!ok);
+    assert_eq!(summary["status"], "failed");
+    let error = summary["failed_binaries"][0]["error"].as_str().unwrap();
+    assert!(error.contains("strip failed for"), "{error}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_parallel_targets_bypass_the_context_runner() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join("demo"), "demo with symbols").unwrap();
+    // No real `strip` can be found, so only the mock could make it succeed.
+    let empty_path = temp_dir.path().join("no-tools");
+    fs::create_dir_all(&empty_path).unwrap();
+
+    let run = |parallel_targets: bool| {
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("one".into()),
+            extra_targets: vec!["two".into()],
+            parallel_targets,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            strip: true,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env
+            .insert("PATH".to_string(), empty_path.clone().into_os_string());
+        ctx.runner = Box::new(MockRunner {
+            calls: calls.clone(),
+            exit_code: 0,
+        });
+        let ok = run_with_options(temp_dir.path(), &options, &mut ctx).is_ok();
+        drop(ctx);
+        let calls = calls.borrow().len();
+        (ok, calls)
+    };
+
+    assert_eq!(run(false), (true, 2));
+    // Each parallel target spawns its tools with a SystemRunner instead.
+    assert_eq!(run(true), (false, 0));
+}
+
+#[test]
+fn test_strip_rejects_symlink_mode() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let options = RunOptions {
+        strip: true,
+        link_mode: LinkMode::Symlink,
+        quiet: true,
+        ..Default::default()
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("--strip"), "{err}");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_copy_sets_executable_mode() {
+    use std::os::unix::fs::PermissionsExt;
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let source = rel.join("demo");
+    create_and_write_file(&source, "demo").unwrap();
+    fs::set_permissions(&source, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let deployed_mode = |mode: Option<u32>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            mode,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        let deployed = temp_dir.path().join("dist").join("demo");
+        fs::metadata(deployed).unwrap().permissions().mode() & 0o7777
+    };
+
+    assert_eq!(deployed_mode(None), 0o755);
+    assert_eq!(deployed_mode(Some(0o700)), 0o700);
+}
+
+#[test]
+fn test_project_config_supplies_target_and_summary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    create_and_write_file(
+        &temp_dir.path().join(".mdrcp.toml"),
+        "target = \"from-config\"\nsummary = \"json\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["copied_count"], 1);
+    assert!(temp_dir
+        .path()
+        .join("from-config")
+        .join(exe_filename("demo"))
+        .exists());
+
+    // The command line wins over the file.
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        ..options
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("demo"))
+        .exists());
+}
+
+#[test]
+fn test_given_flags_beat_project_config_at_default_values() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    create_and_write_file(
+        &temp_dir.path().join(".mdrcp.toml"),
+        "target = \"from-config\"\nsummary = \"json\"\nprofile = \"debug\"",
+    )
+    .unwrap();
+    for profile in ["release", "debug"] {
+        let dir = temp_dir.path().join("target").join(profile);
+        fs::create_dir_all(&dir).unwrap();
+        create_and_write_file(&dir.join(exe_filename("demo")), profile).unwrap();
+    }
+
+    let args: Vec<String> = ["--release", "--summary", "text", "--quiet"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let mdrcp::Command::Deploy(options) = mdrcp::parse_args(&args).unwrap() else {
+        panic!("expected deploy");
+    };
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+    assert!(serde_json::from_slice::<Value>(&stdout).is_err());
+    let deployed = temp_dir
+        .path()
+        .join("from-config")
+        .join(exe_filename("demo"));
+    assert_eq!(fs::read_to_string(&deployed).unwrap(), "release");
+
+    // The library entry point reads the same config.
+    fs::remove_file(&deployed).unwrap();
+    let report = mdrcp::deploy(temp_dir.path(), &RunOptions::default()).unwrap();
+    assert_eq!(report.copied, vec!["demo".to_string()]);
+    assert_eq!(fs::read_to_string(&deployed).unwrap(), "debug");
+}
+
+#[test]
+fn test_mdrcp_target_env_sets_default_target() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+
+    let run = |options: &RunOptions| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env
+            .insert("MDRCP_TARGET".to_string(), "from-env".into());
+        run_with_options(temp_dir.path(), options, &mut ctx).unwrap();
+        drop(ctx);
+        String::from_utf8(stdout).unwrap()
+    };
+
+    let options = RunOptions {
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let text = run(&options);
+    assert!(
+        text.contains("Destination provided via MDRCP_TARGET."),
+        "{text}"
+    );
+    let env_dir = temp_dir.path().join("from-env");
+    assert!(env_dir.join(exe_filename("demo")).exists());
+
+    let summary: Value = serde_json::from_str(&run(&RunOptions {
+        summary: SummaryFormat::Json,
+        ..options.clone()
+    }))
+    .unwrap();
+    assert_eq!(summary["target_dir"], env_dir.display().to_string());
+    assert_eq!(summary["override_used"], false);
+
+    // --target still wins.
+    run(&RunOptions {
+        target_override: Some("dist".into()),
+        ..options
+    });
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("demo"))
+        .exists());
+}
+
+#[test]
+fn test_target_triple_reads_cross_compiled_layout() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let musl = temp_dir
+        .path()
+        .join("target")
+        .join("x86_64-unknown-linux-musl")
+        .join("release");
+    fs::create_dir_all(&musl).unwrap();
+    create_and_write_file(&musl.join(exe_filename("demo")), "musl build").unwrap();
+
+    let run = |target_triple: Option<&str>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            target_triple: target_triple.map(String::from),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx)
+    };
+
+    // The host layout (target/release) is empty, so only the triple finds it.
+    assert!(run(None).is_err());
+    run(Some("x86_64-unknown-linux-musl")).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("dist").join(exe_filename("demo"))).unwrap(),
+        "musl build"
+    );
+    let err = run(Some("aarch64-unknown-linux-gnu")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("--target aarch64-unknown-linux-gnu"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_list_reports_built_and_unbuilt_binaries() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"built\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"missing\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("built")), "built").unwrap();
+
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        ..Default::default()
+    };
+    let mut stdout = Vec::new();
+    mdrcp::write_binary_list(&mut stdout, temp_dir.path(), &options).unwrap();
+    let list: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        list,
+        serde_json::json!({"binaries": [
+            {
+                "name": "built",
+                "exists": true,
+                "path": rel.join(exe_filename("built")).display().to_string()
+            },
+            {
+                "name": "missing",
+                "exists": false,
+                "path": rel.join(exe_filename("missing")).display().to_string()
+            }
+        ]})
+    );
+
+    // Nothing was deployed and no target was needed.
+    assert!(!temp_dir.path().join("dist").exists());
+    let mut stdout = Vec::new();
+    mdrcp::write_binary_list(&mut stdout, temp_dir.path(), &RunOptions::default()).unwrap();
+    let text = String::from_utf8(stdout).unwrap();
+    assert!(text.contains("not built"), "{text}");
+
+    // CARGO_TARGET_DIR from --env-file moves the scan, as it does for a deploy.
+    let elsewhere = temp_dir.path().join("elsewhere").join("release");
+    fs::create_dir_all(&elsewhere).unwrap();
+    create_and_write_file(&elsewhere.join(exe_filename("missing")), "m").unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("build.env"),
+        &format!(
+            "CARGO_TARGET_DIR={}",
+            temp_dir.path().join("elsewhere").display()
+        ),
+    )
+    .unwrap();
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        env_file: Some(PathBuf::from("build.env")),
+        ..Default::default()
+    };
+    let mut stdout = Vec::new();
+    mdrcp::write_binary_list(&mut stdout, temp_dir.path(), &options).unwrap();
+    let list: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(list["binaries"][1]["name"], "missing");
+    assert_eq!(list["binaries"][1]["exists"], true);
+    assert_eq!(
+        list["binaries"][1]["path"],
+        elsewhere
+            .join(exe_filename("missing"))
+            .display()
+            .to_string()
+    );
+}
+
+#[test]
+fn test_verbose_traces_discovery_on_stderr() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"extra\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+
+    let run = |verbosity: u8| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            exclude: vec!["ex*".to_string()],
+            verbosity,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        String::from_utf8(stderr).unwrap()
+    };
+
+    assert!(!run(0).contains("debug:"));
+
+    let level1 = run(1);
+    let manifest = temp_dir.path().join("Cargo.toml");
+    assert!(
+        level1.contains(&format!("reading {}", manifest.display())),
+        "{level1}"
+    );
+    assert!(level1.contains("candidate demo"), "{level1}");
+    assert!(level1.contains("dropped extra (--exclude)"), "{level1}");
+    assert!(!level1.contains("checking"), "{level1}");
+
+    let level2 = run(2);
+    assert!(
+        level2.contains(&format!("scanning {}", rel.display())),
+        "{level2}"
+    );
+    let check = format!(
+        "checking {}: found",
+        rel.join(exe_filename("demo")).display()
+    );
+    assert!(level2.contains(&check), "{level2}");
+}
+
+#[test]
+fn test_include_libs_deploys_cdylib_separately() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"my-plugin\"\nversion=\"0.1.0\"\n\n[lib]\ncrate-type=[\"cdylib\"]\n\n[[bin]]\nname=\"host\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("host")), "host").unwrap();
+    let lib_name = format!(
+        "{}my_plugin{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    );
+    create_and_write_file(&rel.join(&lib_name), "lib").unwrap();
+
+    let run = |include_libs: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            include_libs,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<serde_json::Value>(&stdout).unwrap()
+    };
+
+    let without = run(false);
+    assert!(without.get("copied_libraries").is_none());
+    assert!(!temp_dir.path().join("dist").join(&lib_name).exists());
+
+    let summary = run(true);
+    assert_eq!(summary["copied_count"], 1);
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("host")])
+    );
+    assert_eq!(summary["copied_libraries"], serde_json::json!([lib_name]));
+    assert!(temp_dir.path().join("dist").join(&lib_name).exists());
+}
+
+#[test]
+fn test_prefix_and_suffix_rename_deployed_binaries() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"foo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("foo")), "foo").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        prefix: Some("my-".to_string()),
+        suffix: Some("-2".to_string()),
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+
+    let expected = mdrcp::affixed_exe_filename("foo", "my-", "-2");
+    assert_eq!(expected, exe_filename("my-foo-2"));
+    let summary: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["copied_binaries"], serde_json::json!([expected]));
+    let dist = temp_dir.path().join("dist");
+    assert!(dist.join(&expected).exists());
+    assert!(!dist.join(exe_filename("foo")).exists());
+
+    let options = RunOptions {
+        prefix: Some("bin/".to_string()),
+        ..options
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("path separators"), "{err}");
+}
+
+#[test]
+fn test_manifest_out_lists_deployed_artifacts() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo-bytes").unwrap();
+
+    let run = |manifest_out: &str| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            verify: true,
+            manifest_out: Some(PathBuf::from(manifest_out)),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    run("deployed.json");
+    let text = fs::read_to_string(temp_dir.path().join("deployed.json")).unwrap();
+    let manifest: mdrcp::ArtifactManifest = serde_json::from_str(&text).unwrap();
+    assert_eq!(manifest.artifacts.len(), 1);
+    let artifact = &manifest.artifacts[0];
+    assert_eq!(artifact.name, exe_filename("demo"));
+    assert_eq!(artifact.size, 10);
+    assert!(Path::new(&artifact.path).is_absolute());
+    assert!(Path::new(&artifact.path).ends_with(Path::new("dist").join(exe_filename("demo"))));
+    assert_eq!(artifact.sha256.as_ref().map(String::len), Some(64));
+
+    // An unwritable location only warns; the deploy itself succeeded.
+    let summary = run("missing-dir/deployed.json");
+    assert_eq!(summary["status"], "ok");
+    assert_eq!(summary["warnings"][0]["code"], "manifest_write_failed");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_force_overwrites_read_only_destination() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join("demo"), "new").unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    let installed = dist.join("demo");
+    create_and_write_file(&installed, "old").unwrap();
+    fs::set_permissions(&installed, fs::Permissions::from_mode(0o444)).unwrap();
+
+    let run = |force: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            force,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let _ = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    // Root ignores file modes, so only an unprivileged run is refused.
+    if unsafe { libc::geteuid() } != 0 {
+        let summary = run(false);
+        assert_eq!(summary["status"], "failed");
+        assert_eq!(summary["failed_binaries"][0]["binary"], "demo");
+        assert_eq!(fs::read_to_string(&installed).unwrap(), "old");
+    }
+
+    let summary = run(true);
+    assert_eq!(summary["status"], "ok");
+    assert_eq!(fs::read_to_string(&installed).unwrap(), "new");
+}
+
+#[test]
+fn test_cargo_target_dir_locates_built_binaries() {
+    let temp_dir = tempdir().unwrap();
+    let project = temp_dir.path().join("project");
+    fs::create_dir_all(&project).unwrap();
+    create_and_write_file(
+        &project.join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let shared = temp_dir.path().join("shared-target");
+    fs::create_dir_all(shared.join("release")).unwrap();
+    create_and_write_file(&shared.join("release").join(exe_filename("demo")), "demo").unwrap();
+
+    let run = |ctx_env: &[(&str, &Path)], cargo_target_dir: Option<PathBuf>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            cargo_target_dir,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        for (key, value) in ctx_env {
+            ctx.env
+                .insert(key.to_string(), value.as_os_str().to_owned());
+        }
+        let result = run_with_options(&project, &options, &mut ctx);
+        drop(ctx);
+        result.map(|()| serde_json::from_slice::<Value>(&stdout).unwrap())
+    };
+
+    let summary = run(&[("CARGO_TARGET_DIR", &shared)], None).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("demo")])
+    );
+
+    // --target-dir wins over the environment.
+    let elsewhere = temp_dir.path().join("elsewhere");
+    let err = run(&[("CARGO_TARGET_DIR", &shared)], Some(elsewhere)).unwrap_err();
+    assert!(
+        err.to_string().contains("No built release executables"),
+        "{err}"
+    );
+
+    // build.target-dir from the project's .cargo/config.toml, relative to
+    // the directory holding .cargo.
+    fs::create_dir_all(project.join(".cargo")).unwrap();
+    create_and_write_file(
+        &project.join(".cargo").join("config.toml"),
+        "[build]\ntarget-dir = \"../shared-target\"\n",
+    )
+    .unwrap();
+    let summary = run(&[], None).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("demo")])
+    );
+}
+
+#[test]
+fn test_text_output_numbers_binaries_in_multi_binary_deploys() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha").unwrap();
+
+    let run = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        String::from_utf8(stdout).unwrap()
+    };
+
+    // A single binary keeps the plain lines.
+    let single = run();
+    assert!(!single.contains("[1/1]"), "{single}");
+    assert!(!single.contains(" of "), "{single}");
+
+    create_and_write_file(&rel.join(exe_filename("beta")), "beta").unwrap();
+    let multi = run();
+    assert!(multi.contains("[1/2]"), "{multi}");
+    assert!(multi.contains("[2/2]"), "{multi}");
+    assert!(multi.contains("2 of 2"), "{multi}");
+}
+
+#[test]
+fn test_deploy_errors_map_to_distinct_exit_codes() {
+    let temp_dir = tempdir().unwrap();
+    let exit_code = |options: &RunOptions| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let err = run_with_options(temp_dir.path(), options, &mut ctx).unwrap_err();
+        let code = err
+            .downcast_ref::<mdrcp::DeployError>()
+            .map(mdrcp::DeployError::exit_code);
+        (code, err.to_string())
+    };
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+
+    let (code, message) = exit_code(&options);
+    assert_eq!(code, Some(2));
+    assert!(message.starts_with("No Cargo.toml found"), "{message}");
+
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let (code, message) = exit_code(&options);
+    assert_eq!(code, Some(3));
+    assert!(
+        message.starts_with("No built release executables"),
+        "{message}"
+    );
+
+    // Empty files fail the minimum-size check.
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha").unwrap();
+    create_and_write_file(&rel.join(exe_filename("beta")), "").unwrap();
+    let (code, message) = exit_code(&options);
+    assert_eq!(code, Some(4));
+    assert_eq!(
+        message,
+        "Failed to copy 1 of 2 executables (copied 1 successfully)"
+    );
+
+    create_and_write_file(&rel.join(exe_filename("alpha")), "").unwrap();
+    let (code, message) = exit_code(&options);
+    assert_eq!(code, Some(5));
+    assert_eq!(message, "Failed to copy 2 executable(s)");
+}
+
+#[test]
+fn test_silent_keeps_stderr_empty_but_summary_complete() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha").unwrap();
+    // Empty: fails the minimum-size check.
+    create_and_write_file(&rel.join(exe_filename("beta")), "").unwrap();
+
+    let run = |silent: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            silent,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env
+            .insert("PATH".to_string(), OsString::from("/nonexistent"));
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        let summary: Value = serde_json::from_slice(&stdout).unwrap();
+        (result, summary, String::from_utf8(stderr).unwrap())
+    };
+
+    let (_, _, stderr) = run(false);
+    assert!(stderr.contains("Warning:"), "{stderr}");
+
+    let (result, summary, stderr) = run(true);
+    assert!(result.is_err());
+    assert_eq!(stderr, "");
+    assert_eq!(summary["status"], "partial");
+    assert_eq!(summary["warnings"][0]["code"], "not_on_path");
+}
+
+#[test]
+fn test_examples_flag_deploys_example_binaries() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[example]]\nname=\"helper\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(rel.join("examples")).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+    create_and_write_file(&rel.join("examples").join(exe_filename("helper")), "helper").unwrap();
+
+    let run = |examples: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            examples,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    // Examples stay out of a default run.
+    let summary = run(false);
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("demo")])
+    );
+
+    let summary = run(true);
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("demo"), exe_filename("helper")])
+    );
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("helper"))
+        .exists());
+}
+
+#[test]
+fn test_default_members_narrow_the_scan_unless_all() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"app\",\"tools\"]\ndefault-members=[\"app\"]",
+    )
+    .unwrap();
+    for m in ["app", "tools"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+        create_and_write_file(
+            &temp_dir.path().join(m).join("Cargo.toml"),
+            &format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"", m),
+        )
+        .unwrap();
+    }
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["app", "tools"] {
+        create_and_write_file(&rel.join(exe_filename(m)), m).unwrap();
+    }
+
+    let run = |all_members: bool, only: Vec<String>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            all_members,
+            only,
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()["copied_binaries"].clone()
+    };
+
+    assert_eq!(run(false, vec![]), serde_json::json!([exe_filename("app")]));
+    assert_eq!(
+        run(true, vec![]),
+        serde_json::json!([exe_filename("app"), exe_filename("tools")])
+    );
+    // Naming a binary reaches members outside default-members.
+    assert_eq!(
+        run(false, vec!["tools".to_string()]),
+        serde_json::json!([exe_filename("tools")])
+    );
+}
+
+#[test]
+fn test_workspace_glob_members_skip_files_and_non_crates() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"crates/*\", \"missing/*\"]",
+    )
+    .unwrap();
+    let crates = temp_dir.path().join("crates");
+    fs::create_dir_all(crates.join("alpha")).unwrap();
+    create_and_write_file(
+        &crates.join("alpha").join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    // Matches of the glob that are not member crates.
+    fs::create_dir_all(crates.join("docs")).unwrap();
+    create_and_write_file(&crates.join("README.md"), "notes").unwrap();
+
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("alpha")])
+    );
+}
+
+#[test]
+fn test_workspace_exclude_skips_members() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"crates/*\"]\nexclude=[\"crates/internal\"]",
+    )
+    .unwrap();
+    for m in ["app", "internal"] {
+        let crate_dir = temp_dir.path().join("crates").join(m);
+        fs::create_dir_all(&crate_dir).unwrap();
+        create_and_write_file(
+            &crate_dir.join("Cargo.toml"),
+            &format!("[package]\nname=\"{}\"\nversion=\"0.1.0\"", m),
+        )
+        .unwrap();
+    }
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for m in ["app", "internal"] {
+        create_and_write_file(&rel.join(exe_filename(m)), m).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("app")])
+    );
+    assert!(!temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("internal"))
+        .exists());
+}
+
+#[test]
+fn test_ndjson_summary_streams_copied_events() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["alpha", "beta"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Ndjson,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+
+    let out = String::from_utf8(stdout).unwrap();
+    let events: Vec<serde_json::Value> = out
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(events.len(), 3, "{out}");
+    let dist = temp_dir.path().join("dist");
+    let mut copied: Vec<&str> = Vec::new();
+    for event in &events[..2] {
+        assert_eq!(event["event"], "copied");
+        let binary = event["binary"].as_str().unwrap();
+        assert_eq!(
+            event["target"],
+            dist.join(exe_filename(binary)).display().to_string()
+        );
+        copied.push(binary);
+    }
+    copied.sort();
+    assert_eq!(copied, [exe_filename("alpha"), exe_filename("beta")]);
+    let summary = &events[2];
+    assert_eq!(summary["event"], "summary");
+    assert_eq!(summary["status"], "ok");
+    assert_eq!(summary["copied_count"], 2);
+    assert!(stderr.is_empty(), "{}", String::from_utf8_lossy(&stderr));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_failing_pre_hook_aborts_before_copying() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["alpha", "beta"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        pre_hook: Some("systemctl --user is-active demo".to_string()),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.runner = Box::new(MockRunner {
+        calls: calls.clone(),
+        exit_code: 3,
+    });
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    drop(ctx);
+    assert!(err.to_string().contains("exited with code 3"), "{err}");
+    let code = err
+        .downcast_ref::<mdrcp::DeployError>()
+        .unwrap()
+        .exit_code();
+    assert_eq!(code, 5);
+
+    let calls = calls.borrow();
+    assert_eq!(calls.len(), 1);
+    let (program, args, env) = &calls[0];
+    assert_eq!(program, "sh");
+    assert_eq!(args, &["-c", "systemctl --user is-active demo"]);
+    let dist = temp_dir.path().join("dist");
+    assert_eq!(env["MDRCP_TARGET_DIR"], dist.as_os_str());
+    assert_eq!(
+        env["MDRCP_BINARIES"],
+        format!("{} {}", exe_filename("alpha"), exe_filename("beta")).as_str()
+    );
+
+    let summary: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["status"], "failed");
+    assert_eq!(summary["copied_binaries"], serde_json::json!([]));
+    assert!(summary["aborted"]
+        .as_str()
+        .unwrap()
+        .contains("exited with code 3"));
+    assert!(!dist.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_pre_hook_runs_through_shell_before_copy() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+
+    let log = temp_dir.path().join("hook.log");
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        pre_hook: Some(format!(
+            "echo \"$MDRCP_BINARIES\" > '{}'; test ! -e \"$MDRCP_TARGET_DIR/demo\"",
+            log.display()
+        )),
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+
+    assert_eq!(fs::read_to_string(&log).unwrap(), "demo\n");
+    assert!(temp_dir.path().join("dist").join("demo").exists());
+    let summary: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["status"], "ok");
+    assert!(summary.get("aborted").is_none());
+}
+
+#[test]
+fn test_backup_dir_collects_backups_in_timestamped_folder() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "new").unwrap();
+
+    let deploy = || {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            on_conflict: ConflictPolicy::Backup,
+            backup_dir: Some("backups".into()),
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    // Nothing to back up yet: no folder is created.
+    let summary = deploy();
+    let backups = temp_dir.path().join("backups");
+    assert!(!backups.exists());
+    assert!(summary.get("backup_dir").is_none());
+
+    let dist = temp_dir.path().join("dist");
+    let exe = exe_filename("demo");
+    fs::write(dist.join(&exe), "old").unwrap();
+    let summary = deploy();
+    let stamps: Vec<PathBuf> = fs::read_dir(&backups)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(stamps.len(), 1, "{stamps:?}");
+    let stamp = &stamps[0];
+    assert_eq!(summary["backup_dir"], stamp.display().to_string());
+    assert_eq!(fs::read_to_string(stamp.join(&exe)).unwrap(), "old");
+    assert_eq!(
+        summary["backed_up"],
+        serde_json::json!([stamp.join(&exe).display().to_string()])
+    );
+    assert_eq!(fs::read_to_string(dist.join(&exe)).unwrap(), "new");
+    assert!(!dist.join(format!("{}.bak", exe)).exists());
+}
+
+#[test]
+fn test_timings_add_duration_only_when_requested() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["alpha", "beta"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let deploy = |timings: bool, summary: SummaryFormat| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary,
+            timings,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        String::from_utf8(stdout).unwrap()
+    };
+
+    let summary: Value = serde_json::from_str(&deploy(false, SummaryFormat::Json)).unwrap();
+    assert!(summary.get("copied").is_none());
+
+    let summary: Value = serde_json::from_str(&deploy(true, SummaryFormat::Json)).unwrap();
+    let copied = summary["copied"].as_array().unwrap();
+    assert_eq!(copied.len(), 2);
+    for entry in copied {
+        assert!(entry["duration_ms"].is_u64(), "{entry}");
+        assert!(entry.get("sha256").is_none());
+    }
+
+    let text = deploy(true, SummaryFormat::Text);
+    let heading = text.find("Slowest copies:").expect(&text);
+    assert!(text[heading..].contains(&exe_filename("alpha")), "{text}");
+    assert!(text[heading..].contains(" ms"), "{text}");
+}
+
+#[test]
+fn test_failure_in_one_target_names_that_target() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+    // A directory where the staging copy should land makes that target fail.
+    let staging = temp_dir.path().join("staging");
+    fs::create_dir_all(staging.join(exe_filename("alpha"))).unwrap();
+
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("prod")),
+        extra_targets: vec![PathBuf::from("staging")],
+        summary: SummaryFormat::Json,
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    assert_eq!(
+        options.targets().collect::<Vec<_>>(),
+        [&PathBuf::from("prod"), &PathBuf::from("staging")]
+    );
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    drop(ctx);
+    assert!(err.to_string().contains("1 of 2 targets"), "{err}");
+    // The failing target's exit code survives, not a generic 1.
+    let code = err
+        .downcast_ref::<mdrcp::DeployError>()
+        .map(|e| e.exit_code());
+    assert_eq!(code, Some(5));
+
+    assert!(temp_dir
+        .path()
+        .join("prod")
+        .join(exe_filename("alpha"))
+        .exists());
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["status"], "failed");
+    assert_eq!(summary["target_dirs"].as_array().unwrap().len(), 2);
+    assert_eq!(summary["copied_count"], 1);
+    let error = summary["failed_binaries"][0]["error"].as_str().unwrap();
+    let dest = staging.join(exe_filename("alpha"));
+    assert!(error.contains(&dest.display().to_string()), "{error}");
+}
+
+#[test]
+fn test_check_fresh_flags_binaries_older_than_sources() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let module = temp_dir.path().join("src").join("net").join("mod.rs");
+    fs::create_dir_all(module.parent().unwrap()).unwrap();
+    create_and_write_file(&module, "pub fn connect() {}").unwrap();
+    // Only the nested module is newer than the binary below.
+    File::options()
+        .write(true)
+        .open(temp_dir.path().join("Cargo.toml"))
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() - std::time::Duration::from_secs(3600))
+        .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let exe = rel.join(exe_filename("demo"));
+    create_and_write_file(&exe, "demo").unwrap();
+    let set_built = |offset: i64| {
+        let now = std::time::SystemTime::now();
+        let delta = std::time::Duration::from_secs(offset.unsigned_abs());
+        let mtime = if offset < 0 { now - delta } else { now + delta };
+        fs::File::options()
+            .write(true)
+            .open(&exe)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    };
+
+    let deploy = |check_fresh: FreshnessCheck| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            quiet: true,
+            check_fresh,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        (result, stdout)
+    };
+
+    // Built a minute before src/net/mod.rs was last edited.
+    set_built(-60);
+    let (result, _) = deploy(FreshnessCheck::Strict);
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("may be stale"), "{err}");
+    assert!(err.contains("mod.rs"), "{err}");
+    assert!(!temp_dir.path().join("dist").exists());
+
+    let (result, stdout) = deploy(FreshnessCheck::Warn);
+    result.unwrap();
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["copied_count"], 1);
+    assert_eq!(summary["warnings"][0]["code"], "stale_binary");
+
+    // Rebuilt after the edit: nothing to report.
+    set_built(60);
+    let (result, stdout) = deploy(FreshnessCheck::Strict);
+    result.unwrap();
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["warnings"], serde_json::json!([]));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_build_runs_cargo_and_failure_copies_nothing() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let built = temp_dir
+        .path()
+        .join("target")
+        .join("x86_64-unknown-linux-musl")
+        .join("release");
+    fs::create_dir_all(&built).unwrap();
+    create_and_write_file(&built.join(exe_filename("demo")), "demo").unwrap();
+
+    let deploy = |exit_code: i32| {
+        let calls = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            build: true,
+            target_triple: Some("x86_64-unknown-linux-musl".to_string()),
+            quiet: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.runner = Box::new(MockRunner {
+            calls: calls.clone(),
+            exit_code,
+        });
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        let calls = calls.borrow().clone();
+        (result, calls)
+    };
+
+    let (result, calls) = deploy(101);
+    let err = format!("{:#}", result.unwrap_err());
+    assert!(
+        err.contains("cargo build --release --target x86_64-unknown-linux-musl"),
+        "{err}"
+    );
+    assert!(err.contains("mock failure"), "{err}");
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].0, "cargo");
+    assert!(!temp_dir.path().join("dist").exists());
+
+    let (result, calls) = deploy(0);
+    result.unwrap();
+    assert_eq!(calls.len(), 1);
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("demo"))
+        .exists());
+}
+
+#[cfg(feature = "toolchain-tests")]
+#[test]
+fn test_build_compiles_with_real_toolchain() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"hello\"\nversion=\"0.1.0\"\nedition=\"2021\"\n\n[workspace]",
+    )
+    .unwrap();
+    fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("src").join("main.rs"),
+        "fn main() { println!(\"hello\"); }",
+    )
+    .unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        build: true,
+        profile: BuildProfile::Debug,
+        // Keep the build inside the temp dir whatever the caller's setup.
+        cargo_target_dir: Some("target".into()),
+        quiet: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("hello"))
+        .exists());
+}
+
+#[test]
+fn test_interactive_prompt_skips_declined_overwrites() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let dist = temp_dir.path().join("dist");
+    fs::create_dir_all(&dist).unwrap();
+    for name in ["alpha", "beta"] {
+        create_and_write_file(&rel.join(exe_filename(name)), "new").unwrap();
+        create_and_write_file(&dist.join(exe_filename(name)), "old").unwrap();
+    }
+
+    let deploy = |answers: &str, terminal: bool, yes: bool| {
+        for name in ["alpha", "beta"] {
+            fs::write(dist.join(exe_filename(name)), "old").unwrap();
+        }
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some("dist".into()),
+            summary: SummaryFormat::Json,
+            interactive: true,
+            yes,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.stdin = Box::new(std::io::Cursor::new(answers.as_bytes().to_vec()));
+        ctx.stdin_is_terminal = terminal;
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        let summary: Value = serde_json::from_slice(&stdout).unwrap();
+        (summary, String::from_utf8(stderr).unwrap())
+    };
+
+    // "y" for alpha, "n" for beta.
+    let (summary, prompts) = deploy("y\nn\n", true, false);
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("alpha")])
+    );
+    assert_eq!(
+        summary["skipped_binaries"],
+        serde_json::json!([exe_filename("beta")])
+    );
+    assert_eq!(prompts.matches("? [y/N] ").count(), 2, "{prompts}");
+    assert!(prompts.contains(&format!(
+        "Overwrite {}?",
+        dist.join(exe_filename("beta")).display()
+    )));
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("beta"))).unwrap(),
+        "old"
+    );
+
+    // --yes and a non-terminal stdin both confirm without asking.
+    for (terminal, yes) in [(true, true), (false, false)] {
+        let (summary, prompts) = deploy("", terminal, yes);
+        assert_eq!(summary["copied_count"], 2);
+        assert!(!prompts.contains("Overwrite"), "{prompts}");
+    }
+
+    // Buffered runs could never show the prompt, so they refuse to start.
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some("dist".into()),
+        extra_targets: vec!["dist2".into()],
+        interactive: true,
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.stdin_is_terminal = true;
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(err.to_string().contains("--interactive"), "{err}");
+    assert!(!temp_dir.path().join("dist2").exists());
+}
+
+#[test]
+fn test_manifest_metadata_target_rename_exclude_under_overrides() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"tool\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"tool\"\n\n[[bin]]\nname=\"tool-debug\"\n\n[package.metadata.mdrcp]\ntarget=\"out\"\nrename={ tool = \"tl\" }\nexclude=[\"*-debug\"]",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["tool", "tool-debug"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let deploy = |options: &RunOptions, env_target: Option<&Path>| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.env
+            .insert("MD_TARGET_DIR".into(), temp_dir.path().join("home").into());
+        if let Some(dir) = env_target {
+            ctx.env.insert("MDRCP_TARGET".into(), dir.into());
+        }
+        run_with_options(temp_dir.path(), options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+    let options = RunOptions {
+        summary: SummaryFormat::Json,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+
+    // Metadata beats the OS default: renamed, excluded, and sent to `out`.
+    let summary = deploy(&options, None);
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("tl")])
+    );
+    let out = temp_dir.path().join("out");
+    assert_eq!(
+        fs::read_to_string(out.join(exe_filename("tl"))).unwrap(),
+        "tool"
+    );
+    assert!(!out.join(exe_filename("tool-debug")).exists());
+
+    // $MDRCP_TARGET and --target both beat the metadata `target`.
+    let env_dir = temp_dir.path().join("from-env");
+    deploy(&options, Some(&env_dir));
+    assert!(env_dir.join(exe_filename("tl")).exists());
+    let cli_dir = temp_dir.path().join("from-cli");
+    let options = RunOptions {
+        target_override: Some(cli_dir.clone()),
+        ..options
+    };
+    deploy(&options, Some(&env_dir));
+    assert!(cli_dir.join(exe_filename("tl")).exists());
+}
+
+#[test]
+fn test_duplicate_binary_names_across_members() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[workspace]\nmembers=[\"a\",\"b\"]",
+    )
+    .unwrap();
+    for m in ["a", "b"] {
+        fs::create_dir_all(temp_dir.path().join(m)).unwrap();
+        create_and_write_file(
+            &temp_dir.path().join(m).join("Cargo.toml"),
+            &format!("[package]\nname=\"{m}\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"helper\""),
+        )
+        .unwrap();
+    }
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("helper")), "helper").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut options = RunOptions {
+        target_override: Some(PathBuf::from("dist")),
+        summary: SummaryFormat::Json,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    let err = run_with_options(temp_dir.path(), &options, &mut ctx).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("duplicate binary name 'helper' in members a and b"),
+        "{err}"
+    );
+    assert!(!temp_dir.path().join("dist").exists());
+
+    options.allow_duplicates = true;
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+    drop(ctx);
+    let summary: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["copied_count"], 1);
+    assert_eq!(summary["warnings"][0]["code"], "duplicate_binary");
+    assert!(summary["warnings"][0]["message"]
+        .as_str()
+        .unwrap()
+        .ends_with("deploying the one from a"));
+}
+
+#[test]
+fn test_summary_carries_schema_and_tool_version() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"alpha\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("alpha")), "alpha bytes").unwrap();
+
+    for format in [SummaryFormat::Json, SummaryFormat::JsonPretty] {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: format,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+        let out = String::from_utf8(stdout).unwrap();
+        assert_eq!(
+            out.trim_end().lines().count() == 1,
+            format == SummaryFormat::Json
+        );
+        let summary: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(summary["schema_version"], mdrcp::SUMMARY_SCHEMA_VERSION);
+        assert_eq!(
+            summary["tool_version"],
+            mdrcp::cli::version_metadata().version
+        );
+    }
+}
+
+#[test]
+fn test_bins_from_stdin_narrows_deploy() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"alpha\"\n\n[[bin]]\nname=\"beta\"\n\n[[bin]]\nname=\"gamma\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["alpha", "beta", "gamma"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let deploy = |input: &str| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: SummaryFormat::Json,
+            bins_from: Some(PathBuf::from("-")),
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        ctx.stdin = Box::new(std::io::Cursor::new(input.as_bytes().to_vec()));
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        result.map(|()| serde_json::from_slice::<Value>(&stdout).unwrap())
+    };
+
+    let summary = deploy("# from cargo metadata\nalpha\n\n  gamma  # trailing note\n").unwrap();
+    assert_eq!(
+        summary["copied_binaries"],
+        serde_json::json!([exe_filename("alpha"), exe_filename("gamma")])
+    );
+
+    let err = deploy("# nothing\n\n").unwrap_err();
+    assert!(err.to_string().contains("listed no binary names"), "{err}");
+    let err = deploy("delta\n").unwrap_err();
+    assert!(err.to_string().contains("'delta' not declared"), "{err}");
+}
+
+#[test]
+fn test_github_format_annotates_failures_and_success() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "just some notes").unwrap();
+
+    let deploy = |options: &RunOptions| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let ok = run_with_options(temp_dir.path(), options, &mut ctx).is_ok();
+        drop(ctx);
+        (
+            ok,
+            String::from_utf8(stdout).unwrap(),
+            String::from_utf8(stderr).unwrap(),
+        )
+    };
+
+    // The JSON summary keeps stdout, so annotations move to stderr.
+    let options = RunOptions {
+        ci_format: Some(CiFormat::Github),
+        summary: SummaryFormat::Json,
+        target_override: Some(PathBuf::from("dist")),
+        verify_format: true,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let (ok, stdout, stderr) = deploy(&options);
+    assert!(!ok);
+    serde_json::from_str::<Value>(&stdout).unwrap();
+    assert!(
+        stderr.contains(&format!(
+            "::error::Failed to copy {}: ",
+            exe_filename("demo")
+        )),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("::notice::"));
+
+    // Text summary: annotations on stdout; --format plain opts out.
+    let options = RunOptions {
+        summary: SummaryFormat::Text,
+        quiet: true,
+        verify_format: false,
+        ..options
+    };
+    let (ok, stdout, _) = deploy(&options);
+    assert!(ok);
+    assert_eq!(stdout, "::notice::Deployed 1 executables\n");
+    let options = RunOptions {
+        ci_format: Some(CiFormat::Plain),
+        ..options
+    };
+    assert_eq!(deploy(&options).1, "");
+
+    // The binary turns annotations on by itself under GITHUB_ACTIONS.
+    let run_bin = |extra: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_mdrcp"))
+            .current_dir(temp_dir.path())
+            .env("GITHUB_ACTIONS", "true")
+            .args(["--quiet", "--target", "dist"])
+            .args(["--suppress-warning", "not_on_path"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+    assert_eq!(run_bin(&[]), "::notice::Deployed 1 executables\n");
+    assert_eq!(run_bin(&["--format", "plain"]), "");
+}
+
+#[test]
+fn test_target_equal_to_release_dir_is_refused() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo bytes").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_override: Some(PathBuf::from("target/release")),
+        summary: SummaryFormat::Json,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    assert!(run_with_options(temp_dir.path(), &options, &mut ctx).is_err());
+    drop(ctx);
+
+    let json: Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(json["status"], "failed");
+    assert_eq!(json["failed_binaries"][0]["binary"], exe_filename("demo"));
+    assert!(json["failed_binaries"][0]["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("source and destination are the same file"));
+    assert_eq!(
+        fs::read_to_string(rel.join(exe_filename("demo"))).unwrap(),
+        "demo bytes"
+    );
+}
+
+#[test]
+fn test_preserve_timestamps_copies_source_mtime() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let source = rel.join(exe_filename("demo"));
+    create_and_write_file(&source, "demo bytes").unwrap();
+    let built = std::time::SystemTime::now() - std::time::Duration::from_secs(86_400);
+    fs::File::options()
+        .write(true)
+        .open(&source)
+        .unwrap()
+        .set_times(fs::FileTimes::new().set_modified(built).set_accessed(built))
+        .unwrap();
+
+    let deploy = |preserve_timestamps: bool| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: SummaryFormat::Json,
+            preserve_timestamps,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        let summary: Value = serde_json::from_slice(&stdout).unwrap();
+        assert_eq!(summary["warnings"], serde_json::json!([]));
+        fs::metadata(temp_dir.path().join("dist").join(exe_filename("demo")))
+            .unwrap()
+            .modified()
+            .unwrap()
+    };
+
+    assert_ne!(deploy(false), built);
+    assert_eq!(deploy(true), built);
+}
+
+#[test]
+fn test_prune_removes_only_recorded_binaries() {
+    let temp_dir = tempdir().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["old-name", "keeper", "new-name"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+    let dist = temp_dir.path().join("dist");
+
+    let deploy = |bins: &[&str], extra: &[&str]| {
+        let declared: String = bins
+            .iter()
+            .map(|name| format!("\n\n[[bin]]\nname=\"{name}\""))
+            .collect();
+        fs::write(
+            &manifest,
+            format!("[package]\nname=\"demo\"\nversion=\"0.1.0\"{declared}"),
+        )
+        .unwrap();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: SummaryFormat::Json,
+            prune: true,
+            only: extra.iter().map(|s| s.to_string()).collect(),
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+        drop(ctx);
+        result.map(|()| serde_json::from_slice::<Value>(&stdout).unwrap())
+    };
+
+    // First run records what it installed; a stranger's file sits beside it.
+    deploy(&["old-name", "keeper"], &[]).unwrap();
+    create_and_write_file(&dist.join("not-ours"), "hands off").unwrap();
+
+    // The crate is renamed: old-name goes, keeper and not-ours stay.
+    let summary = deploy(&["new-name", "keeper"], &[]).unwrap();
+    assert_eq!(
+        summary["pruned"],
+        serde_json::json!([exe_filename("old-name")])
+    );
+    assert!(!dist.join(exe_filename("old-name")).exists());
+    assert!(dist.join(exe_filename("keeper")).exists());
+    assert!(dist.join(exe_filename("new-name")).exists());
+    assert!(dist.join("not-ours").exists());
+
+    // Nothing left to prune; narrowing with --bin is refused.
+    let summary = deploy(&["new-name", "keeper"], &[]).unwrap();
+    assert!(summary.get("pruned").is_none());
+    let err = deploy(&["new-name", "keeper"], &["keeper"]).unwrap_err();
+    assert!(
+        err.to_string().contains("--prune cannot be combined"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_prune_leaves_files_the_record_cannot_vouch_for() {
+    use mdrcp::target_manifest::{read_target_manifest, write_target_manifest};
+
+    let temp_dir = tempdir().unwrap();
+    let manifest = temp_dir.path().join("Cargo.toml");
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["demo", "edited"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+    let dist = temp_dir.path().join("dist");
+
+    let deploy = |bins: &[&str]| {
+        let declared: String = bins
+            .iter()
+            .map(|name| format!("\n\n[[bin]]\nname=\"{name}\""))
+            .collect();
+        fs::write(
+            &manifest,
+            format!("[package]\nname=\"demo\"\nversion=\"0.1.0\"{declared}"),
+        )
+        .unwrap();
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: SummaryFormat::Json,
+            prune: true,
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    deploy(&["demo", "edited"]);
+    // Someone patches a deployed binary in place, and the record is edited
+    // to claim a file outside every directory the deploy writes to.
+    fs::write(dist.join(exe_filename("edited")), "local patch").unwrap();
+    let elsewhere = temp_dir.path().join("elsewhere");
+    fs::create_dir_all(&elsewhere).unwrap();
+    let ls = elsewhere.join(exe_filename("ls"));
+    create_and_write_file(&ls, "system tool").unwrap();
+    let mut record = read_target_manifest(&dist).unwrap();
+    record.binaries.push(exe_filename("ls"));
+    record.destinations.insert(exe_filename("ls"), ls.clone());
+    write_target_manifest(&dist, &record).unwrap();
+
+    let summary = deploy(&["demo"]);
+    assert!(summary.get("pruned").is_none());
+    assert_eq!(
+        fs::read_to_string(dist.join(exe_filename("edited"))).unwrap(),
+        "local patch"
+    );
+    assert!(ls.exists());
+    let warnings: Vec<&str> = summary["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(warnings, ["prune_failed", "prune_failed"]);
+    // Files it refused are dropped from the record, so later runs stay quiet.
+    assert_eq!(
+        read_target_manifest(&dist).unwrap().binaries,
+        vec![exe_filename("demo")]
+    );
+}
+
+#[test]
+fn test_prune_follows_recorded_dest_overrides() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"main\"\n\n[[bin]]\nname=\"other\"",
+    )
+    .unwrap();
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    for name in ["main", "other"] {
+        create_and_write_file(&rel.join(exe_filename(name)), name).unwrap();
+    }
+
+    let deploy = |exclude: &[&str]| {
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let options = RunOptions {
+            target_override: Some(PathBuf::from("dist")),
+            summary: SummaryFormat::Json,
+            prune: true,
+            exclude: exclude.iter().map(|s| s.to_string()).collect(),
+            dest_overrides: [("other".to_string(), PathBuf::from("custom"))].into(),
+            suppress_warnings: vec![WarningCode::NotOnPath],
+            ..Default::default()
+        };
+        let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+        run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+        drop(ctx);
+        serde_json::from_slice::<Value>(&stdout).unwrap()
+    };
+
+    deploy(&[]);
+    let custom = temp_dir.path().join("custom").join(exe_filename("other"));
+    assert!(custom.exists());
+
+    let summary = deploy(&["other"]);
+    assert_eq!(
+        summary["pruned"],
+        serde_json::json!([exe_filename("other")])
+    );
+    assert!(!custom.exists());
+    assert!(temp_dir
+        .path()
+        .join("dist")
+        .join(exe_filename("main"))
+        .exists());
+}
+
+fn deploy_with_platform_targets(temp_dir: &Path) -> Value {
+    create_and_write_file(
+        &temp_dir.join("Cargo.toml"),
+        "[package]\nname=\"demo\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+    let rel = temp_dir.join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    create_and_write_file(&rel.join(exe_filename("demo")), "demo").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let options = RunOptions {
+        target_unix: Some(PathBuf::from("unix-bin")),
+        target_windows: Some(PathBuf::from("windows-bin")),
+        summary: SummaryFormat::Json,
+        suppress_warnings: vec![WarningCode::NotOnPath],
+        ..Default::default()
+    };
+    let mut ctx = mdrcp::CliContext::new(&mut stdout, &mut stderr);
+    ctx.env
+        .insert("MD_TARGET_DIR".into(), temp_dir.join("env-bin").into());
+    run_with_options(temp_dir, &options, &mut ctx).unwrap();
+    drop(ctx);
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+#[cfg(not(windows))]
+#[test]
+fn test_target_unix_replaces_default_on_unix() {
+    let temp_dir = tempdir().unwrap();
+    let summary = deploy_with_platform_targets(temp_dir.path());
+    let unix_bin = temp_dir.path().join("unix-bin");
+    assert_eq!(summary["target_dir"], unix_bin.display().to_string());
+    assert_eq!(summary["override_used"], false);
+    assert!(unix_bin.join("demo").exists());
+    assert!(!temp_dir.path().join("windows-bin").exists());
+    assert!(!temp_dir.path().join("env-bin").exists());
+}
+
+#[cfg(windows)]
+#[test]
+fn test_target_windows_replaces_default_on_windows() {
+    let temp_dir = tempdir().unwrap();
+    let summary = deploy_with_platform_targets(temp_dir.path());
+    let windows_bin = temp_dir.path().join("windows-bin");
+    assert_eq!(summary["target_dir"], windows_bin.display().to_string());
+    assert_eq!(summary["override_used"], false);
+    assert!(windows_bin.join("demo.exe").exists());
+    assert!(!temp_dir.path().join("unix-bin").exists());
+    assert!(!temp_dir.path().join("env-bin").exists());
+}
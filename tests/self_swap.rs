@@ -1,4 +1,6 @@
-use mdrcp::{run_with_options, BuildProfile, CliContext, RunOptions};
+use mdrcp::{
+    run_with_options, BuildProfile, CliContext, RunOptions, SelfUpdateStrategy, SummaryFormat,
+};
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::Path;
@@ -67,3 +69,188 @@ fn test_self_update_trigger() {
     assert!(output_out.contains("Deferred"));
     assert!(output_err.contains("Failed to self-update"));
 }
+
+#[test]
+fn test_skip_self_leaves_running_binary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"myapp\"\nversion=\"0.1.0\"\n\n[[bin]]\nname=\"helper\"",
+    )
+    .unwrap();
+
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let exe = exe_filename("myapp");
+    create_and_write_file(&rel.join(&exe), "new content").unwrap();
+    create_and_write_file(&rel.join(exe_filename("helper")), "helper").unwrap();
+
+    let install_dir = temp_dir.path().join("install");
+    fs::create_dir_all(&install_dir).unwrap();
+    let installed_exe = install_dir.join(&exe);
+    create_and_write_file(&installed_exe, "old content").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.current_exe = Some(installed_exe.clone());
+
+    let options = RunOptions {
+        target_override: Some(install_dir.clone()),
+        self_update: SelfUpdateStrategy::Skip,
+        ..Default::default()
+    };
+
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let output_out = String::from_utf8(stdout).unwrap();
+    let output_err = String::from_utf8(stderr).unwrap();
+    assert!(!output_out.contains("Deferred"));
+    assert!(output_err.contains("running mdrcp executable"));
+    assert_eq!(fs::read_to_string(&installed_exe).unwrap(), "old content");
+    assert!(install_dir.join(exe_filename("helper")).exists());
+}
+
+#[test]
+fn test_replace_strategy_swaps_without_helper() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"myapp\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let exe = exe_filename("myapp");
+    create_and_write_file(&rel.join(&exe), "new content").unwrap();
+
+    let install_dir = temp_dir.path().join("install");
+    fs::create_dir_all(&install_dir).unwrap();
+    let installed_exe = install_dir.join(&exe);
+    create_and_write_file(&installed_exe, "old content").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.current_exe = Some(installed_exe.clone());
+
+    let options = RunOptions {
+        target_override: Some(install_dir.clone()),
+        self_update: SelfUpdateStrategy::Replace,
+        ..Default::default()
+    };
+
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let output_out = String::from_utf8(stdout).unwrap();
+    assert!(output_out.contains("Replaced"));
+    assert!(!output_out.contains("Deferred"));
+    assert_eq!(fs::read_to_string(&installed_exe).unwrap(), "new content");
+}
+
+#[test]
+fn test_self_update_failure_in_json_summary() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"myapp\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let exe = exe_filename("myapp");
+    create_and_write_file(&rel.join(&exe), "new content").unwrap();
+
+    let install_dir = temp_dir.path().join("install");
+    fs::create_dir_all(&install_dir).unwrap();
+    let installed_exe = install_dir.join(&exe);
+    create_and_write_file(&installed_exe, "old content").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    // The "running" binary is a text file, so spawning the helper fails.
+    ctx.current_exe = Some(installed_exe.clone());
+
+    let options = RunOptions {
+        target_override: Some(install_dir.clone()),
+        summary: SummaryFormat::Json,
+        quiet: true,
+        ..Default::default()
+    };
+
+    let result = run_with_options(temp_dir.path(), &options, &mut ctx);
+    assert!(result.is_err());
+
+    let summary: serde_json::Value = serde_json::from_slice(&stdout).unwrap();
+    assert_eq!(summary["self_update"]["attempted"], true);
+    assert_eq!(summary["self_update"]["status"], "failed");
+    assert!(!summary["self_update"]["error"].as_str().unwrap().is_empty());
+}
+
+#[test]
+fn test_no_self_update_takes_normal_copy_path() {
+    let temp_dir = tempdir().unwrap();
+    create_and_write_file(
+        &temp_dir.path().join("Cargo.toml"),
+        "[package]\nname=\"myapp\"\nversion=\"0.1.0\"",
+    )
+    .unwrap();
+
+    let rel = temp_dir.path().join("target").join("release");
+    fs::create_dir_all(&rel).unwrap();
+    let exe = exe_filename("myapp");
+    create_and_write_file(&rel.join(&exe), "new content").unwrap();
+
+    let install_dir = temp_dir.path().join("install");
+    fs::create_dir_all(&install_dir).unwrap();
+    let installed_exe = install_dir.join(&exe);
+    create_and_write_file(&installed_exe, "old content").unwrap();
+
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let mut ctx = CliContext::new(&mut stdout, &mut stderr);
+    ctx.current_exe = Some(installed_exe.clone());
+
+    let options = RunOptions {
+        target_override: Some(install_dir.clone()),
+        self_update: SelfUpdateStrategy::Copy,
+        ..Default::default()
+    };
+
+    run_with_options(temp_dir.path(), &options, &mut ctx).unwrap();
+
+    let output_out = String::from_utf8(stdout).unwrap();
+    assert!(output_out.contains("Copied"));
+    assert!(!output_out.contains("Deferred"));
+    assert_eq!(fs::read_to_string(&installed_exe).unwrap(), "new content");
+}
+
+#[test]
+fn test_finish_update_waits_for_parent_to_exit() {
+    let temp_dir = tempdir().unwrap();
+    let source = temp_dir.path().join("new");
+    let dest = temp_dir.path().join("installed");
+    create_and_write_file(&source, "new content").unwrap();
+    create_and_write_file(&dest, "old content").unwrap();
+
+    // The deploying process holds the updater's stdin open until it exits.
+    let mut updater = std::process::Command::new(env!("CARGO_BIN_EXE_mdrcp"))
+        .arg("--finish-update")
+        .arg(&source)
+        .arg(&dest)
+        .stdin(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    // Longer than the updater's whole retry window.
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "old content");
+    assert!(updater.try_wait().unwrap().is_none());
+
+    drop(updater.stdin.take());
+    assert!(updater.wait().unwrap().success());
+    assert_eq!(fs::read_to_string(&dest).unwrap(), "new content");
+}